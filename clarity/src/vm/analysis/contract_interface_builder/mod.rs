@@ -14,15 +14,18 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
 use stacks_common::types::StacksEpochId;
 
 use crate::vm::analysis::types::ContractAnalysis;
 use crate::vm::analysis::CheckResult;
+use crate::vm::functions::define::DefineFunctionsParsed;
+use crate::vm::functions::NativeFunctions;
+use crate::vm::representations::{SymbolicExpression, SymbolicExpressionType};
 use crate::vm::types::signatures::CallableSubtype;
 use crate::vm::types::{
-    FixedFunction, FunctionArg, FunctionType, TupleTypeSignature, TypeSignature,
+    FixedFunction, FunctionArg, FunctionType, TraitIdentifier, TupleTypeSignature, TypeSignature,
 };
 use crate::vm::{CheckErrors, ClarityName, ClarityVersion};
 
@@ -44,8 +47,8 @@ pub fn build_contract_interface(
         epoch: _,
         clarity_version: _,
         defined_traits: _,
-        implemented_traits: _,
-        expressions: _,
+        implemented_traits,
+        expressions,
         contract_identifier: _,
         type_map: _,
         cost_track: _,
@@ -74,6 +77,11 @@ pub fn build_contract_interface(
             ContractInterfaceFunctionAccess::read_only,
         )?);
 
+    let dynamic_callers = find_dynamic_contract_callers(expressions);
+    for function in contract_interface.functions.iter_mut() {
+        function.dynamic_contract_call = Some(dynamic_callers.contains(function.name.as_str()));
+    }
+
     contract_interface
         .variables
         .append(&mut ContractInterfaceVariable::from_map(
@@ -102,9 +110,103 @@ pub fn build_contract_interface(
             fungible_tokens,
         ));
 
+    contract_interface.implemented_traits =
+        Some(ContractInterfaceTrait::from_set(implemented_traits));
+
+    // a definition which cannot be parsed is skipped, as in `find_dynamic_contract_callers`
+    let mut used_traits = BTreeSet::new();
+    for exp in expressions.iter() {
+        if let Ok(Some(DefineFunctionsParsed::UseTrait {
+            trait_identifier, ..
+        })) = DefineFunctionsParsed::try_parse(exp)
+        {
+            used_traits.insert(trait_identifier.clone());
+        }
+    }
+    contract_interface.used_traits = Some(ContractInterfaceTrait::from_set(&used_traits));
+
     Ok(contract_interface)
 }
 
+/// Find the names of the contract's functions which can perform a dynamic `contract-call?`,
+///  i.e., one whose target is a trait-typed argument rather than a literal contract principal.
+/// A function is counted if it makes such a call itself, or if it refers to another function
+///  of the same contract which does (either by calling it, or by passing it to `map`, `fold`
+///  or `filter`). This is a conservative static check: a reference to a local binding which
+///  shadows a function name is treated as a reference to that function.
+/// This never fails: a definition which cannot be parsed is skipped, so its function is not
+///  flagged.
+fn find_dynamic_contract_callers(expressions: &[SymbolicExpression]) -> HashSet<ClarityName> {
+    let mut direct_callers = HashSet::new();
+    let mut references: HashMap<ClarityName, HashSet<ClarityName>> = HashMap::new();
+
+    for exp in expressions.iter() {
+        let (signature, body) = match DefineFunctionsParsed::try_parse(exp) {
+            Ok(Some(DefineFunctionsParsed::PrivateFunction { signature, body }))
+            | Ok(Some(DefineFunctionsParsed::ReadOnlyFunction { signature, body }))
+            | Ok(Some(DefineFunctionsParsed::PublicFunction { signature, body })) => {
+                (signature, body)
+            }
+            _ => continue,
+        };
+        let Some(function_name) = signature.first().and_then(|name| name.match_atom()) else {
+            continue;
+        };
+
+        let mut referenced_atoms = HashSet::new();
+        if collect_dynamic_contract_calls(body, &mut referenced_atoms) {
+            direct_callers.insert(function_name.clone());
+        }
+        references.insert(function_name.clone(), referenced_atoms);
+    }
+
+    // propagate through references to other functions until nothing changes
+    let mut callers = direct_callers;
+    loop {
+        let newly_found: Vec<_> = references
+            .iter()
+            .filter(|(name, referenced)| {
+                !callers.contains(*name) && referenced.iter().any(|r| callers.contains(r))
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+        if newly_found.is_empty() {
+            return callers;
+        }
+        callers.extend(newly_found);
+    }
+}
+
+/// Walk `expression`, recording every atom it contains in `referenced_atoms`.
+/// Returns true if `expression` contains a dynamic `contract-call?`.
+fn collect_dynamic_contract_calls(
+    expression: &SymbolicExpression,
+    referenced_atoms: &mut HashSet<ClarityName>,
+) -> bool {
+    match &expression.expr {
+        SymbolicExpressionType::Atom(name) => {
+            referenced_atoms.insert(name.clone());
+            false
+        }
+        SymbolicExpressionType::List(list) => {
+            let is_dynamic_call = match &list[..] {
+                [function, target, ..] => {
+                    function.match_atom().map(|name| name.as_str())
+                        == Some(NativeFunctions::ContractCall.get_name_str())
+                        && matches!(target.expr, SymbolicExpressionType::Atom(_))
+                }
+                _ => false,
+            };
+            let mut found = is_dynamic_call;
+            for child in list.iter() {
+                found |= collect_dynamic_contract_calls(child, referenced_atoms);
+            }
+            found
+        }
+        _ => false,
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ContractInterfaceFunctionAccess {
     private,
@@ -261,6 +363,11 @@ pub struct ContractInterfaceFunction {
     pub access: ContractInterfaceFunctionAccess,
     pub args: Vec<ContractInterfaceFunctionArg>,
     pub outputs: ContractInterfaceFunctionOutput,
+    /// Whether this function can perform a `contract-call?` on a trait-typed argument,
+    ///  and so may call into arbitrary other contracts. This is `None` for interfaces
+    ///  stored before this field existed, since they were never checked.
+    #[serde(default)]
+    pub dynamic_contract_call: Option<bool>,
 }
 
 impl ContractInterfaceFunction {
@@ -273,6 +380,7 @@ impl ContractInterfaceFunction {
                 Ok(ContractInterfaceFunction {
                     name: name.clone().into(),
                     access: access.to_owned(),
+                    dynamic_contract_call: None,
                     outputs: ContractInterfaceFunctionOutput {
                         type_f: match function_type {
                             FunctionType::Fixed(FixedFunction { returns, .. }) => {
@@ -317,6 +425,24 @@ pub struct ContractInterfaceVariable {
     pub access: ContractInterfaceVariableAccess,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContractInterfaceTrait {
+    pub contract_identifier: String,
+    pub name: String,
+}
+
+impl ContractInterfaceTrait {
+    fn from_set(traits: &BTreeSet<TraitIdentifier>) -> Vec<Self> {
+        traits
+            .iter()
+            .map(|trait_identifier| Self {
+                contract_identifier: trait_identifier.contract_identifier.to_string(),
+                name: trait_identifier.name.to_string(),
+            })
+            .collect()
+    }
+}
+
 impl ContractInterfaceFungibleTokens {
     pub fn from_set(tokens: &BTreeSet<ClarityName>) -> Vec<Self> {
         tokens
@@ -383,6 +509,14 @@ pub struct ContractInterface {
     pub maps: Vec<ContractInterfaceMap>,
     pub fungible_tokens: Vec<ContractInterfaceFungibleTokens>,
     pub non_fungible_tokens: Vec<ContractInterfaceNonFungibleTokens>,
+    /// Traits this contract declares with `impl-trait`. `None` for interfaces
+    ///  stored before this field existed.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub implemented_traits: Option<Vec<ContractInterfaceTrait>>,
+    /// Traits this contract imports with `use-trait`. `None` for interfaces
+    ///  stored before this field existed.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub used_traits: Option<Vec<ContractInterfaceTrait>>,
     pub epoch: StacksEpochId,
     pub clarity_version: ClarityVersion,
 }
@@ -395,6 +529,8 @@ impl ContractInterface {
             maps: Vec::new(),
             fungible_tokens: Vec::new(),
             non_fungible_tokens: Vec::new(),
+            implemented_traits: None,
+            used_traits: None,
             epoch,
             clarity_version,
         }
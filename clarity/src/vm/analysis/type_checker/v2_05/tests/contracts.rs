@@ -173,29 +173,36 @@ fn test_names_tokens_contracts_interface() {
     let test_contract_json_expected: serde_json::Value = serde_json::from_str(r#"{
         "epoch": "Epoch2_05",
         "clarity_version": "Clarity1",
+        "implemented_traits": [],
+        "used_traits": [],
         "functions": [
             { "name": "f00",
                 "access": "private",
+                "dynamic_contract_call": false,
                 "args": [{ "name": "a1", "type": "int128" }],
                 "outputs": { "type": "bool" }
             },
             { "name": "f01",
                 "access": "private",
+                "dynamic_contract_call": false,
                 "args": [{ "name": "a1", "type": "bool" }],
                 "outputs": { "type": "bool" }
             },
             { "name": "f02",
                 "access": "private",
+                "dynamic_contract_call": false,
                 "args": [{ "name": "a1", "type": "principal" }],
                 "outputs": { "type": "bool" }
             },
             { "name": "f03",
                 "access": "private",
+                "dynamic_contract_call": false,
                 "args": [{ "name": "a1", "type": { "buffer": { "length": 54 } } }],
                 "outputs": { "type": "bool" }
             },
             { "name": "f04",
                 "access": "private",
+                "dynamic_contract_call": false,
                 "args": [{ "name": "a1", "type": { "tuple": [
                     { "name": "t-name1", "type": "bool" },
                     { "name": "t-name2", "type": "int128" }
@@ -204,31 +211,37 @@ fn test_names_tokens_contracts_interface() {
             },
             { "name": "f05",
                 "access": "private",
+                "dynamic_contract_call": false,
                 "args": [{ "name": "a1", "type": { "list": { "type": { "list": { "type": "int128", "length": 3 } }, "length": 7 } } }],
                 "outputs": { "type": "bool" }
             },
             { "name": "f06",
                 "access": "private",
+                "dynamic_contract_call": false,
                 "args": [],
                 "outputs": { "type": "int128" }
             },
             { "name": "f07",
                 "access": "private",
+                "dynamic_contract_call": false,
                 "args": [],
                 "outputs": { "type": "bool" }
             },
             { "name": "f08",
                 "access": "private",
+                "dynamic_contract_call": false,
                 "args": [],
                 "outputs": { "type": "principal" }
             },
             { "name": "f09",
                 "access": "private",
+                "dynamic_contract_call": false,
                 "args": [],
                 "outputs": { "type": { "buffer": { "length": 4 } } }
             },
             { "name": "f10",
                 "access": "private",
+                "dynamic_contract_call": false,
                 "args": [],
                 "outputs": { "type": { "tuple": [
                     { "name": "tn1", "type": "bool" },
@@ -238,6 +251,7 @@ fn test_names_tokens_contracts_interface() {
             },
             { "name": "f11",
                 "access": "private",
+                "dynamic_contract_call": false,
                 "args": [],
                 "outputs": { "type": { "optional": { "tuple": [ {
                     "name": "owner",
@@ -246,26 +260,31 @@ fn test_names_tokens_contracts_interface() {
             },
             { "name": "f12",
                 "access": "private",
+                "dynamic_contract_call": false,
                 "args": [],
                 "outputs": { "type": { "response": { "ok": "int128", "error": "none" } } }
             },
             { "name": "f13",
                 "access": "private",
+                "dynamic_contract_call": false,
                 "args": [],
                 "outputs": { "type": { "response": { "ok": "none", "error": "int128" } } }
             },
             { "name": "f14",
                 "access": "private",
+                "dynamic_contract_call": false,
                 "args": [],
                 "outputs": { "type": { "response": { "ok": "int128", "error": "int128" } } }
             },
             { "name": "f15",
                 "access": "private",
+                "dynamic_contract_call": false,
                 "args": [],
                 "outputs": { "type": { "list": { "type": "int128", "length": 3 } } }
             },
             { "name": "f16",
                 "access": "private",
+                "dynamic_contract_call": false,
                 "args": [],
                 "outputs": {
                   "type": { "list": {
@@ -279,36 +298,43 @@ fn test_names_tokens_contracts_interface() {
             },
             { "name": "pub-f01",
                 "access": "public",
+                "dynamic_contract_call": false,
                 "args": [],
                 "outputs": { "type": { "response": { "ok": "int128", "error": "none" } } }
             },
             { "name": "pub-f02",
                 "access": "public",
+                "dynamic_contract_call": false,
                 "args": [],
                 "outputs": { "type": { "response": { "ok": "bool", "error": "none" } } }
             },
             { "name": "pub-f03",
                 "access": "public",
+                "dynamic_contract_call": false,
                 "args": [],
                 "outputs": { "type": { "response": { "ok": "none", "error": "bool" } } }
             },
             { "name": "pub-f04",
                 "access": "public",
+                "dynamic_contract_call": false,
                 "args": [],
                 "outputs": { "type": { "response": { "ok": "int128", "error": "int128" } } }
             },
             { "name": "pub-f05",
                 "access": "public",
+                "dynamic_contract_call": false,
                 "args": [{ "name": "a1", "type": "int128" }],
                 "outputs": { "type": { "response": { "ok": "bool", "error": "none" } } }
             },
             { "name": "ro-f01",
                 "access": "read_only",
+                "dynamic_contract_call": false,
                 "args": [],
                 "outputs": { "type": "int128" }
             },
             { "name": "ro-f02",
                 "access": "read_only",
+                "dynamic_contract_call": false,
                 "args": [{ "name": "a1", "type": "int128" }],
                 "outputs": { "type": "int128" }
             }
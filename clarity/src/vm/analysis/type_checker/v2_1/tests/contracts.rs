@@ -225,26 +225,31 @@ fn test_names_tokens_contracts_interface() {
         "functions": [
             { "name": "f00",
                 "access": "private",
+                "dynamic_contract_call": false,
                 "args": [{ "name": "a1", "type": "int128" }],
                 "outputs": { "type": "bool" }
             },
             { "name": "f01",
                 "access": "private",
+                "dynamic_contract_call": false,
                 "args": [{ "name": "a1", "type": "bool" }],
                 "outputs": { "type": "bool" }
             },
             { "name": "f02",
                 "access": "private",
+                "dynamic_contract_call": false,
                 "args": [{ "name": "a1", "type": "principal" }],
                 "outputs": { "type": "bool" }
             },
             { "name": "f03",
                 "access": "private",
+                "dynamic_contract_call": false,
                 "args": [{ "name": "a1", "type": { "buffer": { "length": 54 } } }],
                 "outputs": { "type": "bool" }
             },
             { "name": "f04",
                 "access": "private",
+                "dynamic_contract_call": false,
                 "args": [{ "name": "a1", "type": { "tuple": [
                     { "name": "t-name1", "type": "bool" },
                     { "name": "t-name2", "type": "int128" }
@@ -253,31 +258,37 @@ fn test_names_tokens_contracts_interface() {
             },
             { "name": "f05",
                 "access": "private",
+                "dynamic_contract_call": false,
                 "args": [{ "name": "a1", "type": { "list": { "type": { "list": { "type": "int128", "length": 3 } }, "length": 7 } } }],
                 "outputs": { "type": "bool" }
             },
             { "name": "f06",
                 "access": "private",
+                "dynamic_contract_call": false,
                 "args": [],
                 "outputs": { "type": "int128" }
             },
             { "name": "f07",
                 "access": "private",
+                "dynamic_contract_call": false,
                 "args": [],
                 "outputs": { "type": "bool" }
             },
             { "name": "f08",
                 "access": "private",
+                "dynamic_contract_call": false,
                 "args": [],
                 "outputs": { "type": "principal" }
             },
             { "name": "f09",
                 "access": "private",
+                "dynamic_contract_call": false,
                 "args": [],
                 "outputs": { "type": { "buffer": { "length": 4 } } }
             },
             { "name": "f10",
                 "access": "private",
+                "dynamic_contract_call": false,
                 "args": [],
                 "outputs": { "type": { "tuple": [
                     { "name": "tn1", "type": "bool" },
@@ -287,6 +298,7 @@ fn test_names_tokens_contracts_interface() {
             },
             { "name": "f11",
                 "access": "private",
+                "dynamic_contract_call": false,
                 "args": [],
                 "outputs": { "type": { "optional": { "tuple": [ {
                     "name": "owner",
@@ -295,26 +307,31 @@ fn test_names_tokens_contracts_interface() {
             },
             { "name": "f12",
                 "access": "private",
+                "dynamic_contract_call": false,
                 "args": [],
                 "outputs": { "type": { "response": { "ok": "int128", "error": "none" } } }
             },
             { "name": "f13",
                 "access": "private",
+                "dynamic_contract_call": false,
                 "args": [],
                 "outputs": { "type": { "response": { "ok": "none", "error": "int128" } } }
             },
             { "name": "f14",
                 "access": "private",
+                "dynamic_contract_call": false,
                 "args": [],
                 "outputs": { "type": { "response": { "ok": "int128", "error": "int128" } } }
             },
             { "name": "f15",
                 "access": "private",
+                "dynamic_contract_call": false,
                 "args": [],
                 "outputs": { "type": { "list": { "type": "int128", "length": 3 } } }
             },
             { "name": "f16",
                 "access": "private",
+                "dynamic_contract_call": false,
                 "args": [],
                 "outputs": {
                   "type": { "list": {
@@ -328,36 +345,43 @@ fn test_names_tokens_contracts_interface() {
             },
             { "name": "pub-f01",
                 "access": "public",
+                "dynamic_contract_call": false,
                 "args": [],
                 "outputs": { "type": { "response": { "ok": "int128", "error": "none" } } }
             },
             { "name": "pub-f02",
                 "access": "public",
+                "dynamic_contract_call": false,
                 "args": [],
                 "outputs": { "type": { "response": { "ok": "bool", "error": "none" } } }
             },
             { "name": "pub-f03",
                 "access": "public",
+                "dynamic_contract_call": false,
                 "args": [],
                 "outputs": { "type": { "response": { "ok": "none", "error": "bool" } } }
             },
             { "name": "pub-f04",
                 "access": "public",
+                "dynamic_contract_call": false,
                 "args": [],
                 "outputs": { "type": { "response": { "ok": "int128", "error": "int128" } } }
             },
             { "name": "pub-f05",
                 "access": "public",
+                "dynamic_contract_call": false,
                 "args": [{ "name": "a1", "type": "int128" }],
                 "outputs": { "type": { "response": { "ok": "bool", "error": "none" } } }
             },
             { "name": "ro-f01",
                 "access": "read_only",
+                "dynamic_contract_call": false,
                 "args": [],
                 "outputs": { "type": "int128" }
             },
             { "name": "ro-f02",
                 "access": "read_only",
+                "dynamic_contract_call": false,
                 "args": [{ "name": "a1", "type": "int128" }],
                 "outputs": { "type": "int128" }
             }
@@ -432,6 +456,8 @@ fn test_names_tokens_contracts_interface() {
         ],
         "fungible_tokens": [],
         "non_fungible_tokens": [],
+        "implemented_traits": [],
+        "used_traits": [],
         "epoch": "Epoch21",
         "clarity_version": "Clarity2"
     }"#).unwrap();
@@ -441,6 +467,38 @@ fn test_names_tokens_contracts_interface() {
     assert_json_eq!(test_contract_json, test_contract_json_expected);
 }
 
+#[test]
+fn test_contract_interface_dynamic_contract_call() {
+    const DYNAMIC_CALL_CONTRACT: &str = "
+        (define-trait trait-1 ((get-1 (uint) (response uint uint))))
+
+        (define-private (helper (t <trait-1>)) (contract-call? t get-1 u1))
+        (define-private (no-call (a uint)) (ok a))
+
+        (define-public (call-direct (t <trait-1>)) (contract-call? t get-1 u2))
+        (define-public (call-helper (t <trait-1>)) (helper t))
+        (define-public (call-none (a uint)) (no-call a))
+        (define-read-only (ro-none) u0)
+    ";
+
+    let contract_analysis = mem_type_check(DYNAMIC_CALL_CONTRACT).unwrap().1;
+    let interface = build_contract_interface(&contract_analysis).unwrap();
+    assert!(interface
+        .functions
+        .iter()
+        .all(|function| function.dynamic_contract_call.is_some()));
+    let dynamic_callers: Vec<_> = interface
+        .functions
+        .iter()
+        .filter(|function| function.dynamic_contract_call == Some(true))
+        .map(|function| function.name.as_str())
+        .collect();
+    assert_eq!(
+        dynamic_callers,
+        vec!["helper", "call-direct", "call-helper"]
+    );
+}
+
 #[apply(test_clarity_versions)]
 fn test_names_tokens_contracts(#[case] version: ClarityVersion, #[case] epoch: StacksEpochId) {
     let tokens_contract_id = QualifiedContractIdentifier::local("tokens").unwrap();
@@ -1752,6 +1810,72 @@ fn call_versioned(
         .map_err(|e| e.to_string())
 }
 
+#[test]
+fn test_contract_interface_traits() {
+    let mut marf = MemoryBackingStore::new();
+    let mut db = marf.as_analysis_db();
+    let version = ClarityVersion::Clarity2;
+    let epoch = StacksEpochId::Epoch21;
+
+    let contract_analysis = db
+        .execute(|db| {
+            load_versioned(db, "math-trait", version, epoch)?;
+            load_versioned(db, "compute", version, epoch)?;
+            load_versioned(db, "impl-compute", version, epoch)
+        })
+        .unwrap();
+    let interface = build_contract_interface(&contract_analysis).unwrap();
+
+    let implemented_traits = interface.implemented_traits.unwrap();
+    assert_eq!(implemented_traits.len(), 1);
+    assert_eq!(
+        implemented_traits[0].contract_identifier,
+        QualifiedContractIdentifier::local("compute")
+            .unwrap()
+            .to_string()
+    );
+    assert_eq!(implemented_traits[0].name, "compute-trait");
+
+    let used_traits = interface.used_traits.unwrap();
+    assert_eq!(used_traits.len(), 1);
+    assert_eq!(
+        used_traits[0].contract_identifier,
+        QualifiedContractIdentifier::local("math-trait")
+            .unwrap()
+            .to_string()
+    );
+    assert_eq!(used_traits[0].name, "math");
+}
+
+#[test]
+fn test_contract_interface_skips_malformed_definitions() {
+    let mut marf = MemoryBackingStore::new();
+    let mut db = marf.as_analysis_db();
+    let version = ClarityVersion::Clarity2;
+    let epoch = StacksEpochId::Epoch21;
+
+    let mut contract_analysis = db
+        .execute(|db| {
+            load_versioned(db, "math-trait", version, epoch)?;
+            load_versioned(db, "compute", version, epoch)?;
+            load_versioned(db, "impl-compute", version, epoch)
+        })
+        .unwrap();
+    // definitions with no arguments, which fail to parse
+    for define in ["use-trait", "define-public"] {
+        contract_analysis
+            .expressions
+            .push(SymbolicExpression::list(vec![SymbolicExpression::atom(
+                define.into(),
+            )]));
+    }
+    let interface = build_contract_interface(&contract_analysis).unwrap();
+
+    let used_traits = interface.used_traits.unwrap();
+    assert_eq!(used_traits.len(), 1);
+    assert_eq!(used_traits[0].name, "math");
+}
+
 #[apply(test_clarity_versions)]
 fn clarity_trait_experiments_impl(#[case] version: ClarityVersion, #[case] epoch: StacksEpochId) {
     let mut marf = MemoryBackingStore::new();
@@ -1829,9 +1953,8 @@ fn clarity_trait_experiments_use_undefined(
     let err = db
         .execute(|db| load_versioned(db, "no-trait", version, epoch))
         .unwrap_err();
-    assert!(err.starts_with(
-        "ASTError(ParseError { err: TraitReferenceUnknown(\"trait-to-be-defined-later\")"
-    ));
+    assert!(err.starts_with("parse error"));
+    assert!(err.ends_with("use of undeclared trait <trait-to-be-defined-later>"));
 }
 
 #[apply(test_clarity_versions)]
@@ -1849,7 +1972,8 @@ fn clarity_trait_experiments_circular(
             load_versioned(db, "circular-trait-2", version, epoch)
         })
         .unwrap_err();
-    assert!(err.starts_with("ASTError(ParseError { err: CircularReference([\"circular\"])"));
+    assert!(err.starts_with("parse error"));
+    assert!(err.ends_with("detected interdependent functions (circular)"));
 }
 
 #[apply(test_clarity_versions)]
@@ -2087,7 +2211,8 @@ fn clarity_trait_experiments_selfret_trait(
     let err = db
         .execute(|db| load_versioned(db, "selfret-trait", version, epoch))
         .unwrap_err();
-    assert!(err.starts_with("ASTError(ParseError { err: CircularReference([\"self-return\"])"));
+    assert!(err.starts_with("parse error"));
+    assert!(err.ends_with("detected interdependent functions (self-return)"));
 }
 
 #[apply(test_clarity_versions)]
@@ -2493,7 +2618,8 @@ fn clarity_trait_experiments_trait_data_1(
             load_versioned(db, "trait-data-1", version, epoch)
         })
         .unwrap_err();
-    assert!(err.starts_with("ASTError(ParseError { err: TraitReferenceNotAllowed"));
+    assert!(err.starts_with("parse error"));
+    assert!(err.ends_with("trait references can not be stored"));
 }
 
 #[apply(test_clarity_versions)]
@@ -2512,7 +2638,8 @@ fn clarity_trait_experiments_trait_data_2(
             load_versioned(db, "trait-data-2", version, epoch)
         })
         .unwrap_err();
-    assert!(err.starts_with("ASTError(ParseError { err: TraitReferenceNotAllowed"));
+    assert!(err.starts_with("parse error"));
+    assert!(err.ends_with("trait references can not be stored"));
 }
 
 #[apply(test_clarity_versions)]
@@ -299,6 +299,80 @@ fn build_ast_precheck_size<T: CostTracker>(
     Ok(contract)
 }
 
+/// Parse `source_code` with the latest Clarity version and epoch, and render the resulting
+/// expressions as indented S-expressions, in source order. Lists containing other lists are
+/// split over several lines, with their items indented beneath them. With `developer-mode`,
+/// each line ends with a comment giving the span of the expression that starts on it.
+pub fn parse_to_string(source_code: &str) -> ParseResult<String> {
+    let contract_ast = build_ast_precheck_size(
+        &QualifiedContractIdentifier::transient(),
+        source_code,
+        &mut (),
+        ClarityVersion::latest(),
+        StacksEpochId::latest(),
+    )?;
+    let mut output = String::new();
+    for expression in contract_ast.expressions.iter() {
+        write_expression_tree(&mut output, expression, 0);
+    }
+    Ok(output)
+}
+
+/// Write `expression` on a single line, with no trailing newline
+fn write_expression_inline(output: &mut String, expression: &SymbolicExpression) {
+    match expression.match_list() {
+        Some(list) => {
+            output.push('(');
+            for (i, item) in list.iter().enumerate() {
+                if i > 0 {
+                    output.push(' ');
+                }
+                write_expression_inline(output, item);
+            }
+            output.push(')');
+        }
+        None => output.push_str(&expression.to_string()),
+    }
+}
+
+/// Write `expression` at indentation level `depth`, ending with a newline
+fn write_expression_tree(output: &mut String, expression: &SymbolicExpression, depth: usize) {
+    output.push_str(&"  ".repeat(depth));
+    let nested_items = match expression.match_list() {
+        Some(list) if list.iter().any(|item| item.match_list().is_some()) => {
+            // keep a non-list head (e.g. the function name) on the opening line
+            output.push('(');
+            match list.split_first() {
+                Some((head, rest)) if head.match_list().is_none() => {
+                    write_expression_inline(output, head);
+                    Some(rest)
+                }
+                _ => Some(list),
+            }
+        }
+        _ => {
+            write_expression_inline(output, expression);
+            None
+        }
+    };
+    if cfg!(feature = "developer-mode") {
+        let span = expression.span();
+        output.push_str(&format!(
+            " ;; {}:{}-{}:{}",
+            span.start_line, span.start_column, span.end_line, span.end_column
+        ));
+    }
+    output.push('\n');
+
+    if let Some(items) = nested_items {
+        for item in items.iter() {
+            write_expression_tree(output, item, depth + 1);
+        }
+        output.push_str(&"  ".repeat(depth));
+        output.push_str(")\n");
+    }
+}
+
 /// Test compatibility
 #[cfg(any(test, feature = "testing"))]
 pub fn build_ast<T: CostTracker>(
@@ -324,7 +398,7 @@ mod test {
 
     use crate::vm::ast::errors::ParseErrors;
     use crate::vm::ast::stack_depth_checker::AST_CALL_STACK_DEPTH_BUFFER;
-    use crate::vm::ast::{build_ast, build_ast_with_rules, ASTRules};
+    use crate::vm::ast::{build_ast, build_ast_with_rules, parse_to_string, ASTRules};
     use crate::vm::costs::{LimitedCostTracker, *};
     use crate::vm::representations::depth_traverse;
     use crate::vm::types::QualifiedContractIdentifier;
@@ -621,4 +695,29 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_parse_to_string() {
+        let source = "(define-private (add-two (a int)) (+ a 2))\n(add-two 1)";
+        let output = parse_to_string(source).unwrap();
+        // drop the span comments added in developer-mode
+        let lines: Vec<_> = output
+            .lines()
+            .map(|line| line.split(" ;;").next().unwrap())
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                "(define-private",
+                "  (add-two",
+                "    (a int)",
+                "  )",
+                "  (+ a 2)",
+                ")",
+                "(add-two 1)",
+            ]
+        );
+
+        assert!(parse_to_string("(define-private (add-two (a int))").is_err());
+    }
 }
@@ -27,7 +27,9 @@ use crate::vm::analysis::errors::CheckErrors;
 use crate::vm::contexts::ContractContext;
 use crate::vm::costs::cost_functions::ClarityCostFunction;
 use crate::vm::costs::{cost_functions, runtime_cost};
-use crate::vm::errors::{check_argument_count, Error, InterpreterResult as Result};
+use crate::vm::errors::{
+    check_argument_count, Error, InterpreterResult as Result, ShortReturnType,
+};
 use crate::vm::representations::{ClarityName, Span, SymbolicExpression};
 use crate::vm::types::Value::UInt;
 use crate::vm::types::{
@@ -299,6 +301,7 @@ impl DefinedFunction {
         match result {
             Ok(r) => Ok(r),
             Err(e) => match e {
+                Error::ShortReturn(ShortReturnType::Panic(_)) => Err(e),
                 Error::ShortReturn(v) => Ok(v.into()),
                 _ => Err(e),
             },
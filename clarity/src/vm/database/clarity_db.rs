@@ -1970,6 +1970,33 @@ impl<'a> ClarityDatabase<'a> {
         Ok(principal)
     }
 
+    /// Determine whether or not the given NFT currently has an owner.  Unlike `get_nft_owner()`,
+    /// this does not deserialize the owner.  Burnt NFTs do not exist.
+    pub fn nft_exists(
+        &mut self,
+        contract_identifier: &QualifiedContractIdentifier,
+        asset_name: &str,
+        asset: &Value,
+        key_type: &TypeSignature,
+    ) -> Result<bool> {
+        if !key_type.admits(&self.get_clarity_epoch_version()?, asset)? {
+            return Err(CheckErrors::TypeValueError(key_type.clone(), (*asset).clone()).into());
+        }
+
+        let key = ClarityDatabase::make_key_for_quad(
+            contract_identifier,
+            StoreType::NonFungibleToken,
+            asset_name,
+            &asset.serialize_to_hex()?,
+        );
+
+        let owner_hex: Option<String> = self.get_data(&key)?;
+        match owner_hex {
+            Some(owner_hex) => Ok(owner_hex != Value::none().serialize_to_hex()?),
+            None => Ok(false),
+        }
+    }
+
     pub fn get_nft_key_type(
         &mut self,
         contract_identifier: &QualifiedContractIdentifier,
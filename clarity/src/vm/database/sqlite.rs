@@ -165,14 +165,14 @@ impl SqliteConnection {
 impl SqliteConnection {
     pub fn initialize_conn(conn: &Connection) -> Result<()> {
         conn.query_row("PRAGMA journal_mode = WAL;", NO_PARAMS, |_row| Ok(()))
-            .map_err(|x| InterpreterError::SqliteError(IncomparableError { err: x }))?;
+            .map_err(|x| InterpreterError::SqliteError(IncomparableError::from(x)))?;
 
         conn.execute(
             "CREATE TABLE IF NOT EXISTS data_table
                       (key TEXT PRIMARY KEY, value TEXT)",
             NO_PARAMS,
         )
-        .map_err(|x| InterpreterError::SqliteError(IncomparableError { err: x }))?;
+        .map_err(|x| InterpreterError::SqliteError(IncomparableError::from(x)))?;
 
         conn.execute(
             "CREATE TABLE IF NOT EXISTS metadata_table
@@ -180,7 +180,7 @@ impl SqliteConnection {
                        UNIQUE (key, blockhash))",
             NO_PARAMS,
         )
-        .map_err(|x| InterpreterError::SqliteError(IncomparableError { err: x }))?;
+        .map_err(|x| InterpreterError::SqliteError(IncomparableError::from(x)))?;
 
         Self::check_schema(conn)?;
 
@@ -200,19 +200,19 @@ impl SqliteConnection {
         let sql = "SELECT sql FROM sqlite_master WHERE name=?";
         let _: String = conn
             .query_row(sql, &["data_table"], |row| row.get(0))
-            .map_err(|x| InterpreterError::SqliteError(IncomparableError { err: x }))?;
+            .map_err(|x| InterpreterError::SqliteError(IncomparableError::from(x)))?;
         let _: String = conn
             .query_row(sql, &["metadata_table"], |row| row.get(0))
-            .map_err(|x| InterpreterError::SqliteError(IncomparableError { err: x }))?;
+            .map_err(|x| InterpreterError::SqliteError(IncomparableError::from(x)))?;
         Ok(())
     }
 
     pub fn inner_open(filename: &str) -> Result<Connection> {
         let conn = Connection::open(filename)
-            .map_err(|x| InterpreterError::SqliteError(IncomparableError { err: x }))?;
+            .map_err(|x| InterpreterError::SqliteError(IncomparableError::from(x)))?;
 
         conn.busy_handler(Some(tx_busy_handler))
-            .map_err(|x| InterpreterError::SqliteError(IncomparableError { err: x }))?;
+            .map_err(|x| InterpreterError::SqliteError(IncomparableError::from(x)))?;
 
         Ok(conn)
     }
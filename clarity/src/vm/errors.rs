@@ -28,11 +28,15 @@ pub use crate::vm::analysis::errors::{
 use crate::vm::ast::errors::ParseError;
 use crate::vm::contexts::StackTrace;
 use crate::vm::costs::CostErrors;
+use crate::vm::representations::Span;
 use crate::vm::types::{TypeSignature, Value};
 
+/// Wrapper for errors that don't implement `PartialEq`.
+/// Two `IncomparableError`s are equal only if both carry a `kind` tag and the tags match.
 #[derive(Debug)]
 pub struct IncomparableError<T> {
     pub err: T,
+    pub kind: Option<String>,
 }
 
 #[derive(Debug)]
@@ -103,19 +107,67 @@ pub enum RuntimeErrorType {
     DefunctPoxContract,
     PoxAlreadyLocked,
     MetadataAlreadySet,
+    /// A runtime error raised by a host function or native, identified by a numeric code and
+    /// described by a human-readable message
+    Custom {
+        code: u32,
+        message: String,
+    },
 }
 
 #[derive(Debug, PartialEq)]
 pub enum ShortReturnType {
     ExpectedValue(Value),
     AssertionFailed(Value),
+    /// Unconditionally abort the transaction.  Unlike the other short-returns, this is not
+    /// caught at the function boundary.
+    Panic(Value),
 }
 
 pub type InterpreterResult<R> = Result<R, Error>;
 
+impl<T> IncomparableError<T> {
+    /// Wrap an opaque error, which will never compare equal to anything
+    pub fn new(err: T) -> IncomparableError<T> {
+        IncomparableError { err, kind: None }
+    }
+
+    /// Wrap an error, and tag it with a kind that equality checks can compare
+    pub fn with_kind(err: T, kind: String) -> IncomparableError<T> {
+        IncomparableError {
+            err,
+            kind: Some(kind),
+        }
+    }
+}
+
 impl<T> PartialEq<IncomparableError<T>> for IncomparableError<T> {
-    fn eq(&self, _other: &IncomparableError<T>) -> bool {
-        return false;
+    fn eq(&self, other: &IncomparableError<T>) -> bool {
+        match (&self.kind, &other.kind) {
+            (Some(kind), Some(other_kind)) => kind == other_kind,
+            _ => false,
+        }
+    }
+}
+
+impl From<SqliteError> for IncomparableError<SqliteError> {
+    fn from(err: SqliteError) -> Self {
+        let kind = match &err {
+            SqliteError::SqliteFailure(e, _) => format!("SqliteFailure({:?})", e.code),
+            e => format!("{:?}", e)
+                .split(|c: char| !c.is_alphanumeric())
+                .next()
+                .unwrap_or("")
+                .to_string(),
+        };
+        IncomparableError::with_kind(err, kind)
+    }
+}
+
+impl From<SerdeJSONErr> for IncomparableError<SerdeJSONErr> {
+    fn from(err: SerdeJSONErr) -> Self {
+        let kind = format!("{:?}", err.classify());
+        IncomparableError::with_kind(err, kind)
     }
 }
 
@@ -131,6 +183,35 @@ impl PartialEq<Error> for Error {
     }
 }
 
+impl Error {
+    /// Suggested HTTP status code for reporting this error to an RPC client.
+    /// * 400 for errors that static analysis should have caught
+    /// * 422 for errors that the contract raised at runtime
+    /// * 500 for interpreter failures
+    /// * 200 for short-returns, which are not errors from the caller's point of view, except for
+    ///   panics, which get 422
+    pub fn http_status_hint(&self) -> u16 {
+        match self {
+            Error::Unchecked(_) => 400,
+            Error::Runtime(..) => 422,
+            Error::Interpreter(_) => 500,
+            Error::ShortReturn(ShortReturnType::Panic(_)) => 422,
+            Error::ShortReturn(_) => 200,
+        }
+    }
+
+    /// Is this error caused by a (possibly) transient storage failure, such that evaluating the
+    /// same code again could succeed?  All other errors are deterministic.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Error::Interpreter(InterpreterError::SqliteError(_))
+            | Error::Interpreter(InterpreterError::DBError(_))
+            | Error::Interpreter(InterpreterError::MarfFailure(_)) => true,
+            _ => false,
+        }
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -147,6 +228,7 @@ impl fmt::Display for Error {
                 }
                 Ok(())
             }
+            Error::ShortReturn(ShortReturnType::Panic(v)) => write!(f, "Panic: {}", v),
             _ => write!(f, "{:?}", self),
         }
     }
@@ -154,7 +236,20 @@ impl fmt::Display for Error {
 
 impl fmt::Display for RuntimeErrorType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
+        match self {
+            RuntimeErrorType::Custom { code, message } => {
+                write!(f, "Runtime error {}: {}", code, message)
+            }
+            RuntimeErrorType::ASTError(parse_error) => match parse_error.diagnostic.spans.first() {
+                Some(span) if *span != Span::ZERO => write!(
+                    f,
+                    "parse error at line {}, col {}: {}",
+                    span.start_line, span.start_column, parse_error.diagnostic.message
+                ),
+                _ => write!(f, "parse error: {}", parse_error.diagnostic.message),
+            },
+            _ => write!(f, "{:?}", self),
+        }
     }
 }
 
@@ -229,6 +324,7 @@ impl Into<Value> for ShortReturnType {
         match self {
             ShortReturnType::ExpectedValue(v) => v,
             ShortReturnType::AssertionFailed(v) => v,
+            ShortReturnType::Panic(v) => v,
         }
     }
 }
@@ -250,6 +346,138 @@ _native_:native_div
         assert_eq!(format!("{}", execute(t).unwrap_err()), expected);
     }
 
+    #[test]
+    fn ast_error_formats() {
+        let parse_error = ParseError::new(ParseErrors::FailedParsingIntValue("1x".into()));
+        assert_eq!(
+            format!("{}", RuntimeErrorType::ASTError(parse_error)),
+            "parse error: Failed to parse int literal '1x'"
+        );
+
+        let mut parse_error = ParseError::new(ParseErrors::FailedParsingIntValue("1x".into()));
+        parse_error.diagnostic.spans = vec![Span {
+            start_line: 3,
+            start_column: 5,
+            end_line: 3,
+            end_column: 6,
+        }];
+        assert_eq!(
+            format!("{}", RuntimeErrorType::ASTError(parse_error)),
+            "parse error at line 3, col 5: Failed to parse int literal '1x'"
+        );
+    }
+
+    #[test]
+    fn custom_runtime_error() {
+        let err = RuntimeErrorType::Custom {
+            code: 7,
+            message: "supply cap exceeded".into(),
+        };
+        assert_eq!(format!("{}", err), "Runtime error 7: supply cap exceeded");
+        assert_eq!(
+            format!("{}", Error::from(err)),
+            "Runtime error 7: supply cap exceeded"
+        );
+
+        assert_eq!(
+            RuntimeErrorType::Custom {
+                code: 7,
+                message: "a".into()
+            },
+            RuntimeErrorType::Custom {
+                code: 7,
+                message: "a".into()
+            }
+        );
+        assert_ne!(
+            RuntimeErrorType::Custom {
+                code: 7,
+                message: "a".into()
+            },
+            RuntimeErrorType::Custom {
+                code: 8,
+                message: "a".into()
+            }
+        );
+    }
+
+    #[test]
+    fn incomparable_error_equality() {
+        let no_rows = || InterpreterError::SqliteError(SqliteError::QueryReturnedNoRows.into());
+        assert_eq!(no_rows(), no_rows());
+        assert_ne!(
+            no_rows(),
+            InterpreterError::SqliteError(SqliteError::InvalidQuery.into())
+        );
+
+        let bad_json =
+            || IncomparableError::from(serde_json::from_str::<u32>("not json").unwrap_err());
+        assert_eq!(bad_json(), bad_json());
+
+        // untagged errors are never equal
+        assert_ne!(
+            IncomparableError::new(SqliteError::QueryReturnedNoRows),
+            IncomparableError::new(SqliteError::QueryReturnedNoRows)
+        );
+        assert_ne!(
+            IncomparableError::new(SqliteError::QueryReturnedNoRows),
+            IncomparableError::from(SqliteError::QueryReturnedNoRows)
+        );
+    }
+
+    #[test]
+    fn http_status_hints() {
+        assert_eq!(
+            Error::from(CheckErrors::NoSuchContract("foo".into())).http_status_hint(),
+            400
+        );
+        assert_eq!(
+            Error::from(RuntimeErrorType::DivisionByZero).http_status_hint(),
+            422
+        );
+        assert_eq!(
+            Error::from(InterpreterError::Expect("bad".into())).http_status_hint(),
+            500
+        );
+        assert_eq!(
+            Error::from(ShortReturnType::ExpectedValue(Value::Bool(true))).http_status_hint(),
+            200
+        );
+    }
+
+    #[test]
+    fn transient_errors() {
+        assert!(Error::from(InterpreterError::DBError("locked".into())).is_transient());
+        assert!(Error::from(InterpreterError::SqliteError(
+            SqliteError::QueryReturnedNoRows.into()
+        ))
+        .is_transient());
+        assert!(!Error::from(InterpreterError::BadSender(Value::Bool(true))).is_transient());
+        assert!(!Error::from(InterpreterError::FailureConstructingTupleWithType).is_transient());
+        assert!(!Error::from(RuntimeErrorType::DivisionByZero).is_transient());
+    }
+
+    #[test]
+    fn panic_short_return() {
+        let panic = ShortReturnType::Panic(Value::UInt(1));
+        assert_ne!(panic, ShortReturnType::AssertionFailed(Value::UInt(1)));
+        assert_eq!(
+            Error::from(ShortReturnType::Panic(Value::UInt(1))),
+            Error::from(ShortReturnType::Panic(Value::UInt(1)))
+        );
+        assert_eq!(
+            format!("{}", Error::from(ShortReturnType::Panic(Value::UInt(1)))),
+            "Panic: u1"
+        );
+        assert_eq!(
+            Error::from(ShortReturnType::Panic(Value::UInt(1))).http_status_hint(),
+            422
+        );
+
+        let v: Value = panic.into();
+        assert_eq!(v, Value::UInt(1));
+    }
+
     #[test]
     fn equality() {
         assert_eq!(
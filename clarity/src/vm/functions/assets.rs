@@ -16,6 +16,7 @@
 
 use stacks_common::types::StacksEpochId;
 
+use crate::vm::contexts::StackTrace;
 use crate::vm::costs::cost_functions::ClarityCostFunction;
 use crate::vm::costs::{cost_functions, runtime_cost, CostTracker};
 use crate::vm::database::{ClarityDatabase, ClaritySerializable, STXBalance};
@@ -69,6 +70,20 @@ macro_rules! clarity_ecode {
     };
 }
 
+/// Log the stack trace of a `NoSuchToken` error before it gets converted into a Clarity value,
+/// since it would otherwise be lost.
+#[cfg(feature = "developer-mode")]
+fn log_no_such_token(asset_name: &str, stack_trace: &Option<StackTrace>) {
+    debug!(
+        "No such token {}; stack trace: {:?}",
+        asset_name,
+        stack_trace.as_ref().unwrap_or(&vec![])
+    );
+}
+
+#[cfg(not(feature = "developer-mode"))]
+fn log_no_such_token(_asset_name: &str, _stack_trace: &Option<StackTrace>) {}
+
 switch_on_global_epoch!(special_mint_asset(
     special_mint_asset_v200,
     special_mint_asset_v205
@@ -411,7 +426,10 @@ pub fn special_mint_asset_v200(
             &asset,
             expected_asset_type,
         ) {
-            Err(Error::Runtime(RuntimeErrorType::NoSuchToken, _)) => Ok(()),
+            Err(Error::Runtime(RuntimeErrorType::NoSuchToken, stack_trace)) => {
+                log_no_such_token(asset_name, &stack_trace);
+                Ok(())
+            }
             Ok(_owner) => return clarity_ecode!(MintAssetErrorCodes::ALREADY_EXIST),
             Err(e) => Err(e),
         }?;
@@ -478,7 +496,10 @@ pub fn special_mint_asset_v205(
             &asset,
             expected_asset_type,
         ) {
-            Err(Error::Runtime(RuntimeErrorType::NoSuchToken, _)) => Ok(()),
+            Err(Error::Runtime(RuntimeErrorType::NoSuchToken, stack_trace)) => {
+                log_no_such_token(asset_name, &stack_trace);
+                Ok(())
+            }
             Ok(_owner) => return clarity_ecode!(MintAssetErrorCodes::ALREADY_EXIST),
             Err(e) => Err(e),
         }?;
@@ -550,8 +571,9 @@ pub fn special_transfer_asset_v200(
             expected_asset_type,
         ) {
             Ok(owner) => Ok(owner),
-            Err(Error::Runtime(RuntimeErrorType::NoSuchToken, _)) => {
-                return clarity_ecode!(TransferAssetErrorCodes::DOES_NOT_EXIST)
+            Err(Error::Runtime(RuntimeErrorType::NoSuchToken, stack_trace)) => {
+                log_no_such_token(asset_name, &stack_trace);
+                return clarity_ecode!(TransferAssetErrorCodes::DOES_NOT_EXIST);
             }
             Err(e) => Err(e),
         }?;
@@ -640,8 +662,9 @@ pub fn special_transfer_asset_v205(
             expected_asset_type,
         ) {
             Ok(owner) => Ok(owner),
-            Err(Error::Runtime(RuntimeErrorType::NoSuchToken, _)) => {
-                return clarity_ecode!(TransferAssetErrorCodes::DOES_NOT_EXIST)
+            Err(Error::Runtime(RuntimeErrorType::NoSuchToken, stack_trace)) => {
+                log_no_such_token(asset_name, &stack_trace);
+                return clarity_ecode!(TransferAssetErrorCodes::DOES_NOT_EXIST);
             }
             Err(e) => Err(e),
         }?;
@@ -857,7 +880,10 @@ pub fn special_get_owner_v200(
         Ok(owner) => Ok(Value::some(Value::Principal(owner)).map_err(|_| {
             InterpreterError::Expect("Principal should always fit in optional.".into())
         })?),
-        Err(Error::Runtime(RuntimeErrorType::NoSuchToken, _)) => Ok(Value::none()),
+        Err(Error::Runtime(RuntimeErrorType::NoSuchToken, stack_trace)) => {
+            log_no_such_token(asset_name, &stack_trace);
+            Ok(Value::none())
+        }
         Err(e) => Err(e),
     }
 }
@@ -900,7 +926,10 @@ pub fn special_get_owner_v205(
         Ok(owner) => Ok(Value::some(Value::Principal(owner)).map_err(|_| {
             InterpreterError::Expect("Principal should always fit in optional.".into())
         })?),
-        Err(Error::Runtime(RuntimeErrorType::NoSuchToken, _)) => Ok(Value::none()),
+        Err(Error::Runtime(RuntimeErrorType::NoSuchToken, stack_trace)) => {
+            log_no_such_token(asset_name, &stack_trace);
+            Ok(Value::none())
+        }
         Err(e) => Err(e),
     }
 }
@@ -1028,8 +1057,9 @@ pub fn special_burn_asset_v200(
             &asset,
             expected_asset_type,
         ) {
-            Err(Error::Runtime(RuntimeErrorType::NoSuchToken, _)) => {
-                return clarity_ecode!(BurnAssetErrorCodes::DOES_NOT_EXIST)
+            Err(Error::Runtime(RuntimeErrorType::NoSuchToken, stack_trace)) => {
+                log_no_such_token(asset_name, &stack_trace);
+                return clarity_ecode!(BurnAssetErrorCodes::DOES_NOT_EXIST);
             }
             Ok(owner) => Ok(owner),
             Err(e) => Err(e),
@@ -1109,8 +1139,9 @@ pub fn special_burn_asset_v205(
             &asset,
             expected_asset_type,
         ) {
-            Err(Error::Runtime(RuntimeErrorType::NoSuchToken, _)) => {
-                return clarity_ecode!(BurnAssetErrorCodes::DOES_NOT_EXIST)
+            Err(Error::Runtime(RuntimeErrorType::NoSuchToken, stack_trace)) => {
+                log_no_such_token(asset_name, &stack_trace);
+                return clarity_ecode!(BurnAssetErrorCodes::DOES_NOT_EXIST);
             }
             Ok(owner) => Ok(owner),
             Err(e) => Err(e),
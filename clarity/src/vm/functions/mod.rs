@@ -195,7 +195,60 @@ define_versioned_named_enum!(NativeFunctions(ClarityVersion) {
     ReplaceAt("replace-at?", ClarityVersion::Clarity2),
 });
 
+/// The kind of work that a native function does, for grouping natives by how they should be
+/// charged
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CostCategory {
+    /// Arithmetic, comparison, boolean, and bitwise operations
+    Arithmetic,
+    /// Conversions between types and representations
+    Conversion,
+    /// Construction and manipulation of sequences and tuples
+    Sequence,
+    /// Hashing and signature operations
+    Crypto,
+    /// Evaluation, branching, and short-circuiting, including option and response handling
+    ControlFlow,
+    /// Reads contract or chain state
+    DatabaseRead,
+    /// Writes contract or chain state
+    DatabaseWrite,
+}
+
 impl NativeFunctions {
+    /// Get the kind of work this native does
+    pub fn cost_category(&self) -> CostCategory {
+        use crate::vm::functions::NativeFunctions::*;
+        match self {
+            Add | Subtract | Multiply | Divide | CmpGeq | CmpLeq | CmpLess | CmpGreater
+            | Modulo | Power | Sqrti | Log2 | BitwiseXor | And | Or | Not | Equals | BitwiseAnd
+            | BitwiseOr | BitwiseNot | BitwiseLShift | BitwiseRShift | BitwiseXor2 => {
+                CostCategory::Arithmetic
+            }
+            ToInt | ToUInt | BuffToIntLe | BuffToUIntLe | BuffToIntBe | BuffToUIntBe
+            | IsStandard | PrincipalDestruct | PrincipalConstruct | StringToInt | StringToUInt
+            | IntToAscii | IntToUtf8 | ContractOf | PrincipalOf | ToConsensusBuff
+            | FromConsensusBuff => CostCategory::Conversion,
+            Map | Fold | Append | Concat | AsMaxLen | Len | ElementAt | ElementAtAlias
+            | IndexOf | IndexOfAlias | ListCons | TupleCons | TupleGet | TupleMerge | Filter
+            | Slice | ReplaceAt => CostCategory::Sequence,
+            Hash160 | Sha256 | Sha512 | Sha512Trunc256 | Keccak256 | Secp256k1Recover
+            | Secp256k1Verify => CostCategory::Crypto,
+            If | Let | Begin | Print | ContractCall | AsContract | AtBlock | ConsError
+            | ConsOkay | ConsSome | DefaultTo | Asserts | UnwrapRet | UnwrapErrRet | Unwrap
+            | UnwrapErr | Match | TryRet | IsOkay | IsNone | IsErr | IsSome => {
+                CostCategory::ControlFlow
+            }
+            FetchVar | FetchEntry | GetBlockInfo | GetBurnBlockInfo | GetTokenBalance
+            | GetAssetOwner | GetTokenSupply | GetStxBalance | StxGetAccount => {
+                CostCategory::DatabaseRead
+            }
+            SetVar | SetEntry | InsertEntry | DeleteEntry | TransferToken | TransferAsset
+            | MintAsset | MintToken | BurnToken | BurnAsset | StxTransfer | StxTransferMemo
+            | StxBurn => CostCategory::DatabaseWrite,
+        }
+    }
+
     pub fn lookup_by_name_at_version(
         name: &str,
         version: &ClarityVersion,
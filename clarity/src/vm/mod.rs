@@ -39,6 +39,9 @@ pub mod version;
 
 pub mod coverage;
 
+#[cfg(feature = "developer-mode")]
+pub mod trace;
+
 pub mod events;
 
 #[cfg(any(test, feature = "testing"))]
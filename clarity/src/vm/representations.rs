@@ -83,6 +83,86 @@ guarded_string!(
     RuntimeErrorType::BadNameValue
 );
 
+/// Why a string is not a legal `ClarityName` or `ContractName`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NameError {
+    /// The name is empty
+    Empty,
+    /// The name is longer than `MAX_STRING_LEN` bytes
+    TooLong(usize),
+    /// The name starts with a digit
+    LeadingDigit,
+    /// The character at this (character) index may not appear there
+    IllegalCharacter(usize, char),
+}
+
+impl fmt::Display for NameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NameError::Empty => write!(f, "name is empty"),
+            NameError::TooLong(len) => write!(
+                f,
+                "name is {} bytes long, but at most {} are allowed",
+                len, MAX_STRING_LEN
+            ),
+            NameError::LeadingDigit => write!(f, "name starts with a digit"),
+            NameError::IllegalCharacter(index, c) => {
+                write!(f, "illegal character {:?} at index {}", c, index)
+            }
+        }
+    }
+}
+
+impl std::error::Error for NameError {}
+
+/// Check the rules shared by clarity names and contract names: the name must be non-empty and
+/// at most `MAX_STRING_LEN` bytes, start with a letter, and otherwise contain only letters,
+/// digits, and characters accepted by `is_legal_tail`.
+fn validate_name(name: &str, is_legal_tail: fn(char) -> bool) -> Result<(), NameError> {
+    if name.is_empty() {
+        return Err(NameError::Empty);
+    }
+    if name.len() > MAX_STRING_LEN as usize {
+        return Err(NameError::TooLong(name.len()));
+    }
+    for (index, c) in name.chars().enumerate() {
+        if index == 0 {
+            if c.is_ascii_digit() {
+                return Err(NameError::LeadingDigit);
+            }
+            if !c.is_ascii_alphabetic() {
+                return Err(NameError::IllegalCharacter(index, c));
+            }
+        } else if !c.is_ascii_alphanumeric() && !is_legal_tail(c) {
+            return Err(NameError::IllegalCharacter(index, c));
+        }
+    }
+    Ok(())
+}
+
+impl ClarityName {
+    /// Check whether `name` is a legal clarity name, and if not, explain why.
+    /// This accepts exactly the names that `ClarityName::try_from` accepts.
+    pub fn validate(name: &str) -> Result<(), NameError> {
+        // arithmetic and comparison operators are the only names that don't start with a letter
+        if matches!(name, "-" | "+" | "=" | "/" | "*" | "<" | ">" | "<=" | ">=") {
+            return Ok(());
+        }
+        validate_name(name, |c| "-_!?+<>=/*".contains(c))
+    }
+}
+
+impl ContractName {
+    /// Check whether `name` is a legal contract name, and if not, explain why.
+    /// This accepts exactly the names that `ContractName::try_from` accepts.
+    pub fn validate(name: &str) -> Result<(), NameError> {
+        if name == "__transient" {
+            return Ok(());
+        }
+        validate_name(name, |c| c == '-' || c == '_')
+    }
+}
+
 impl StacksMessageCodec for ClarityName {
     fn consensus_serialize<W: Write>(&self, fd: &mut W) -> Result<(), codec_error> {
         // ClarityName can't be longer than vm::representations::MAX_STRING_LEN, which itself is
@@ -676,3 +756,93 @@ impl Span {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn validate_names_explains_rejections() {
+        assert_eq!(ContractName::validate("pox-4"), Ok(()));
+        assert_eq!(ContractName::validate("__transient"), Ok(()));
+        assert_eq!(ContractName::validate(""), Err(NameError::Empty));
+        assert_eq!(ContractName::validate("4pox"), Err(NameError::LeadingDigit));
+        assert_eq!(
+            ContractName::validate("-pox"),
+            Err(NameError::IllegalCharacter(0, '-'))
+        );
+        assert_eq!(
+            ContractName::validate("pox.4"),
+            Err(NameError::IllegalCharacter(3, '.'))
+        );
+        assert_eq!(
+            ContractName::validate("is-ok?"),
+            Err(NameError::IllegalCharacter(5, '?'))
+        );
+        assert_eq!(
+            ContractName::validate(&"a".repeat(129)),
+            Err(NameError::TooLong(129))
+        );
+
+        assert_eq!(ClarityName::validate("is-ok?"), Ok(()));
+        assert_eq!(ClarityName::validate("<="), Ok(()));
+        assert_eq!(
+            ClarityName::validate("=>"),
+            Err(NameError::IllegalCharacter(0, '='))
+        );
+        assert_eq!(ClarityName::validate("1+"), Err(NameError::LeadingDigit));
+        assert_eq!(
+            ClarityName::validate("caf\u{e9}"),
+            Err(NameError::IllegalCharacter(3, '\u{e9}'))
+        );
+    }
+
+    #[test]
+    fn validate_names_agrees_with_try_from() {
+        let candidates = [
+            "",
+            "a",
+            "A1",
+            "1a",
+            "-",
+            "+",
+            "=",
+            "/",
+            "*",
+            "<",
+            ">",
+            "<=",
+            ">=",
+            "=>",
+            "<>",
+            "--",
+            "a-b_c",
+            "a!?+<>=/*",
+            "a.b",
+            "a b",
+            "_a",
+            "__transient",
+            "a\n",
+            "caf\u{e9}",
+        ];
+        let long_names = ["a".repeat(128), "a".repeat(129)];
+        for name in candidates
+            .iter()
+            .copied()
+            .chain(long_names.iter().map(String::as_str))
+        {
+            assert_eq!(
+                ClarityName::validate(name).is_ok(),
+                ClarityName::try_from(name.to_string()).is_ok(),
+                "clarity name {:?}",
+                name
+            );
+            assert_eq!(
+                ContractName::validate(name).is_ok(),
+                ContractName::try_from(name.to_string()).is_ok(),
+                "contract name {:?}",
+                name
+            );
+        }
+    }
+}
@@ -25,7 +25,9 @@ use crate::vm::tests::{
     execute, is_committed, is_err_code, symbols_from_values, test_clarity_versions, test_epochs,
     tl_env_factory as env_factory, TopLevelMemoryEnvironmentGenerator,
 };
-use crate::vm::types::{AssetIdentifier, PrincipalData, QualifiedContractIdentifier, Value};
+use crate::vm::types::{
+    AssetIdentifier, PrincipalData, QualifiedContractIdentifier, TypeSignature, Value,
+};
 use crate::vm::version::ClarityVersion;
 use crate::vm::ContractContext;
 
@@ -1384,5 +1386,40 @@ fn test_simple_naming_system(
                 .unwrap(),
             Value::some(p1).unwrap()
         );
+
+        // 5 is owned, 8 was burnt, and 12345 was never minted
+        let db = &mut env.global_context.database;
+        assert!(db
+            .nft_exists(
+                &names_contract_id,
+                "names",
+                &Value::Int(5),
+                &TypeSignature::IntType
+            )
+            .unwrap());
+        assert!(!db
+            .nft_exists(
+                &names_contract_id,
+                "names",
+                &Value::Int(8),
+                &TypeSignature::IntType
+            )
+            .unwrap());
+        assert!(!db
+            .nft_exists(
+                &names_contract_id,
+                "names",
+                &Value::Int(12345),
+                &TypeSignature::IntType
+            )
+            .unwrap());
+        assert!(db
+            .nft_exists(
+                &names_contract_id,
+                "names",
+                &Value::UInt(5),
+                &TypeSignature::IntType
+            )
+            .is_err());
     }
 }
@@ -231,6 +231,27 @@ fn test_simple_buff_to_uint_be() {
     );
 }
 
+#[test]
+fn test_buff_to_int_be_consensus_round_trip() {
+    // A consensus-serialized int or uint is a type prefix followed by its 16 big-endian bytes,
+    // so dropping the prefix gives back a buffer that converts to the original value.
+    for value in [0, 1, -1, 258, i128::MAX, i128::MIN] {
+        let test = format!(
+            "(buff-to-int-be (unwrap-panic (as-max-len? (unwrap-panic (slice? (unwrap-panic (to-consensus-buff? {})) u1 u17)) u16)))",
+            value
+        );
+        assert_eq!(Value::Int(value), execute_v2(&test).unwrap().unwrap());
+    }
+
+    for value in [0, 1, 258, i128::MAX as u128, u128::MAX] {
+        let test = format!(
+            "(buff-to-uint-be (unwrap-panic (as-max-len? (unwrap-panic (slice? (unwrap-panic (to-consensus-buff? u{})) u1 u17)) u16)))",
+            value
+        );
+        assert_eq!(Value::UInt(value), execute_v2(&test).unwrap().unwrap());
+    }
+}
+
 #[test]
 fn test_simple_string_to_int() {
     let good1_test = r#"(string-to-int? "-1")"#;
@@ -0,0 +1,99 @@
+// Copyright (C) 2013-2020 Blockstack PBC, a public benefit corporation
+// Copyright (C) 2020-2024 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::EvalHook;
+use crate::vm::contexts::{Environment, LocalContext};
+use crate::vm::costs::ExecutionCost;
+use crate::vm::errors::Error;
+use crate::vm::{ExecutionResult, SymbolicExpression, Value};
+
+/// One evaluated expression, as recorded by a `TraceCollector`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TraceEntry {
+    /// The expression that was evaluated
+    pub expr: SymbolicExpression,
+    /// What it evaluated to, or the error it raised
+    pub result: Result<Value, String>,
+    /// The cost of evaluating it, including the cost of its sub-expressions
+    pub cost: ExecutionCost,
+}
+
+/// Eval hook that records a `TraceEntry` for each expression once it has been evaluated.
+/// Entries are stored in the order in which evaluation finishes, so sub-expressions come before
+/// the expressions that contain them.
+#[derive(Debug, Default)]
+pub struct TraceCollector {
+    /// expression IDs and the total cost when they began evaluating
+    pending: Vec<(u64, ExecutionCost)>,
+    entries: Vec<TraceEntry>,
+}
+
+impl TraceCollector {
+    pub fn new() -> TraceCollector {
+        TraceCollector::default()
+    }
+
+    pub fn entries(&self) -> &[TraceEntry] {
+        &self.entries
+    }
+
+    pub fn into_entries(self) -> Vec<TraceEntry> {
+        self.entries
+    }
+}
+
+impl EvalHook for TraceCollector {
+    fn will_begin_eval(
+        &mut self,
+        env: &mut Environment,
+        _context: &LocalContext,
+        expr: &SymbolicExpression,
+    ) {
+        self.pending
+            .push((expr.id, env.global_context.cost_track.get_total()));
+    }
+
+    fn did_finish_eval(
+        &mut self,
+        env: &mut Environment,
+        _context: &LocalContext,
+        expr: &SymbolicExpression,
+        res: &Result<Value, Error>,
+    ) {
+        // `eval` can bail out before finishing a sub-expression, so skip over any
+        // sub-expressions that never finished.
+        let mut start_cost = ExecutionCost::zero();
+        while let Some((id, cost)) = self.pending.pop() {
+            if id == expr.id {
+                start_cost = cost;
+                break;
+            }
+        }
+
+        let mut cost = env.global_context.cost_track.get_total();
+        if cost.sub(&start_cost).is_err() {
+            cost = ExecutionCost::zero();
+        }
+
+        self.entries.push(TraceEntry {
+            expr: expr.clone(),
+            result: res.as_ref().cloned().map_err(|e| e.to_string()),
+            cost,
+        });
+    }
+
+    fn did_complete(&mut self, _result: Result<&mut ExecutionResult, String>) {}
+}
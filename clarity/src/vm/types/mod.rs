@@ -34,7 +34,8 @@ use crate::vm::errors::{
     CheckErrors, IncomparableError, InterpreterError, InterpreterResult as Result, RuntimeErrorType,
 };
 use crate::vm::representations::{
-    ClarityName, ContractName, SymbolicExpression, SymbolicExpressionType,
+    ClarityName, ContractName, NameError, SymbolicExpression, SymbolicExpressionType,
+    MAX_STRING_LEN,
 };
 pub use crate::vm::types::signatures::{
     parse_name_type_pairs, AssetIdentifier, BufferLength, FixedFunction, FunctionArg,
@@ -114,20 +115,81 @@ impl QualifiedContractIdentifier {
     }
 
     pub fn parse(literal: &str) -> Result<QualifiedContractIdentifier> {
-        let split: Vec<_> = literal.splitn(2, '.').collect();
-        if split.len() != 2 {
-            return Err(RuntimeErrorType::ParseError(
-                "Invalid principal literal: expected a `.` in a qualified contract name"
-                    .to_string(),
-            )
-            .into());
+        Ok(Self::parse_checked(literal)?)
+    }
+
+    /// Parse `<address>.<contract-name>`, reporting which part of the literal is malformed
+    pub fn parse_checked(
+        literal: &str,
+    ) -> std::result::Result<QualifiedContractIdentifier, ContractIdentifierParseError> {
+        let (address, name) = literal
+            .split_once('.')
+            .ok_or(ContractIdentifierParseError::MissingSeparator)?;
+        let sender =
+            decode_standard_principal(address).map_err(ContractIdentifierParseError::BadAddress)?;
+        if name.len() > MAX_STRING_LEN as usize {
+            return Err(ContractIdentifierParseError::NameTooLong(name.to_string()));
         }
-        let sender = PrincipalData::parse_standard_principal(split[0])?;
-        let name = split[1].to_string().try_into()?;
+        ContractName::validate(name).map_err(|reason| {
+            ContractIdentifierParseError::InvalidName(name.to_string(), reason)
+        })?;
+        // `validate` accepts exactly the names that `try_from` accepts, so this cannot fail
+        let name = ContractName::try_from(name.to_string()).map_err(|_| {
+            ContractIdentifierParseError::InvalidName(name.to_string(), NameError::Empty)
+        })?;
         Ok(QualifiedContractIdentifier::new(sender, name))
     }
 }
 
+/// Why a `QualifiedContractIdentifier` could not be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContractIdentifierParseError {
+    /// There is no `.` between the issuer address and the contract name
+    MissingSeparator,
+    /// The issuer address is not a valid c32 address
+    BadAddress(String),
+    /// The contract name is not a legal contract name, for the given reason
+    InvalidName(String, NameError),
+    /// The contract name is longer than `MAX_STRING_LEN` bytes
+    NameTooLong(String),
+}
+
+impl fmt::Display for ContractIdentifierParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ContractIdentifierParseError::MissingSeparator => write!(
+                f,
+                "Invalid principal literal: expected a `.` in a qualified contract name"
+            ),
+            ContractIdentifierParseError::BadAddress(reason) => {
+                write!(f, "Invalid principal literal: {}", reason)
+            }
+            ContractIdentifierParseError::InvalidName(name, reason) => {
+                write!(f, "Invalid contract name {}: {}", name, reason)
+            }
+            ContractIdentifierParseError::NameTooLong(name) => write!(
+                f,
+                "Invalid contract name: {} is longer than {} bytes",
+                name, MAX_STRING_LEN
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ContractIdentifierParseError {}
+
+impl From<ContractIdentifierParseError> for crate::vm::errors::Error {
+    fn from(err: ContractIdentifierParseError) -> Self {
+        match err {
+            ContractIdentifierParseError::InvalidName(name, _)
+            | ContractIdentifierParseError::NameTooLong(name) => {
+                RuntimeErrorType::BadNameValue("ContractName", name).into()
+            }
+            _ => RuntimeErrorType::ParseError(err.to_string()).into(),
+        }
+    }
+}
+
 impl fmt::Display for QualifiedContractIdentifier {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}.{}", self.issuer, self.name)
@@ -905,6 +967,210 @@ impl Value {
         Ok(TypeSignature::type_of(self)?.depth())
     }
 
+    /// Approximate in-memory size of this value, in bytes, for memory accounting.
+    /// Unlike `size()`, this is computed from the value's contents rather than its type
+    /// signature, so it does not allocate: sequences count their actual length, tuples
+    /// sum their field names and values, and lists sum their elements.
+    pub fn repr_size(&self) -> u64 {
+        match self {
+            Value::Int(_) | Value::UInt(_) => 16,
+            Value::Bool(_) => 1,
+            // version byte + 20-byte hash, plus the contract name for contract principals
+            Value::Principal(PrincipalData::Standard(_)) => 21,
+            Value::Principal(PrincipalData::Contract(contract))
+            | Value::CallableContract(CallableData {
+                contract_identifier: contract,
+                ..
+            }) => 21 + contract.name.len() as u64,
+            Value::Sequence(SequenceData::Buffer(buff)) => 4 + buff.data.len() as u64,
+            Value::Sequence(SequenceData::String(CharType::ASCII(ascii))) => {
+                4 + ascii.data.len() as u64
+            }
+            Value::Sequence(SequenceData::String(CharType::UTF8(utf8))) => utf8
+                .data
+                .iter()
+                .fold(4, |total, c| total.saturating_add(c.len() as u64)),
+            Value::Sequence(SequenceData::List(list)) => list
+                .data
+                .iter()
+                .fold(4, |total, item| total.saturating_add(item.repr_size())),
+            Value::Tuple(tuple) => tuple.data_map.iter().fold(0, |total, (name, value)| {
+                total
+                    .saturating_add(name.len() as u64)
+                    .saturating_add(value.repr_size())
+            }),
+            Value::Optional(OptionalData { data: None }) => u64::from(WRAPPER_VALUE_SIZE),
+            Value::Optional(OptionalData { data: Some(value) }) => {
+                u64::from(WRAPPER_VALUE_SIZE).saturating_add(value.repr_size())
+            }
+            Value::Response(response) => {
+                u64::from(WRAPPER_VALUE_SIZE).saturating_add(response.data.repr_size())
+            }
+        }
+    }
+
+    /// A deterministic total order over all values, so that values of any (and mixed) types can
+    /// be sorted consistently. Values of different kinds are ordered by kind:
+    ///
+    ///   int < uint < bool < buff < string-ascii < string-utf8 < list < principal
+    ///     < callable contract < tuple < optional < response
+    ///
+    /// Values of the same kind are ordered as follows:
+    ///  * ints and uints numerically, and `false` before `true`
+    ///  * buffers and strings lexicographically by their bytes, so a prefix sorts first
+    ///  * lists lexicographically by their elements, using this order
+    ///  * standard principals before contract principals; standard principals by version, then
+    ///    hash bytes; contract principals by issuer, then name
+    ///  * callable contracts by contract identifier, then trait identifier (none first)
+    ///  * tuples lexicographically by their (field name, field value) pairs in field name order
+    ///  * `none` before `(some ..)`, and `(err ..)` before `(ok ..)`, then by the wrapped value
+    pub fn canonical_cmp(&self, other: &Value) -> cmp::Ordering {
+        use cmp::Ordering;
+        match (self, other) {
+            (Value::Int(a), Value::Int(b)) => a.cmp(b),
+            (Value::UInt(a), Value::UInt(b)) => a.cmp(b),
+            (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+            (
+                Value::Sequence(SequenceData::Buffer(a)),
+                Value::Sequence(SequenceData::Buffer(b)),
+            ) => a.data.cmp(&b.data),
+            (
+                Value::Sequence(SequenceData::String(CharType::ASCII(a))),
+                Value::Sequence(SequenceData::String(CharType::ASCII(b))),
+            ) => a.data.cmp(&b.data),
+            (
+                Value::Sequence(SequenceData::String(CharType::UTF8(a))),
+                Value::Sequence(SequenceData::String(CharType::UTF8(b))),
+            ) => a.data.cmp(&b.data),
+            (Value::Sequence(SequenceData::List(a)), Value::Sequence(SequenceData::List(b))) => a
+                .data
+                .iter()
+                .zip(b.data.iter())
+                .map(|(x, y)| x.canonical_cmp(y))
+                .find(|ord| ord.is_ne())
+                .unwrap_or_else(|| a.data.len().cmp(&b.data.len())),
+            (Value::Principal(a), Value::Principal(b)) => match (a, b) {
+                (PrincipalData::Standard(a), PrincipalData::Standard(b)) => a.cmp(b),
+                (PrincipalData::Contract(a), PrincipalData::Contract(b)) => a.cmp(b),
+                (PrincipalData::Standard(_), PrincipalData::Contract(_)) => Ordering::Less,
+                (PrincipalData::Contract(_), PrincipalData::Standard(_)) => Ordering::Greater,
+            },
+            (Value::CallableContract(a), Value::CallableContract(b)) => a
+                .contract_identifier
+                .cmp(&b.contract_identifier)
+                .then_with(|| a.trait_identifier.cmp(&b.trait_identifier)),
+            (Value::Tuple(a), Value::Tuple(b)) => a
+                .data_map
+                .iter()
+                .zip(b.data_map.iter())
+                .map(|((x_name, x), (y_name, y))| {
+                    x_name.cmp(y_name).then_with(|| x.canonical_cmp(y))
+                })
+                .find(|ord| ord.is_ne())
+                .unwrap_or_else(|| a.data_map.len().cmp(&b.data_map.len())),
+            (Value::Optional(a), Value::Optional(b)) => match (&a.data, &b.data) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Less,
+                (Some(_), None) => Ordering::Greater,
+                (Some(a), Some(b)) => a.canonical_cmp(b),
+            },
+            (Value::Response(a), Value::Response(b)) => a
+                .committed
+                .cmp(&b.committed)
+                .then_with(|| a.data.canonical_cmp(&b.data)),
+            _ => self.canonical_rank().cmp(&other.canonical_rank()),
+        }
+    }
+
+    /// Position of this value's kind in the order used by `canonical_cmp`
+    fn canonical_rank(&self) -> u8 {
+        match self {
+            Value::Int(_) => 0,
+            Value::UInt(_) => 1,
+            Value::Bool(_) => 2,
+            Value::Sequence(SequenceData::Buffer(_)) => 3,
+            Value::Sequence(SequenceData::String(CharType::ASCII(_))) => 4,
+            Value::Sequence(SequenceData::String(CharType::UTF8(_))) => 5,
+            Value::Sequence(SequenceData::List(_)) => 6,
+            Value::Principal(_) => 7,
+            Value::CallableContract(_) => 8,
+            Value::Tuple(_) => 9,
+            Value::Optional(_) => 10,
+            Value::Response(_) => 11,
+        }
+    }
+
+    /// Render this value as Clarity source which evaluates back to an equal value, e.g.
+    /// `(tuple (a u1) (b "x"))`, `0x1234`, or `'SP000000000000000000002Q6VF78.pox`.
+    /// Callable contracts are rendered as their contract principal, since that is the literal
+    /// from which they are constructed.
+    pub fn to_clarity_literal(&self) -> String {
+        let mut out = String::new();
+        self.write_clarity_literal(&mut out);
+        out
+    }
+
+    fn write_clarity_literal(&self, out: &mut String) {
+        match self {
+            Value::Int(int) => out.push_str(&int.to_string()),
+            Value::UInt(int) => out.push_str(&format!("u{}", int)),
+            Value::Bool(boolean) => out.push_str(&boolean.to_string()),
+            Value::Principal(principal) => out.push_str(&format!("'{}", principal)),
+            Value::CallableContract(callable) => {
+                out.push_str(&format!("'{}", callable.contract_identifier))
+            }
+            Value::Sequence(SequenceData::Buffer(buff)) => out.push_str(&format!("0x{}", buff)),
+            Value::Sequence(SequenceData::String(CharType::ASCII(ascii))) => {
+                out.push('"');
+                for c in ascii.data.iter() {
+                    push_escaped_char(out, *c as char);
+                }
+                out.push('"');
+            }
+            Value::Sequence(SequenceData::String(CharType::UTF8(utf8))) => {
+                out.push_str("u\"");
+                for c in utf8.data.iter() {
+                    match str::from_utf8(c).ok().and_then(|c| c.chars().next()) {
+                        Some(c) if c.is_ascii() => push_escaped_char(out, c),
+                        Some(c) => out.push_str(&format!("\\u{{{:x}}}", c as u32)),
+                        None => out.push_str(&format!("\\u{{{}}}", hash::to_hex(c))),
+                    }
+                }
+                out.push('"');
+            }
+            Value::Sequence(SequenceData::List(list)) => {
+                out.push_str("(list");
+                for item in list.data.iter() {
+                    out.push(' ');
+                    item.write_clarity_literal(out);
+                }
+                out.push(')');
+            }
+            Value::Tuple(tuple) => {
+                out.push_str("(tuple");
+                for (name, value) in tuple.data_map.iter() {
+                    out.push_str(" (");
+                    out.push_str(name);
+                    out.push(' ');
+                    value.write_clarity_literal(out);
+                    out.push(')');
+                }
+                out.push(')');
+            }
+            Value::Optional(OptionalData { data: None }) => out.push_str("none"),
+            Value::Optional(OptionalData { data: Some(value) }) => {
+                out.push_str("(some ");
+                value.write_clarity_literal(out);
+                out.push(')');
+            }
+            Value::Response(response) => {
+                out.push_str(if response.committed { "(ok " } else { "(err " });
+                response.data.write_clarity_literal(out);
+                out.push(')');
+            }
+        }
+    }
+
     /// Invariant: the supplied Values have already been "checked", i.e., it's a valid Value object
     ///  this invariant is enforced through the Value constructors, each of which checks to ensure
     ///  that any typing data is correct.
@@ -1367,19 +1633,21 @@ impl PrincipalData {
     }
 
     pub fn parse_standard_principal(literal: &str) -> Result<StandardPrincipalData> {
-        let (version, data) = c32::c32_address_decode(literal).map_err(|x| {
-            RuntimeErrorType::ParseError(format!("Invalid principal literal: {}", x))
-        })?;
-        if data.len() != 20 {
-            return Err(RuntimeErrorType::ParseError(
-                "Invalid principal literal: Expected 20 data bytes.".to_string(),
-            )
-            .into());
-        }
-        let mut fixed_data = [0; 20];
-        fixed_data.copy_from_slice(&data[..20]);
-        Ok(StandardPrincipalData(version, fixed_data))
+        decode_standard_principal(literal).map_err(|reason| {
+            RuntimeErrorType::ParseError(format!("Invalid principal literal: {}", reason)).into()
+        })
+    }
+}
+
+/// Decode a c32 address into a standard principal, or explain why it is not one
+fn decode_standard_principal(literal: &str) -> std::result::Result<StandardPrincipalData, String> {
+    let (version, data) = c32::c32_address_decode(literal).map_err(|x| x.to_string())?;
+    if data.len() != 20 {
+        return Err("Expected 20 data bytes.".to_string());
     }
+    let mut fixed_data = [0; 20];
+    fixed_data.copy_from_slice(&data[..20]);
+    Ok(StandardPrincipalData(version, fixed_data))
 }
 
 impl StandardPrincipalData {
@@ -1607,6 +1875,19 @@ impl fmt::Display for TupleData {
     }
 }
 
+/// Push `c` onto a Clarity string literal, escaping it if the lexer requires it.
+fn push_escaped_char(out: &mut String, c: char) {
+    match c {
+        '\\' => out.push_str("\\\\"),
+        '"' => out.push_str("\\\""),
+        '\n' => out.push_str("\\n"),
+        '\t' => out.push_str("\\t"),
+        '\r' => out.push_str("\\r"),
+        '\0' => out.push_str("\\0"),
+        _ => out.push(c),
+    }
+}
+
 /// Given the serialized string representation of a Clarity value,
 ///  return the size of the same byte representation.
 pub fn byte_len_of_serialization(serialized: &str) -> u64 {
@@ -1715,6 +1996,277 @@ mod test {
         assert_eq!(Value::Int(10).size().unwrap(), 16);
     }
 
+    #[test]
+    fn repr_size_nested() {
+        assert_eq!(Value::Int(10).repr_size(), 16);
+        assert_eq!(Value::buff_from(vec![1, 2, 3]).unwrap().repr_size(), 7);
+
+        // 4-byte list header + 3 ints
+        let ints = Value::list_from(vec![Value::Int(1), Value::Int(2), Value::Int(3)]).unwrap();
+        assert_eq!(ints.repr_size(), 4 + 3 * 16);
+
+        // field names + field values
+        let tuple = Value::from(
+            TupleData::from_data(vec![
+                ("a".into(), Value::Bool(true)),
+                ("items".into(), ints.clone()),
+                ("opt".into(), Value::some(Value::UInt(1)).unwrap()),
+            ])
+            .unwrap(),
+        );
+        assert_eq!(tuple.repr_size(), (1 + 1) + (5 + 52) + (3 + 1 + 16));
+
+        let nested = Value::list_from(vec![tuple.clone(), tuple.clone()]).unwrap();
+        assert_eq!(nested.repr_size(), 4 + 2 * tuple.repr_size());
+
+        let outer = Value::okay(Value::from(
+            TupleData::from_data(vec![("inner".into(), nested.clone())]).unwrap(),
+        ))
+        .unwrap();
+        assert_eq!(outer.repr_size(), 1 + 5 + nested.repr_size());
+    }
+
+    #[test]
+    fn canonical_cmp_across_types() {
+        let ordered = vec![
+            Value::Int(-5),
+            Value::Int(3),
+            Value::UInt(0),
+            Value::UInt(7),
+            Value::Bool(false),
+            Value::Bool(true),
+            Value::buff_from(vec![]).unwrap(),
+            Value::buff_from(vec![1]).unwrap(),
+            Value::buff_from(vec![1, 0]).unwrap(),
+            Value::buff_from(vec![2]).unwrap(),
+            Value::string_ascii_from_bytes(b"abc".to_vec()).unwrap(),
+            Value::string_utf8_from_bytes("abc".as_bytes().to_vec()).unwrap(),
+            Value::list_from(vec![Value::Int(1)]).unwrap(),
+            Value::from(StandardPrincipalData::transient()),
+            Value::from(QualifiedContractIdentifier::transient()),
+            Value::from(TupleData::from_data(vec![("a".into(), Value::Int(1))]).unwrap()),
+            Value::none(),
+            Value::some(Value::Int(1)).unwrap(),
+            Value::error(Value::Int(1)).unwrap(),
+            Value::okay(Value::Int(1)).unwrap(),
+        ];
+
+        for (i, a) in ordered.iter().enumerate() {
+            assert_eq!(a.canonical_cmp(a), cmp::Ordering::Equal);
+            for b in ordered[i + 1..].iter() {
+                assert_eq!(a.canonical_cmp(b), cmp::Ordering::Less, "{a} < {b}");
+                assert_eq!(b.canonical_cmp(a), cmp::Ordering::Greater, "{b} > {a}");
+            }
+        }
+
+        let mut shuffled: Vec<_> = ordered.iter().rev().cloned().collect();
+        shuffled.sort_by(Value::canonical_cmp);
+        assert_eq!(shuffled, ordered);
+    }
+
+    #[test]
+    fn canonical_cmp_nested() {
+        let list = |items: Vec<Value>| Value::list_from(items).unwrap();
+        let tuple = |fields: Vec<(&str, Value)>| {
+            Value::from(
+                TupleData::from_data(
+                    fields
+                        .into_iter()
+                        .map(|(name, value)| (name.into(), value))
+                        .collect(),
+                )
+                .unwrap(),
+            )
+        };
+
+        // lists compare element-wise, then by length
+        assert_eq!(
+            list(vec![Value::Int(1), Value::Int(2)]).canonical_cmp(&list(vec![Value::Int(2)])),
+            cmp::Ordering::Less
+        );
+        assert_eq!(
+            list(vec![Value::Int(1)]).canonical_cmp(&list(vec![Value::Int(1), Value::Int(0)])),
+            cmp::Ordering::Less
+        );
+
+        // tuples compare by sorted field names before values, regardless of construction order
+        let t1 = tuple(vec![("b", Value::Int(0)), ("a", Value::Int(9))]);
+        let t2 = tuple(vec![("a", Value::Int(9)), ("b", Value::Int(0))]);
+        assert_eq!(t1.canonical_cmp(&t2), cmp::Ordering::Equal);
+        assert_eq!(
+            tuple(vec![("a", Value::Int(100))]).canonical_cmp(&tuple(vec![("b", Value::Int(0))])),
+            cmp::Ordering::Less
+        );
+        assert_eq!(
+            tuple(vec![("a", Value::Int(1)), ("b", Value::Int(5))])
+                .canonical_cmp(&tuple(vec![("a", Value::Int(1)), ("b", Value::Int(6))])),
+            cmp::Ordering::Less
+        );
+
+        // wrapped values compare by their contents
+        let nested = |n| {
+            Value::okay(Value::some(list(vec![tuple(vec![("x", Value::UInt(n))])])).unwrap())
+                .unwrap()
+        };
+        assert_eq!(nested(1).canonical_cmp(&nested(2)), cmp::Ordering::Less);
+        assert_eq!(nested(2).canonical_cmp(&nested(2)), cmp::Ordering::Equal);
+        assert_eq!(
+            Value::error(nested(9))
+                .unwrap()
+                .canonical_cmp(&Value::okay(nested(0)).unwrap()),
+            cmp::Ordering::Less
+        );
+    }
+
+    #[test]
+    fn to_clarity_literal_round_trips() {
+        let contract =
+            QualifiedContractIdentifier::parse("SP000000000000000000002Q6VF78.pox").unwrap();
+        let values = vec![
+            (Value::Int(-42), "-42"),
+            (
+                Value::Int(i128::MIN),
+                "-170141183460469231731687303715884105728",
+            ),
+            (
+                Value::UInt(u128::MAX),
+                "u340282366920938463463374607431768211455",
+            ),
+            (Value::Bool(true), "true"),
+            (Value::Bool(false), "false"),
+            (Value::buff_from(vec![]).unwrap(), "0x"),
+            (Value::buff_from(vec![0x12, 0x34]).unwrap(), "0x1234"),
+            (
+                Value::string_ascii_from_bytes(b"a \"quoted\"\\ line\n".to_vec()).unwrap(),
+                r#""a \"quoted\"\\ line\n""#,
+            ),
+            (
+                Value::string_utf8_from_bytes("caf\u{e9} \u{1f600}\t".as_bytes().to_vec()).unwrap(),
+                r#"u"caf\u{e9} \u{1f600}\t""#,
+            ),
+            (
+                Value::from(PrincipalData::from(contract.issuer.clone())),
+                "'SP000000000000000000002Q6VF78",
+            ),
+            (
+                Value::from(PrincipalData::from(contract.clone())),
+                "'SP000000000000000000002Q6VF78.pox",
+            ),
+            (
+                Value::list_from(vec![Value::UInt(1), Value::UInt(2)]).unwrap(),
+                "(list u1 u2)",
+            ),
+            (Value::list_from(vec![]).unwrap(), "(list)"),
+            (
+                Value::from(
+                    TupleData::from_data(vec![
+                        (
+                            "b".into(),
+                            Value::string_ascii_from_bytes(b"x".to_vec()).unwrap(),
+                        ),
+                        ("a".into(), Value::UInt(1)),
+                    ])
+                    .unwrap(),
+                ),
+                r#"(tuple (a u1) (b "x"))"#,
+            ),
+            (Value::none(), "none"),
+            (Value::some(Value::Int(1)).unwrap(), "(some 1)"),
+            (Value::okay(Value::Bool(true)).unwrap(), "(ok true)"),
+            (Value::error(Value::UInt(3)).unwrap(), "(err u3)"),
+            (
+                Value::okay(
+                    Value::list_from(vec![
+                        Value::some(Value::from(
+                            TupleData::from_data(vec![(
+                                "owner".into(),
+                                Value::from(PrincipalData::from(contract.clone())),
+                            )])
+                            .unwrap(),
+                        ))
+                        .unwrap(),
+                        Value::none(),
+                    ])
+                    .unwrap(),
+                )
+                .unwrap(),
+                "(ok (list (some (tuple (owner 'SP000000000000000000002Q6VF78.pox))) none))",
+            ),
+        ];
+
+        for (value, expected) in values {
+            let literal = value.to_clarity_literal();
+            assert_eq!(literal, expected);
+            assert_eq!(
+                crate::vm::execute_v2(&literal).unwrap(),
+                Some(value),
+                "{literal}"
+            );
+        }
+
+        // callable contracts are written as their contract principal
+        let callable = Value::CallableContract(CallableData {
+            contract_identifier: contract,
+            trait_identifier: None,
+        });
+        assert_eq!(
+            callable.to_clarity_literal(),
+            "'SP000000000000000000002Q6VF78.pox"
+        );
+    }
+
+    #[test]
+    fn parse_checked_contract_identifiers() {
+        let parsed =
+            QualifiedContractIdentifier::parse_checked("SP000000000000000000002Q6VF78.pox-4")
+                .unwrap();
+        assert_eq!(parsed.to_string(), "SP000000000000000000002Q6VF78.pox-4");
+
+        assert_eq!(
+            QualifiedContractIdentifier::parse_checked("SP000000000000000000002Q6VF78"),
+            Err(ContractIdentifierParseError::MissingSeparator)
+        );
+        assert!(matches!(
+            QualifiedContractIdentifier::parse_checked("not-an-address.pox"),
+            Err(ContractIdentifierParseError::BadAddress(_))
+        ));
+        assert_eq!(
+            QualifiedContractIdentifier::parse_checked("SP000000000000000000002Q6VF78.1pox"),
+            Err(ContractIdentifierParseError::InvalidName(
+                "1pox".into(),
+                NameError::LeadingDigit
+            ))
+        );
+        assert_eq!(
+            QualifiedContractIdentifier::parse_checked("SP000000000000000000002Q6VF78."),
+            Err(ContractIdentifierParseError::InvalidName(
+                "".into(),
+                NameError::Empty
+            ))
+        );
+        let long_name = "a".repeat(MAX_STRING_LEN as usize + 1);
+        assert_eq!(
+            QualifiedContractIdentifier::parse_checked(&format!(
+                "SP000000000000000000002Q6VF78.{}",
+                long_name
+            )),
+            Err(ContractIdentifierParseError::NameTooLong(long_name))
+        );
+
+        // `parse` keeps reporting the same errors as before
+        assert_eq!(
+            QualifiedContractIdentifier::parse("SP000000000000000000002Q6VF78.1pox"),
+            Err(RuntimeErrorType::BadNameValue("ContractName", "1pox".into()).into())
+        );
+        assert_eq!(
+            QualifiedContractIdentifier::parse("SP000000000000000000002Q6VF78"),
+            Err(RuntimeErrorType::ParseError(
+                "Invalid principal literal: expected a `.` in a qualified contract name".into()
+            )
+            .into())
+        );
+    }
+
     #[test]
     fn simple_tuple_get_test() {
         let t = TupleData::from_data(vec![("abc".into(), Value::Int(0))]).unwrap();
@@ -54,6 +54,9 @@ pub enum SerializationError {
     DeserializeExpected(TypeSignature),
     LeftoverBytesInDeserialization,
     SerializationError(String),
+    /// The lengths declared by the serialized value would require allocating more
+    ///  than the permitted number of bytes
+    TooLarge,
 }
 
 lazy_static! {
@@ -72,6 +75,13 @@ const DESERIALIZATION_TYPE_CHECK_EPOCH: StacksEpochId = StacksEpochId::Epoch21;
 ///  supported, so we increase the bound to a higher level limit imposed by the cost checker.
 const SANITIZATION_READ_BOUND: u64 = 15_000_000;
 
+/// Default bound on the bytes `try_deserialize_hex_untyped` will allocate while decoding a value,
+///  as declared by the length prefixes of its buffers, strings, lists and tuples. Each list item
+///  is charged `size_of::<Value>()`, and a list holds at most `MAX_VALUE_SIZE` items, so twice
+///  that leaves room for the largest list of booleans along with whatever else the value holds.
+pub const DEFAULT_DESERIALIZATION_ALLOCATION_BOUND: u64 =
+    2 * MAX_VALUE_SIZE as u64 * std::mem::size_of::<Value>() as u64;
+
 /// Before epoch-2.4, this is the deserialization depth limit.
 /// After epoch-2.4, with type sanitization support, the full
 ///  clarity depth limit is supported.
@@ -100,6 +110,9 @@ impl std::fmt::Display for SerializationError {
             SerializationError::LeftoverBytesInDeserialization => {
                 write!(f, "Deserialization error: bytes left over in buffer")
             }
+            SerializationError::TooLarge => {
+                write!(f, "Deserialization error: value exceeds allocation bound")
+            }
         }
     }
 }
@@ -118,7 +131,7 @@ impl error::Error for SerializationError {
 //   there are available bytes to read will result in an IOError(UnexpectedEOF)
 impl From<std::io::Error> for SerializationError {
     fn from(err: std::io::Error) -> Self {
-        SerializationError::IOError(IncomparableError { err })
+        SerializationError::IOError(IncomparableError::new(err))
     }
 }
 
@@ -545,7 +558,8 @@ impl Value {
             BOUND_VALUE_SERIALIZATION_BYTES as u64
         };
         let mut bound_reader = BoundReader::from_reader(r, bound_value_serialization_bytes);
-        let value = Value::inner_deserialize_read(&mut bound_reader, expected_type, sanitize)?;
+        let value =
+            Value::inner_deserialize_read(&mut bound_reader, expected_type, sanitize, u64::MAX)?;
         let bytes_read = bound_reader.num_read();
         if let Some(expected_type) = expected_type {
             let expect_size = match expected_type.max_serialized_size() {
@@ -573,14 +587,27 @@ impl Value {
         Ok((value, bytes_read))
     }
 
+    /// Deserialize a value from `r`. `allocation_bound` caps the total number of bytes that the
+    ///  length prefixes in the input may cause to be allocated; exceeding it fails with
+    ///  `SerializationError::TooLarge` before the allocation happens.
     fn inner_deserialize_read<R: Read>(
         r: &mut R,
         top_expected_type: Option<&TypeSignature>,
         sanitize: bool,
+        allocation_bound: u64,
     ) -> Result<Value, SerializationError> {
         use super::PrincipalData::*;
         use super::Value::*;
 
+        let mut allocated: u64 = 0;
+        let mut allocate = |bytes: u64| -> Result<(), SerializationError> {
+            allocated = allocated
+                .checked_add(bytes)
+                .filter(|total| *total <= allocation_bound)
+                .ok_or(SerializationError::TooLarge)?;
+            Ok(())
+        };
+
         let mut stack = vec![DeserializeStackItem::TopLevel {
             expected_type: top_expected_type.cloned(),
         }];
@@ -635,6 +662,7 @@ impl Value {
                         }
                     }
 
+                    allocate(u64::from(u32::from(&buffer_len)))?;
                     let mut data = vec![0; u32::from(buffer_len) as usize];
 
                     r.read_exact(&mut data[..])?;
@@ -735,6 +763,7 @@ impl Value {
                     };
 
                     if len > 0 {
+                        allocate(u64::from(len) * std::mem::size_of::<Value>() as u64)?;
                         let items = Vec::with_capacity(len as usize);
                         let stack_item = DeserializeStackItem::List {
                             items,
@@ -797,6 +826,9 @@ impl Value {
                     };
 
                     if len > 0 {
+                        allocate(
+                            expected_len * std::mem::size_of::<(ClarityName, Value)>() as u64,
+                        )?;
                         let items = Vec::with_capacity(expected_len as usize);
                         let first_key = ClarityName::deserialize_read(r)?;
                         // figure out if the next (key, value) pair for this
@@ -855,6 +887,7 @@ impl Value {
                         }
                     }
 
+                    allocate(u64::from(u32::from(&buffer_len)))?;
                     let mut data = vec![0; u32::from(buffer_len) as usize];
 
                     r.read_exact(&mut data[..])?;
@@ -866,6 +899,7 @@ impl Value {
                     r.read_exact(&mut total_len)?;
                     let total_len = BufferLength::try_from(u32::from_be_bytes(total_len))?;
 
+                    allocate(u64::from(u32::from(&total_len)))?;
                     let mut data: Vec<u8> = vec![0; u32::from(total_len) as usize];
 
                     r.read_exact(&mut data[..])?;
@@ -1159,7 +1193,21 @@ impl Value {
     /// Try to deserialize a value without type information. This *does not* perform sanitization
     ///  so it should not be used when decoding clarity database values.
     fn try_deserialize_bytes_untyped(bytes: &Vec<u8>) -> Result<Value, SerializationError> {
-        Value::deserialize_read(&mut bytes.as_slice(), None, false)
+        Value::try_deserialize_bounded(bytes, DEFAULT_DESERIALIZATION_ALLOCATION_BOUND)
+    }
+
+    /// Try to deserialize a value without type information, failing with
+    ///  `SerializationError::TooLarge` if the lengths declared for its buffers, strings, lists
+    ///  and tuples would require allocating more than `max_size` bytes. This *does not* perform
+    ///  sanitization, so it should not be used when decoding clarity database values.
+    pub fn try_deserialize_bounded(
+        bytes: &[u8],
+        max_size: u64,
+    ) -> Result<Value, SerializationError> {
+        let mut reader = bytes;
+        let mut bound_reader =
+            BoundReader::from_reader(&mut reader, BOUND_VALUE_SERIALIZATION_BYTES as u64);
+        Value::inner_deserialize_read(&mut bound_reader, None, false, max_size)
     }
 
     /// Try to deserialize a value from a hex string without type information. This *does not*
@@ -1379,9 +1427,10 @@ pub mod tests {
     use rstest::rstest;
     use rstest_reuse::{self, *};
     use stacks_common::types::StacksEpochId;
+    use stacks_common::util::hash::to_hex;
 
     use super::super::*;
-    use super::SerializationError;
+    use super::{SerializationError, DEFAULT_DESERIALIZATION_ALLOCATION_BOUND};
     use crate::vm::database::{ClarityDeserializable, ClaritySerializable, RollbackWrapper};
     use crate::vm::errors::Error;
     use crate::vm::tests::test_clarity_versions;
@@ -2198,6 +2247,70 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn try_deser_bounded() {
+        // a buffer that declares 4096 bytes of data, but only carries 4
+        let mut buff = vec![2];
+        buff.extend_from_slice(&4096u32.to_be_bytes());
+        buff.extend_from_slice(&[0, 1, 2, 3]);
+        assert_eq!(
+            Value::try_deserialize_bounded(&buff, 1024).unwrap_err(),
+            SerializationError::TooLarge
+        );
+
+        // nested lists which each declare the maximum number of items, so that decoding
+        //  them would pre-allocate more than the default bound
+        let mut nested_lists = vec![];
+        for _ in 0..3 {
+            nested_lists.push(11);
+            nested_lists.extend_from_slice(&MAX_VALUE_SIZE.to_be_bytes());
+        }
+        assert_eq!(
+            Value::try_deserialize_bounded(&nested_lists, DEFAULT_DESERIALIZATION_ALLOCATION_BOUND)
+                .unwrap_err(),
+            SerializationError::TooLarge
+        );
+        assert_eq!(
+            Value::try_deserialize_hex_untyped(&to_hex(&nested_lists)).unwrap_err(),
+            SerializationError::TooLarge
+        );
+
+        // values within the bound still decode
+        let value = Value::cons_list_unsanitized(vec![Value::Int(1), Value::Int(2)]).unwrap();
+        assert_eq!(
+            Value::try_deserialize_bounded(&value.serialize_to_vec().unwrap(), 1024).unwrap(),
+            value
+        );
+    }
+
+    #[test]
+    fn try_deser_bounded_max_bool_list() {
+        // find the longest list of booleans a value can hold
+        let (mut lo, mut hi) = (1, MAX_VALUE_SIZE);
+        while lo < hi {
+            let mid = lo + (hi - lo + 1) / 2;
+            if ListTypeData::new_list(BoolType, mid).is_ok() {
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+        let max_len = lo;
+
+        let mut bytes = vec![11];
+        bytes.extend_from_slice(&max_len.to_be_bytes());
+        bytes.resize(bytes.len() + max_len as usize, 3);
+
+        let value = Value::try_deserialize_hex_untyped(&to_hex(&bytes)).unwrap();
+        match value {
+            Value::Sequence(SequenceData::List(list)) => {
+                assert_eq!(list.data.len(), max_len as usize);
+                assert!(list.data.iter().all(|item| *item == Value::Bool(true)));
+            }
+            _ => panic!("Expected a list, got {}", value),
+        }
+    }
+
     #[test]
     fn try_overflow_stack() {
         let input = "08080808080808080808070707080807080808080808080708080808080708080707080707080807080808080808080708080808080708080707080708070807080808080808080708080808080708080708080808080808080807070807080808080808070808070707080807070808070808080808070808070708070807080808080808080707080708070807080708080808080808070808080808070808070808080808080808080707080708080808080807080807070708080707080807080808080807080807070807080708080808080808070708070808080808080708080707070808070708080807080807070708";
@@ -17,6 +17,7 @@
 use std::io;
 
 use clarity::vm::types::QualifiedContractIdentifier;
+use stacks_common::util::hash::Sha512Trunc256Sum;
 
 /// Errors originating from doing an RPC request to the Stacks node
 #[derive(thiserror::Error, Debug)]
@@ -72,3 +73,22 @@ pub enum EventError {
     #[error("Unrecognized StackerDB contract: {0}")]
     UnrecognizedStackerDBContract(QualifiedContractIdentifier),
 }
+
+/// Errors from verifying a `BlockResponse` written by the signers
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum BlockResponseError {
+    /// The message is not a block response
+    #[error("Not a block response")]
+    NotABlockResponse,
+    /// The response is for a different block than the one expected
+    #[error("Block response is for {actual}, expected {expected}")]
+    HashMismatch {
+        /// The signer signature hash of the expected block
+        expected: Sha512Trunc256Sum,
+        /// The signer signature hash in the response
+        actual: Sha512Trunc256Sum,
+    },
+    /// The response's threshold signature does not verify against the aggregate key
+    #[error("Invalid threshold signature")]
+    InvalidSignature,
+}
@@ -449,7 +449,7 @@ impl TryFrom<StackerDBChunksEvent> for SignerEvent {
             }
             SignerEvent::MinerMessages(blocks, messages, miner_pk)
         } else if event.contract_id.name.starts_with(SIGNERS_NAME) && event.contract_id.is_boot() {
-            let Some((signer_set, _)) =
+            let Some((signer_set, message_id)) =
                 get_signers_db_signer_set_message_id(event.contract_id.name.as_str())
             else {
                 return Err(EventError::UnrecognizedStackerDBContract(event.contract_id));
@@ -459,6 +459,18 @@ impl TryFrom<StackerDBChunksEvent> for SignerEvent {
                 .modified_slots
                 .iter()
                 .filter_map(|chunk| read_next::<SignerMessage, _>(&mut &chunk.data[..]).ok())
+                .filter(|msg| {
+                    // Drop messages written to the wrong contract for their type
+                    let valid = msg.validate_slot(message_id);
+                    if !valid {
+                        warn!(
+                            "Dropping {:?} message written to {}",
+                            msg.msg_id(),
+                            &event.contract_id
+                        );
+                    }
+                    valid
+                })
                 .collect();
             SignerEvent::SignerMessages(signer_set, signer_messages)
         } else {
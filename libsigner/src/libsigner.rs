@@ -44,13 +44,16 @@ mod runloop;
 mod session;
 mod signer_set;
 
-pub use crate::error::{EventError, RPCError};
+pub use crate::error::{BlockResponseError, EventError, RPCError};
 pub use crate::events::{
     BlockProposalSigners, EventReceiver, EventStopSignaler, SignerEvent, SignerEventReceiver,
     SignerStopSignaler,
 };
+#[cfg(any(test, feature = "testing"))]
+pub use crate::messages::one_of_each_message_type;
 pub use crate::messages::{
-    BlockRejection, BlockResponse, MessageSlotID, RejectCode, SignerMessage,
+    validate_slot_for_message, BlockRejection, BlockResponse, BlockResponseKind, MessageSlotID,
+    RejectCode, SignerMessage,
 };
 pub use crate::runloop::{RunningSigner, Signer, SignerRunLoop};
 pub use crate::session::{SignerSession, StackerDBSession};
@@ -31,7 +31,7 @@ use std::sync::mpsc::Sender;
 use std::sync::Arc;
 
 use blockstack_lib::chainstate::nakamoto::signer_set::NakamotoSigners;
-use blockstack_lib::chainstate::nakamoto::NakamotoBlock;
+use blockstack_lib::chainstate::nakamoto::{NakamotoBlock, NakamotoBlockVote};
 use blockstack_lib::chainstate::stacks::events::StackerDBChunksEvent;
 use blockstack_lib::chainstate::stacks::{StacksTransaction, ThresholdSignature};
 use blockstack_lib::net::api::postblock_proposal::{
@@ -63,7 +63,7 @@ use wsts::schnorr::ID;
 use wsts::state_machine::{signer, SignError};
 
 use crate::http::{decode_http_body, decode_http_request};
-use crate::EventError;
+use crate::{BlockResponseError, EventError};
 
 define_u8_enum!(
 /// Enum representing the stackerdb message identifier: this is
@@ -105,7 +105,10 @@ define_u8_enum!(SignerMessageTypePrefix {
 });
 
 impl MessageSlotID {
-    /// Return the StackerDB contract corresponding to messages of this type
+    /// Return the StackerDB contract corresponding to messages of this type.
+    /// This is the boot contract `signers-{reward_cycle % 2}-{message ID}`, so the signer sets of
+    ///  consecutive reward cycles write to alternating contracts and each cycle's set reuses the
+    ///  contracts of the cycle two before it.
     pub fn stacker_db_contract(
         &self,
         mainnet: bool,
@@ -120,6 +123,96 @@ impl MessageSlotID {
     }
 }
 
+impl From<&Message> for MessageSlotID {
+    fn from(msg: &Message) -> Self {
+        match msg {
+            Message::DkgBegin(_) => MessageSlotID::DkgBegin,
+            Message::DkgPrivateBegin(_) => MessageSlotID::DkgPrivateBegin,
+            Message::DkgEndBegin(_) => MessageSlotID::DkgEndBegin,
+            Message::DkgEnd(_) => MessageSlotID::DkgEnd,
+            Message::DkgPublicShares(_) => MessageSlotID::DkgPublicShares,
+            Message::DkgPrivateShares(_) => MessageSlotID::DkgPrivateShares,
+            Message::NonceRequest(_) => MessageSlotID::NonceRequest,
+            Message::NonceResponse(_) => MessageSlotID::NonceResponse,
+            Message::SignatureShareRequest(_) => MessageSlotID::SignatureShareRequest,
+            Message::SignatureShareResponse(_) => MessageSlotID::SignatureShareResponse,
+        }
+    }
+}
+
+/// Check that a wsts message was written to the signers StackerDB contract for its type, i.e. that
+///  `message_id` (the Y in signers-X-Y) is the message's `MessageSlotID`
+pub fn validate_slot_for_message(message_id: u32, msg: &Message) -> bool {
+    MessageSlotID::from(msg).to_u32() == message_id
+}
+
+/// Build one wsts message of each type, in `MessageSlotID` order, for tests that need to cover
+///  every message type
+#[cfg(any(test, feature = "testing"))]
+pub fn one_of_each_message_type() -> Vec<Message> {
+    vec![
+        Message::DkgBegin(DkgBegin { dkg_id: 0 }),
+        Message::DkgPrivateBegin(DkgPrivateBegin {
+            dkg_id: 0,
+            signer_ids: vec![],
+            key_ids: vec![],
+        }),
+        Message::DkgEndBegin(DkgEndBegin {
+            dkg_id: 0,
+            signer_ids: vec![],
+            key_ids: vec![],
+        }),
+        Message::DkgEnd(DkgEnd {
+            dkg_id: 0,
+            signer_id: 0,
+            status: DkgStatus::Success,
+        }),
+        Message::DkgPublicShares(DkgPublicShares {
+            dkg_id: 0,
+            signer_id: 0,
+            comms: vec![],
+        }),
+        Message::DkgPrivateShares(DkgPrivateShares {
+            dkg_id: 0,
+            signer_id: 0,
+            shares: vec![],
+        }),
+        Message::NonceRequest(NonceRequest {
+            dkg_id: 0,
+            sign_id: 0,
+            sign_iter_id: 0,
+            message: vec![],
+            is_taproot: false,
+            merkle_root: None,
+        }),
+        Message::NonceResponse(NonceResponse {
+            dkg_id: 0,
+            sign_id: 0,
+            sign_iter_id: 0,
+            signer_id: 0,
+            key_ids: vec![],
+            nonces: vec![],
+            message: vec![],
+        }),
+        Message::SignatureShareRequest(SignatureShareRequest {
+            dkg_id: 0,
+            sign_id: 0,
+            sign_iter_id: 0,
+            nonce_responses: vec![],
+            message: vec![],
+            is_taproot: false,
+            merkle_root: None,
+        }),
+        Message::SignatureShareResponse(SignatureShareResponse {
+            dkg_id: 0,
+            sign_id: 0,
+            sign_iter_id: 0,
+            signer_id: 0,
+            signature_shares: vec![],
+        }),
+    ]
+}
+
 impl Display for MessageSlotID {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:?}({})", self, self.to_u8())
@@ -263,23 +356,69 @@ impl SignerMessage {
     /// Helper function to determine the slot ID for the provided stacker-db writer id
     pub fn msg_id(&self) -> MessageSlotID {
         match self {
-            Self::Packet(packet) => match packet.msg {
-                Message::DkgBegin(_) => MessageSlotID::DkgBegin,
-                Message::DkgPrivateBegin(_) => MessageSlotID::DkgPrivateBegin,
-                Message::DkgEndBegin(_) => MessageSlotID::DkgEndBegin,
-                Message::DkgEnd(_) => MessageSlotID::DkgEnd,
-                Message::DkgPublicShares(_) => MessageSlotID::DkgPublicShares,
-                Message::DkgPrivateShares(_) => MessageSlotID::DkgPrivateShares,
-                Message::NonceRequest(_) => MessageSlotID::NonceRequest,
-                Message::NonceResponse(_) => MessageSlotID::NonceResponse,
-                Message::SignatureShareRequest(_) => MessageSlotID::SignatureShareRequest,
-                Message::SignatureShareResponse(_) => MessageSlotID::SignatureShareResponse,
-            },
+            Self::Packet(packet) => MessageSlotID::from(&packet.msg),
             Self::BlockResponse(_) => MessageSlotID::BlockResponse,
             Self::Transactions(_) => MessageSlotID::Transactions,
             Self::DkgResults { .. } => MessageSlotID::DkgResults,
         }
     }
+
+    /// Check that this message was written to the signers StackerDB contract for its type, i.e.
+    ///  that `message_id` (the Y in signers-X-Y) is this message's `MessageSlotID`
+    pub fn validate_slot(&self, message_id: u32) -> bool {
+        self.msg_id().to_u32() == message_id
+    }
+
+    /// Verify that this message is a response to the block with the given signer signature hash,
+    ///  and that any threshold signature it carries was produced by the given aggregate key.
+    /// Accepted blocks are signed over the signer signature hash, and signed rejections over the
+    ///  serialized `NakamotoBlockVote` rejecting it. Rejections that carry no signature (e.g. a
+    ///  failed block validation) have nothing to verify beyond their hash.
+    pub fn verify_block_response(
+        &self,
+        aggregate_key: &Point,
+        expected_hash: &Sha512Trunc256Sum,
+    ) -> Result<BlockResponseKind, BlockResponseError> {
+        let Self::BlockResponse(response) = self else {
+            return Err(BlockResponseError::NotABlockResponse);
+        };
+        let (actual, signed_message, signature, kind) = match response {
+            BlockResponse::Accepted((hash, signature)) => (
+                hash,
+                hash.0.to_vec(),
+                Some(signature),
+                BlockResponseKind::Accepted,
+            ),
+            BlockResponse::Rejected(rejection) => {
+                let signature = match &rejection.reason_code {
+                    RejectCode::SignedRejection(signature) => Some(signature),
+                    _ => None,
+                };
+                let vote = NakamotoBlockVote {
+                    signer_signature_hash: rejection.signer_signature_hash,
+                    rejected: true,
+                };
+                (
+                    &rejection.signer_signature_hash,
+                    vote.serialize_to_vec(),
+                    signature,
+                    BlockResponseKind::Rejected(rejection.reason_code.clone()),
+                )
+            }
+        };
+        if actual != expected_hash {
+            return Err(BlockResponseError::HashMismatch {
+                expected: *expected_hash,
+                actual: *actual,
+            });
+        }
+        if let Some(signature) = signature {
+            if !signature.0.verify(aggregate_key, &signed_message) {
+                return Err(BlockResponseError::InvalidSignature);
+            }
+        }
+        Ok(kind)
+    }
 }
 
 impl SignerMessage {
@@ -1039,6 +1178,15 @@ impl std::fmt::Display for BlockResponse {
     }
 }
 
+/// Whether a verified `BlockResponse` accepted or rejected its block
+#[derive(Debug, Clone, PartialEq)]
+pub enum BlockResponseKind {
+    /// The signers signed the block
+    Accepted,
+    /// The signers rejected the block for the given reason
+    Rejected(RejectCode),
+}
+
 impl BlockResponse {
     /// Create a new accepted BlockResponse for the provided block signer signature hash and signature
     pub fn accepted(hash: Sha512Trunc256Sum, sig: Signature) -> Self {
@@ -1712,4 +1860,121 @@ mod test {
                 .expect("Failed to deserialize SignerMessage");
         assert_eq!(signer_message, deserialized_signer_message);
     }
+
+    /// Produce a single-key schnorr signature that verifies as a threshold signature
+    fn sign_with_key(private_key: Scalar, message: &[u8]) -> Signature {
+        let nonce = Scalar::random(&mut OsRng);
+        let public_key = Point::from(private_key);
+        let nonce_point = Point::from(nonce);
+        let c = wsts::compute::challenge(&public_key, &nonce_point, message);
+        Signature {
+            R: nonce_point,
+            z: nonce + c * private_key,
+        }
+    }
+
+    #[test]
+    fn verify_block_response() {
+        let private_key = Scalar::random(&mut OsRng);
+        let aggregate_key = Point::from(private_key);
+        let hash = Sha512Trunc256Sum([1u8; 32]);
+        let other_hash = Sha512Trunc256Sum([2u8; 32]);
+
+        let accepted: SignerMessage =
+            BlockResponse::accepted(hash, sign_with_key(private_key, hash.as_bytes())).into();
+        assert_eq!(
+            accepted.verify_block_response(&aggregate_key, &hash),
+            Ok(BlockResponseKind::Accepted)
+        );
+        assert_eq!(
+            accepted.verify_block_response(&aggregate_key, &other_hash),
+            Err(BlockResponseError::HashMismatch {
+                expected: other_hash,
+                actual: hash
+            })
+        );
+        let wrong_key = Point::from(Scalar::random(&mut OsRng));
+        assert_eq!(
+            accepted.verify_block_response(&wrong_key, &hash),
+            Err(BlockResponseError::InvalidSignature)
+        );
+
+        let vote = NakamotoBlockVote {
+            signer_signature_hash: hash,
+            rejected: true,
+        };
+        let signature = sign_with_key(private_key, &vote.serialize_to_vec());
+        let rejected: SignerMessage = BlockResponse::rejected(hash, signature.clone()).into();
+        assert_eq!(
+            rejected.verify_block_response(&aggregate_key, &hash),
+            Ok(BlockResponseKind::Rejected(RejectCode::SignedRejection(
+                ThresholdSignature(signature)
+            )))
+        );
+        // A signature over the block hash alone is an acceptance, not a rejection
+        let rejected: SignerMessage =
+            BlockResponse::rejected(hash, sign_with_key(private_key, hash.as_bytes())).into();
+        assert_eq!(
+            rejected.verify_block_response(&aggregate_key, &hash),
+            Err(BlockResponseError::InvalidSignature)
+        );
+
+        let code = RejectCode::ValidationFailed(ValidateRejectCode::InvalidBlock);
+        let unsigned: SignerMessage = BlockRejection::new(hash, code.clone()).into();
+        assert_eq!(
+            unsigned.verify_block_response(&aggregate_key, &hash),
+            Ok(BlockResponseKind::Rejected(code))
+        );
+
+        let transactions = SignerMessage::Transactions(vec![]);
+        assert_eq!(
+            transactions.verify_block_response(&aggregate_key, &hash),
+            Err(BlockResponseError::NotABlockResponse)
+        );
+    }
+
+    #[test]
+    fn validate_slot_for_each_message_type() {
+        let packets = one_of_each_message_type();
+        let mut messages: Vec<SignerMessage> = packets
+            .iter()
+            .map(|msg| {
+                SignerMessage::Packet(Packet {
+                    msg: msg.clone(),
+                    sig: vec![],
+                })
+            })
+            .collect();
+        messages.push(
+            BlockRejection::new(
+                Sha512Trunc256Sum([0u8; 32]),
+                RejectCode::ValidationFailed(ValidateRejectCode::InvalidBlock),
+            )
+            .into(),
+        );
+        messages.push(SignerMessage::Transactions(vec![]));
+        messages.push(SignerMessage::DkgResults {
+            aggregate_key: Point::from(Scalar::random(&mut OsRng)),
+            party_polynomials: vec![],
+        });
+        assert_eq!(messages.len(), MessageSlotID::ALL.len());
+
+        for (expected_slot, message) in MessageSlotID::ALL.iter().zip(messages.iter()) {
+            for slot in MessageSlotID::ALL {
+                assert_eq!(
+                    message.validate_slot(slot.to_u32()),
+                    slot == expected_slot,
+                    "{message:?} in {slot}"
+                );
+            }
+        }
+        for (expected_slot, msg) in MessageSlotID::ALL.iter().zip(packets.iter()) {
+            for slot in MessageSlotID::ALL {
+                assert_eq!(
+                    validate_slot_for_message(slot.to_u32(), msg),
+                    slot == expected_slot
+                );
+            }
+        }
+    }
 }
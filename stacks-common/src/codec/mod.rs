@@ -3,7 +3,7 @@ use std::{error, fmt, io, mem};
 
 // use crate::types::chainstate::MARFValue;
 use crate::types::chainstate::SortitionId;
-use crate::util::hash::HASH160_ENCODED_SIZE;
+use crate::util::hash::{to_hex, HASH160_ENCODED_SIZE};
 use crate::util::secp256k1::MESSAGE_SIGNATURE_ENCODED_SIZE;
 
 #[macro_use]
@@ -84,6 +84,15 @@ pub trait StacksMessageCodec {
             .expect("BUG: serialization to buffer failed.");
         bytes
     }
+    /// Convenience for serialization to a hex string.
+    fn consensus_serialize_to_hex(&self) -> Result<String, Error>
+    where
+        Self: Sized,
+    {
+        let mut bytes = vec![];
+        self.consensus_serialize(&mut bytes)?;
+        Ok(to_hex(&bytes))
+    }
 }
 
 // impl_byte_array_message_codec!(MARFValue, 40);
@@ -169,6 +178,9 @@ fn read_next_vec<T: StacksMessageCodec + Sized, R: Read>(
     Ok(ret)
 }
 
+/// Read a length-prefixed vector of at most `max_items` items.  Fails with a `DeserializeError`
+/// before allocating anything if the length prefix is larger than `max_items`, or if the vector
+/// could not fit in a `MAX_MESSAGE_LEN`-sized message.
 pub fn read_next_at_most<R: Read, T: StacksMessageCodec + Sized>(
     fd: &mut R,
     max_items: u32,
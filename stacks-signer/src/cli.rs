@@ -50,6 +50,8 @@ pub enum Command {
     GetLatestChunk(GetLatestChunkArgs),
     /// List chunks from the stacker-db instance
     ListChunks(StackerDBArgs),
+    /// Print the version and decoded signer message of each chunk in the stacker-db instance
+    DumpSlots(StackerDBArgs),
     /// Upload a chunk to the stacker-db instance
     PutChunk(PutChunkArgs),
     /// Run DKG and sign the message through the stacker-db instance
@@ -267,7 +269,8 @@ pub struct GenerateStackingSignatureArgs {
 
 /// Parse the contract ID
 fn parse_contract(contract: &str) -> Result<QualifiedContractIdentifier, String> {
-    QualifiedContractIdentifier::parse(contract).map_err(|e| format!("Invalid contract: {}", e))
+    QualifiedContractIdentifier::parse_checked(contract)
+        .map_err(|e| format!("Invalid contract: {}", e))
 }
 
 /// Parse a BTC address argument and return a `PoxAddress`.
@@ -516,6 +516,7 @@ pub(crate) mod tests {
             sign_timeout: config.sign_timeout,
             tx_fee_ustx: config.tx_fee_ustx,
             db_path: config.db_path.clone(),
+            chunk_versions_path: config.chunk_versions_path.clone(),
         }
     }
 
@@ -14,6 +14,12 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 //
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use blockstack_lib::chainstate::stacks::db::StacksChainState;
 use blockstack_lib::chainstate::stacks::StacksTransaction;
 use blockstack_lib::net::api::poststackerdbchunk::StackerDBErrorCodes;
 use hashbrown::HashMap;
@@ -29,6 +35,17 @@ use crate::client::retry_with_exponential_backoff;
 use crate::config::SignerConfig;
 use crate::signer::SignerSlotID;
 
+/// Chunk versions as persisted on disk: reward cycle -> message slot ID -> signer slot ID -> version
+type PersistedChunkVersions = BTreeMap<u64, BTreeMap<u8, BTreeMap<u32, u32>>>;
+
+/// Minimum time between two writes of the chunk versions file. Changes made in between are
+/// written together by the next save, or when the client is dropped.
+const CHUNK_VERSIONS_SAVE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A StackerDB slot as read by `dump_session_slots`: its slot ID, its version, and its contents
+/// if they decode to a `SignerMessage`
+pub type DumpedSlot = (u32, u32, Option<SignerMessage>);
+
 /// The StackerDB client for communicating with the .signers contract
 pub struct StackerDB {
     /// The stacker-db sessions for each signer set and message type.
@@ -44,6 +61,12 @@ pub struct StackerDB {
     reward_cycle: u64,
     /// The stacker-db transaction msg session for the NEXT reward cycle
     next_transaction_session: StackerDBSession,
+    /// The file in which `slot_versions` is persisted across restarts, if any
+    chunk_versions_path: Option<PathBuf>,
+    /// Whether `slot_versions` has changed since it was last written to `chunk_versions_path`
+    chunk_versions_dirty: bool,
+    /// When `slot_versions` was last written to `chunk_versions_path`
+    chunk_versions_saved_at: Option<Instant>,
 }
 
 impl From<&SignerConfig> for StackerDB {
@@ -55,6 +78,7 @@ impl From<&SignerConfig> for StackerDB {
             config.reward_cycle,
             config.signer_slot_id,
         )
+        .with_chunk_versions_path(config.chunk_versions_path.clone())
     }
 }
 impl StackerDB {
@@ -86,6 +110,90 @@ impl StackerDB {
             signer_slot_id,
             reward_cycle,
             next_transaction_session,
+            chunk_versions_path: None,
+            chunk_versions_dirty: false,
+            chunk_versions_saved_at: None,
+        }
+    }
+
+    /// Persist chunk versions to the given file, loading any versions already stored there for
+    /// this reward cycle. If `path` is `None`, chunk versions are only kept in memory.
+    pub fn with_chunk_versions_path(mut self, path: Option<PathBuf>) -> Self {
+        self.chunk_versions_path = path;
+        let Some(path) = &self.chunk_versions_path else {
+            return self;
+        };
+        let Some(versions) = read_chunk_versions(path).remove(&self.reward_cycle) else {
+            return self;
+        };
+        for (msg_id, slots) in versions {
+            let Some(msg_id) = MessageSlotID::from_u8(msg_id) else {
+                warn!("Ignoring persisted chunk versions for unknown message slot ID {msg_id}");
+                continue;
+            };
+            self.slot_versions.insert(
+                msg_id,
+                slots
+                    .into_iter()
+                    .map(|(slot_id, version)| (SignerSlotID(slot_id), version))
+                    .collect(),
+            );
+        }
+        self
+    }
+
+    /// Record that `slot_versions` has changed, and write it out unless it was already written
+    /// within the last `CHUNK_VERSIONS_SAVE_INTERVAL`.
+    fn chunk_versions_changed(&mut self) {
+        self.chunk_versions_dirty = true;
+        let save_due = self.chunk_versions_saved_at.map_or(true, |saved_at| {
+            saved_at.elapsed() >= CHUNK_VERSIONS_SAVE_INTERVAL
+        });
+        if save_due {
+            self.save_chunk_versions();
+        }
+    }
+
+    /// Write out any chunk versions that have changed since they were last written
+    pub fn flush_chunk_versions(&mut self) {
+        if self.chunk_versions_dirty {
+            self.save_chunk_versions();
+        }
+    }
+
+    /// Write `slot_versions` for this reward cycle to the chunk versions file, if there is one.
+    /// Entries for reward cycles before the previous one are dropped. The file is replaced
+    /// atomically, so a crash mid-write leaves the previous contents intact.
+    fn save_chunk_versions(&mut self) {
+        self.chunk_versions_dirty = false;
+        self.chunk_versions_saved_at = Some(Instant::now());
+        let Some(path) = &self.chunk_versions_path else {
+            return;
+        };
+        let mut persisted = read_chunk_versions(path);
+        persisted.retain(|reward_cycle, _| reward_cycle.saturating_add(1) >= self.reward_cycle);
+        let versions = self
+            .slot_versions
+            .iter()
+            .map(|(msg_id, slots)| {
+                let slots = slots
+                    .iter()
+                    .map(|(slot_id, version)| (slot_id.0, *version))
+                    .collect();
+                (msg_id.to_u8(), slots)
+            })
+            .collect();
+        persisted.insert(self.reward_cycle, versions);
+        let result = serde_json::to_vec(&persisted)
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| {
+                let path_str = path
+                    .to_str()
+                    .ok_or_else(|| "path is not valid UTF-8".to_string())?;
+                StacksChainState::atomic_file_write(path_str, &bytes).map_err(|e| e.to_string())
+            });
+        if let Err(e) = result {
+            warn!("Failed to persist stacker-db chunk versions to {path:?}: {e}");
         }
     }
 
@@ -146,6 +254,7 @@ impl StackerDB {
 
             if chunk_ack.accepted {
                 debug!("Chunk accepted by stackerdb: {chunk_ack:?}");
+                self.chunk_versions_changed();
                 return Ok(chunk_ack);
             } else {
                 warn!("Chunk rejected by stackerdb: {chunk_ack:?}");
@@ -245,6 +354,18 @@ impl StackerDB {
         Self::get_transactions(&mut self.next_transaction_session, signer_ids)
     }
 
+    /// Read every slot of each signer message contract this client is attached to, for debugging
+    pub fn dump_slots(&mut self) -> Result<Vec<(MessageSlotID, Vec<DumpedSlot>)>, ClientError> {
+        let mut dump = Vec::with_capacity(MessageSlotID::ALL.len());
+        for msg_id in MessageSlotID::ALL {
+            let Some(session) = self.signers_message_stackerdb_sessions.get_mut(msg_id) else {
+                return Err(ClientError::NotConnected);
+            };
+            dump.push((*msg_id, dump_session_slots(session)?));
+        }
+        Ok(dump)
+    }
+
     /// Retrieve the signer set this stackerdb client is attached to
     pub fn get_signer_set(&self) -> u32 {
         u32::try_from(self.reward_cycle % 2).expect("FATAL: reward cycle % 2 exceeds u32::MAX")
@@ -256,6 +377,49 @@ impl StackerDB {
     }
 }
 
+impl Drop for StackerDB {
+    fn drop(&mut self) {
+        self.flush_chunk_versions();
+    }
+}
+
+/// Read every written slot of the session's StackerDB contract at its current version, decoding
+/// each chunk into a `SignerMessage` where possible
+pub fn dump_session_slots(session: &mut StackerDBSession) -> Result<Vec<DumpedSlot>, ClientError> {
+    let list_request = || session.list_chunks().map_err(backoff::Error::transient);
+    let slots = retry_with_exponential_backoff(list_request)?;
+    let slots_and_versions: Vec<_> = slots
+        .iter()
+        .filter(|slot| slot.slot_version > 0)
+        .map(|slot| (slot.slot_id, slot.slot_version))
+        .collect();
+    let get_request = || {
+        session
+            .get_chunks(&slots_and_versions)
+            .map_err(backoff::Error::transient)
+    };
+    let chunks = retry_with_exponential_backoff(get_request)?;
+    Ok(slots_and_versions
+        .iter()
+        .zip(chunks)
+        .map(|((slot_id, slot_version), chunk)| {
+            let message = chunk.and_then(|data| read_next::<SignerMessage, _>(&mut &data[..]).ok());
+            (*slot_id, *slot_version, message)
+        })
+        .collect())
+}
+
+/// Read persisted chunk versions from `path`. A missing or unreadable file yields no versions.
+fn read_chunk_versions(path: &Path) -> PersistedChunkVersions {
+    let Ok(bytes) = fs::read(path) else {
+        return PersistedChunkVersions::new();
+    };
+    serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+        warn!("Failed to parse stacker-db chunk versions from {path:?}: {e:?}");
+        PersistedChunkVersions::new()
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use std::thread::spawn;
@@ -266,6 +430,8 @@ mod tests {
         TransactionSmartContract, TransactionVersion,
     };
     use blockstack_lib::util_lib::strings::StacksString;
+    use libstackerdb::SlotMetadata;
+    use stacks_common::util::hash::Sha512Trunc256Sum;
 
     use super::*;
     use crate::client::tests::{generate_signer_config, mock_server_from_config, write_response};
@@ -353,4 +519,152 @@ mod tests {
         write_response(mock_server, response_bytes.as_slice());
         assert_eq!(ack, h.join().unwrap().unwrap());
     }
+
+    #[test]
+    fn chunk_versions_should_persist_across_restarts() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-1.toml").unwrap();
+        let mut signer_config = generate_signer_config(&config, 5, 20);
+        let path = std::env::temp_dir().join(format!(
+            "stacks-signer-test-chunk-versions-{}.json",
+            rand::random::<u64>()
+        ));
+        signer_config.chunk_versions_path = Some(path.clone());
+        let slot_id = signer_config.signer_slot_id;
+        let mut stackerdb = StackerDB::from(&signer_config);
+        assert!(stackerdb.slot_versions.is_empty());
+
+        let ack = StackerDBChunkAckData {
+            accepted: true,
+            reason: None,
+            metadata: None,
+            code: None,
+        };
+        let mock_server = mock_server_from_config(&config);
+        let h = spawn(move || {
+            let ack = stackerdb.send_message_with_retry(SignerMessage::Transactions(vec![]));
+            (stackerdb, ack)
+        });
+        let mut response_bytes = b"HTTP/1.1 200 OK\n\n".to_vec();
+        let payload = serde_json::to_string(&ack).expect("Failed to serialize ack");
+        response_bytes.extend(payload.as_bytes());
+        std::thread::sleep(Duration::from_millis(500));
+        write_response(mock_server, response_bytes.as_slice());
+        let (stackerdb, result) = h.join().unwrap();
+        assert_eq!(ack, result.unwrap());
+
+        // the next write to the slot should use version 2
+        let versions = stackerdb
+            .slot_versions
+            .get(&MessageSlotID::Transactions)
+            .unwrap();
+        assert_eq!(versions.get(&slot_id), Some(&2));
+
+        // a restarted signer picks up where the last one left off...
+        let restarted = StackerDB::from(&signer_config);
+        assert_eq!(restarted.slot_versions, stackerdb.slot_versions);
+
+        // ...but only for the same reward cycle
+        signer_config.reward_cycle = signer_config.reward_cycle.wrapping_add(1);
+        let next_cycle = StackerDB::from(&signer_config);
+        assert!(next_cycle.slot_versions.is_empty());
+
+        // without a path, versions are only kept in memory
+        signer_config.chunk_versions_path = None;
+        signer_config.reward_cycle = signer_config.reward_cycle.wrapping_sub(1);
+        assert!(StackerDB::from(&signer_config).slot_versions.is_empty());
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn chunk_versions_should_be_written_in_batches() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-1.toml").unwrap();
+        let mut signer_config = generate_signer_config(&config, 5, 20);
+        let path = std::env::temp_dir().join(format!(
+            "stacks-signer-test-chunk-versions-batch-{}.json",
+            rand::random::<u64>()
+        ));
+        signer_config.chunk_versions_path = Some(path.clone());
+        let slot_id = signer_config.signer_slot_id;
+        let mut stackerdb = StackerDB::from(&signer_config);
+        let msg_id = MessageSlotID::Transactions;
+
+        // the first change is written right away
+        stackerdb
+            .slot_versions
+            .insert(msg_id, HashMap::from([(slot_id, 1)]));
+        stackerdb.chunk_versions_changed();
+        let restarted = StackerDB::from(&signer_config);
+        assert_eq!(restarted.slot_versions, stackerdb.slot_versions);
+        drop(restarted);
+
+        // a change soon after is held back...
+        stackerdb
+            .slot_versions
+            .insert(msg_id, HashMap::from([(slot_id, 2)]));
+        stackerdb.chunk_versions_changed();
+        let restarted = StackerDB::from(&signer_config);
+        assert_eq!(
+            restarted.slot_versions.get(&msg_id).unwrap().get(&slot_id),
+            Some(&1)
+        );
+        drop(restarted);
+
+        // ...until it is flushed
+        stackerdb.flush_chunk_versions();
+        let restarted = StackerDB::from(&signer_config);
+        assert_eq!(restarted.slot_versions, stackerdb.slot_versions);
+        drop(restarted);
+
+        // dropping the client flushes too
+        stackerdb
+            .slot_versions
+            .insert(msg_id, HashMap::from([(slot_id, 3)]));
+        stackerdb.chunk_versions_changed();
+        drop(stackerdb);
+        let restarted = StackerDB::from(&signer_config);
+        assert_eq!(
+            restarted.slot_versions.get(&msg_id).unwrap().get(&slot_id),
+            Some(&3)
+        );
+
+        // no temporary file is left behind
+        let mut tmp_path = path.clone().into_os_string();
+        tmp_path.push(".tmp");
+        assert!(!PathBuf::from(tmp_path).exists());
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn dump_session_slots_should_decode_messages() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let contract_id = MessageSlotID::Transactions.stacker_db_contract(false, 0);
+        let mut session = StackerDBSession::new(&config.node_host, contract_id);
+        let h = spawn(move || dump_session_slots(&mut session));
+
+        let slots = vec![
+            SlotMetadata::new_unsigned(0, 2, Sha512Trunc256Sum([0u8; 32])),
+            SlotMetadata::new_unsigned(1, 0, Sha512Trunc256Sum([0u8; 32])),
+            SlotMetadata::new_unsigned(2, 1, Sha512Trunc256Sum([0u8; 32])),
+        ];
+        let mut response_bytes = b"HTTP/1.1 200 OK\n\n".to_vec();
+        response_bytes.extend(serde_json::to_vec(&slots).unwrap());
+        let mock_server = mock_server_from_config(&config);
+        write_response(mock_server, response_bytes.as_slice());
+
+        let signer_message = SignerMessage::Transactions(vec![]);
+        let mut response_bytes = b"HTTP/1.1 200 OK\n\n".to_vec();
+        response_bytes.extend(signer_message.serialize_to_vec());
+        let mock_server = mock_server_from_config(&config);
+        write_response(mock_server, response_bytes.as_slice());
+
+        let mut response_bytes = b"HTTP/1.1 200 OK\n\n".to_vec();
+        response_bytes.extend([0xffu8; 4]);
+        let mock_server = mock_server_from_config(&config);
+        write_response(mock_server, response_bytes.as_slice());
+
+        let dump = h.join().unwrap().unwrap();
+        assert_eq!(dump, vec![(0, 2, Some(signer_message)), (2, 1, None)]);
+    }
 }
@@ -196,6 +196,59 @@ impl StacksClient {
         }
     }
 
+    /// Tally the votes cast by the given signers for the aggregate public key in a given
+    /// reward cycle and DKG round. Returns each candidate key with its total vote weight, from
+    /// heaviest to lightest. Signers that have not voted are skipped.
+    pub fn get_aggregate_public_key_tally(
+        &self,
+        reward_cycle: u64,
+        round: u64,
+        signers: &[StacksAddress],
+    ) -> Result<Vec<(Point, u64)>, ClientError> {
+        debug!("Getting the aggregate public key tally for round {round} of reward cycle {reward_cycle}...");
+        let function_name = ClarityName::from("get-vote");
+        let mut tally: Vec<(Point, u64)> = vec![];
+        for signer in signers {
+            let function_args = &[
+                ClarityValue::UInt(reward_cycle as u128),
+                ClarityValue::UInt(round as u128),
+                ClarityValue::Principal((*signer).into()),
+            ];
+            let value = self.read_only_contract_call(
+                &boot_code_addr(self.mainnet),
+                &ContractName::from(SIGNERS_VOTING_NAME),
+                &function_name,
+                function_args,
+            )?;
+            // Return value is of type:
+            // ```clarity
+            // (option { aggregate-public-key: (buff 33), signer-weight: uint })
+            // ```
+            let Some(inner_data) = value.expect_optional()? else {
+                continue;
+            };
+            let tuple = inner_data.expect_tuple()?;
+            let weight = u64::try_from(tuple.get("signer-weight")?.to_owned().expect_u128()?)
+                .map_err(|e| {
+                    ClientError::MalformedContractData(format!(
+                        "Failed to convert signer weight to u64: {e}"
+                    ))
+                })?;
+            let Some(point) =
+                self.parse_aggregate_public_key(tuple.get_owned("aggregate-public-key")?)?
+            else {
+                continue;
+            };
+            if let Some((_, total)) = tally.iter_mut().find(|(candidate, _)| *candidate == point) {
+                *total = total.saturating_add(weight);
+            } else {
+                tally.push((point, weight));
+            }
+        }
+        tally.sort_by(|(_, a), (_, b)| b.cmp(a));
+        Ok(tally)
+    }
+
     /// Determine the stacks node current epoch
     pub fn get_node_epoch(&self) -> Result<StacksEpochId, ClientError> {
         let pox_info = self.get_pox_data_with_retry()?;
@@ -320,6 +373,22 @@ impl StacksClient {
         Ok(account_entry.nonce)
     }
 
+    /// Retrieve the current account nonce and STX balance for the provided address
+    pub fn get_account(&self, address: &StacksAddress) -> Result<(u64, u128), ClientError> {
+        let account_entry = self.get_account_entry_with_retry(address)?;
+        let balance = account_entry
+            .balance
+            .strip_prefix("0x")
+            .and_then(|balance| u128::from_str_radix(balance, 16).ok())
+            .ok_or_else(|| {
+                ClientError::UnexpectedResponseFormat(format!(
+                    "Invalid account balance: {}",
+                    account_entry.balance
+                ))
+            })?;
+        Ok((account_entry.nonce, balance))
+    }
+
     /// Get the current peer info data from the stacks node
     pub fn get_peer_info_with_retry(&self) -> Result<RPCPeerInfoData, ClientError> {
         debug!("Getting stacks node info...");
@@ -671,6 +740,7 @@ mod tests {
         NakamotoSignerEntry, PoxStartCycleInfo, RewardSet,
     };
     use blockstack_lib::chainstate::stacks::ThresholdSignature;
+    use clarity::vm::types::TupleData;
     use rand::thread_rng;
     use rand_core::RngCore;
     use stacks_common::bitvec::BitVec;
@@ -685,8 +755,8 @@ mod tests {
         build_account_nonce_response, build_get_approved_aggregate_key_response,
         build_get_last_round_response, build_get_peer_info_response, build_get_pox_data_response,
         build_get_round_info_response, build_get_vote_for_aggregate_key_response,
-        build_get_weight_threshold_response, build_read_only_response, write_response,
-        MockServerClient,
+        build_get_weight_threshold_response, build_read_only_response, mock_server_from_config,
+        write_response, MockServerClient,
     };
 
     #[test]
@@ -1006,6 +1076,35 @@ mod tests {
         assert!(h.join().unwrap().is_err());
     }
 
+    #[test]
+    fn get_account_should_succeed() {
+        let mock = MockServerClient::new();
+        let address = mock.client.stacks_address;
+        let h = spawn(move || mock.client.get_account(&address));
+        write_response(
+            mock.server,
+            b"HTTP/1.1 200 OK\n\n{\"nonce\":7,\"balance\":\"0x0000000000000000000000003b9aca00\",\"locked\":\"0x00000000000000000000000000000000\",\"unlock_height\":0}"
+        );
+        let (nonce, balance) = h.join().unwrap().expect("Failed to deserialize response");
+        assert_eq!(nonce, 7);
+        assert_eq!(balance, 1_000_000_000);
+    }
+
+    #[test]
+    fn get_account_should_fail() {
+        let mock = MockServerClient::new();
+        let address = mock.client.stacks_address;
+        let h = spawn(move || mock.client.get_account(&address));
+        write_response(
+            mock.server,
+            b"HTTP/1.1 200 OK\n\n{\"nonce\":7,\"balance\":\"invalid balance\",\"locked\":\"0x00000000000000000000000000000000\",\"unlock_height\":0}"
+        );
+        assert!(matches!(
+            h.join().unwrap(),
+            Err(ClientError::UnexpectedResponseFormat(_))
+        ));
+    }
+
     #[test]
     fn parse_valid_signer_slots_should_succeed() {
         let mock = MockServerClient::new();
@@ -1236,6 +1335,45 @@ mod tests {
         assert_eq!(h.join().unwrap().unwrap(), None);
     }
 
+    #[test]
+    fn get_aggregate_public_key_tally_should_succeed() {
+        let mock = MockServerClient::new();
+        let config = mock.config.clone();
+        let point_1 = Point::from(Scalar::random(&mut rand::thread_rng()));
+        let point_2 = Point::from(Scalar::random(&mut rand::thread_rng()));
+        let vote_response = |point: Point, weight: u128| {
+            let value = ClarityValue::some(ClarityValue::Tuple(
+                TupleData::from_data(vec![
+                    (
+                        "aggregate-public-key".into(),
+                        ClarityValue::buff_from(point.compress().as_bytes().to_vec()).unwrap(),
+                    ),
+                    ("signer-weight".into(), ClarityValue::UInt(weight)),
+                ])
+                .unwrap(),
+            ))
+            .unwrap();
+            build_read_only_response(&value)
+        };
+        let signers: Vec<_> = (0..4)
+            .map(|_| {
+                let public_key = StacksPublicKey::from_private(&StacksPrivateKey::new());
+                StacksAddress::p2pkh(false, &public_key)
+            })
+            .collect();
+        let h = spawn(move || mock.client.get_aggregate_public_key_tally(0, 0, &signers));
+        write_response(mock.server, vote_response(point_1, 1).as_bytes());
+        let responses = [
+            vote_response(point_2, 3),
+            build_read_only_response(&ClarityValue::none()),
+            vote_response(point_1, 4),
+        ];
+        for response in responses {
+            write_response(mock_server_from_config(&config), response.as_bytes());
+        }
+        assert_eq!(h.join().unwrap().unwrap(), vec![(point_1, 5), (point_2, 3)]);
+    }
+
     #[test]
     fn get_round_vote_weight_should_succeed() {
         let mock = MockServerClient::new();
@@ -148,6 +148,8 @@ pub struct SignerConfig {
     pub tx_fee_ustx: u64,
     /// The path to the signer's database file
     pub db_path: PathBuf,
+    /// The path to the file in which stacker-db chunk versions are persisted, if any
+    pub chunk_versions_path: Option<PathBuf>,
 }
 
 /// The parsed configuration for the signer
@@ -183,6 +185,10 @@ pub struct GlobalConfig {
     pub auth_password: String,
     /// The path to the signer's database file
     pub db_path: PathBuf,
+    /// The path to the file in which stacker-db chunk versions are persisted, if any
+    pub chunk_versions_path: Option<PathBuf>,
+    /// The address on which to serve Prometheus metrics, if any
+    pub metrics_endpoint: Option<SocketAddr>,
 }
 
 /// Internal struct for loading up the config file
@@ -215,6 +221,12 @@ struct RawConfigFile {
     pub auth_password: String,
     /// The path to the signer's database file or :memory: for an in-memory database
     pub db_path: String,
+    /// The path to a file in which to persist the last written stacker-db chunk versions, so
+    /// they survive a restart. If unset, chunk versions are only kept in memory.
+    pub chunk_versions_path: Option<String>,
+    /// The address on which to serve Prometheus metrics. Only used if the signer is built with
+    /// the `monitoring_prom` feature.
+    pub metrics_endpoint: Option<String>,
 }
 
 impl RawConfigFile {
@@ -291,8 +303,21 @@ impl TryFrom<RawConfigFile> for GlobalConfig {
         let nonce_timeout = raw_data.nonce_timeout_ms.map(Duration::from_millis);
         let sign_timeout = raw_data.sign_timeout_ms.map(Duration::from_millis);
         let db_path = raw_data.db_path.into();
-
-        Ok(Self {
+        let chunk_versions_path = raw_data.chunk_versions_path.map(PathBuf::from);
+        let metrics_endpoint = raw_data
+            .metrics_endpoint
+            .map(|metrics_endpoint| {
+                metrics_endpoint
+                    .to_socket_addrs()
+                    .ok()
+                    .and_then(|mut addrs| addrs.next())
+                    .ok_or_else(|| {
+                        ConfigError::BadField("metrics_endpoint".to_string(), metrics_endpoint)
+                    })
+            })
+            .transpose()?;
+
+        let config = Self {
             node_host: raw_data.node_host,
             endpoint,
             stacks_private_key,
@@ -308,7 +333,11 @@ impl TryFrom<RawConfigFile> for GlobalConfig {
             tx_fee_ustx: raw_data.tx_fee_ustx.unwrap_or(TX_FEE_USTX),
             auth_password: raw_data.auth_password,
             db_path,
-        })
+            chunk_versions_path,
+            metrics_endpoint,
+        };
+        config.validate()?;
+        Ok(config)
     }
 }
 
@@ -331,6 +360,36 @@ impl GlobalConfig {
         Self::try_from(&PathBuf::from(path))
     }
 
+    /// Check the config for values that parse but cannot work together
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.endpoint.port() == 0 {
+            return Err(ConfigError::BadField(
+                "endpoint".to_string(),
+                self.endpoint.to_string(),
+            ));
+        }
+        let timeouts = [
+            ("event_timeout_ms", Some(self.event_timeout)),
+            ("dkg_public_timeout_ms", self.dkg_public_timeout),
+            ("dkg_private_timeout_ms", self.dkg_private_timeout),
+            ("dkg_end_timeout_ms", self.dkg_end_timeout),
+            ("nonce_timeout_ms", self.nonce_timeout),
+            ("sign_timeout_ms", self.sign_timeout),
+        ];
+        for (field, timeout) in timeouts {
+            if timeout == Some(Duration::ZERO) {
+                return Err(ConfigError::BadField(field.to_string(), "0".to_string()));
+            }
+        }
+        if self.chunk_versions_path.as_ref() == Some(&self.db_path) {
+            return Err(ConfigError::InvalidConfig(format!(
+                "chunk_versions_path must not be the same file as db_path ({})",
+                self.db_path.display()
+            )));
+        }
+        Ok(())
+    }
+
     /// Return a string with non-sensitive configuration
     /// information for logging purposes
     pub fn config_to_log_string(&self) -> String {
@@ -466,4 +525,54 @@ DKG transaction fee: 0.01 uSTX
             )
         );
     }
+
+    #[test]
+    fn validate_should_reject_inconsistent_configs() {
+        let base = fs::read_to_string("./src/tests/conf/signer-0.toml").unwrap();
+        assert!(GlobalConfig::load_from_str(&base).is_ok());
+
+        let zero_timeout = format!("{base}\nsign_timeout_ms = 0\n");
+        assert!(matches!(
+            GlobalConfig::load_from_str(&zero_timeout),
+            Err(ConfigError::BadField(field, _)) if field == "sign_timeout_ms"
+        ));
+
+        let zero_port = base.replace("localhost:30000", "localhost:0");
+        assert!(matches!(
+            GlobalConfig::load_from_str(&zero_port),
+            Err(ConfigError::BadField(field, _)) if field == "endpoint"
+        ));
+
+        let shared_path = base.replace(
+            "db_path = \":memory:\"",
+            "db_path = \"/tmp/signer.sqlite\"\nchunk_versions_path = \"/tmp/signer.sqlite\"",
+        );
+        assert!(matches!(
+            GlobalConfig::load_from_str(&shared_path),
+            Err(ConfigError::InvalidConfig(_))
+        ));
+
+        let bad_metrics_endpoint = format!("{base}\nmetrics_endpoint = \"not an address\"\n");
+        assert!(matches!(
+            GlobalConfig::load_from_str(&bad_metrics_endpoint),
+            Err(ConfigError::BadField(field, _)) if field == "metrics_endpoint"
+        ));
+    }
+
+    #[test]
+    fn should_load_metrics_endpoint() {
+        let base = fs::read_to_string("./src/tests/conf/signer-0.toml").unwrap();
+        assert_eq!(
+            GlobalConfig::load_from_str(&base).unwrap().metrics_endpoint,
+            None
+        );
+
+        let with_metrics = format!("{base}\nmetrics_endpoint = \"127.0.0.1:9154\"\n");
+        assert_eq!(
+            GlobalConfig::load_from_str(&with_metrics)
+                .unwrap()
+                .metrics_endpoint,
+            Some("127.0.0.1:9154".parse().unwrap())
+        );
+    }
 }
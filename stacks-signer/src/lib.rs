@@ -28,6 +28,7 @@ pub mod client;
 pub mod config;
 /// The coordinator selector for the signer
 pub mod coordinator;
+pub mod monitoring;
 /// The primary runloop for the signer
 pub mod runloop;
 /// The signer module for processing events
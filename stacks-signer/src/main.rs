@@ -30,6 +30,7 @@ use std::fs::File;
 use std::io::{self, BufRead, Write};
 use std::path::{Path, PathBuf};
 use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread;
 use std::time::Duration;
 
 use blockstack_lib::chainstate::nakamoto::NakamotoBlock;
@@ -48,7 +49,9 @@ use stacks_signer::cli::{
     Cli, Command, GenerateFilesArgs, GenerateStackingSignatureArgs, GetChunkArgs,
     GetLatestChunkArgs, PutChunkArgs, RunDkgArgs, RunSignerArgs, SignArgs, StackerDBArgs,
 };
+use stacks_signer::client::dump_session_slots;
 use stacks_signer::config::{build_signer_config_tomls, GlobalConfig};
+use stacks_signer::monitoring::start_serving_monitoring_metrics;
 use stacks_signer::runloop::{RunLoop, RunLoopCommand};
 use stacks_signer::signer::Command as SignerCommand;
 use tracing_subscriber::prelude::*;
@@ -86,6 +89,12 @@ fn spawn_running_signer(path: &PathBuf) -> SpawnedSigner {
     let config = GlobalConfig::try_from(path).unwrap();
     let endpoint = config.endpoint;
     info!("Starting signer with config: {}", config);
+    if let Some(metrics_endpoint) = config.metrics_endpoint {
+        thread::Builder::new()
+            .name("prometheus".to_string())
+            .spawn(move || start_serving_monitoring_metrics(metrics_endpoint))
+            .expect("FATAL: failed to start monitoring thread");
+    }
     let (cmd_send, cmd_recv) = channel();
     let (res_send, res_recv) = channel();
     let ev = SignerEventReceiver::new(config.network.is_mainnet());
@@ -179,6 +188,18 @@ fn handle_list_chunks(args: StackerDBArgs) {
     println!("{}", serde_json::to_string(&chunk_list).unwrap());
 }
 
+fn handle_dump_slots(args: StackerDBArgs) {
+    debug!("Dumping slots...");
+    let mut session = stackerdb_session(&args.host, args.contract);
+    let slots = dump_session_slots(&mut session).unwrap();
+    for (slot_id, slot_version, message) in slots {
+        match message {
+            Some(message) => println!("{slot_id} (version {slot_version}): {message:?}"),
+            None => println!("{slot_id} (version {slot_version}): <not a signer message>"),
+        }
+    }
+}
+
 fn handle_put_chunk(args: PutChunkArgs) {
     debug!("Putting chunk...");
     let mut session = stackerdb_session(&args.db_args.host, args.db_args.contract);
@@ -382,6 +403,9 @@ fn main() {
         Command::ListChunks(args) => {
             handle_list_chunks(args);
         }
+        Command::DumpSlots(args) => {
+            handle_dump_slots(args);
+        }
         Command::PutChunk(args) => {
             handle_put_chunk(args);
         }
@@ -0,0 +1,109 @@
+// Copyright (C) 2013-2020 Blockstack PBC, a public benefit corporation
+// Copyright (C) 2020-2024 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Signer metrics. These are only collected when built with the `monitoring_prom` feature, in
+//! which case they are registered with the default prometheus registry and served at the
+//! configured `metrics_endpoint`.
+
+use std::net::SocketAddr;
+
+#[cfg(not(feature = "monitoring_prom"))]
+use slog::slog_warn;
+#[cfg(not(feature = "monitoring_prom"))]
+use stacks_common::warn;
+use wsts::net::Message;
+
+#[cfg(feature = "monitoring_prom")]
+mod prometheus;
+
+/// Errors from the metrics server
+#[derive(Debug)]
+pub enum MonitoringError {
+    /// The bind address is already in use, or otherwise couldn't be bound
+    AlreadyBound,
+    /// The HTTP response header couldn't be built
+    InvalidHeader,
+}
+
+/// Serve the signer's metrics at `bind_address`.  Blocks for as long as the server runs, so
+/// call it from its own thread.
+#[cfg(feature = "monitoring_prom")]
+pub fn start_serving_monitoring_metrics(bind_address: SocketAddr) -> Result<(), MonitoringError> {
+    prometheus::start_serving_prometheus_metrics(bind_address)
+}
+
+/// Serve the signer's metrics at `bind_address`.  Without the `monitoring_prom` feature there
+/// are no metrics to serve, so this only logs a warning.
+#[cfg(not(feature = "monitoring_prom"))]
+pub fn start_serving_monitoring_metrics(bind_address: SocketAddr) -> Result<(), MonitoringError> {
+    warn!("Attempted to start monitoring service at bind_address = {bind_address}, but stacks-signer was built without `monitoring_prom` feature.");
+    Ok(())
+}
+
+/// Count a wsts message that passed signature verification, by message type
+#[allow(unused_variables)]
+pub fn increment_messages_processed(msg: &Message) {
+    #[cfg(feature = "monitoring_prom")]
+    prometheus::SIGNER_MESSAGES_PROCESSED
+        .with_label_values(&[message_type_label(msg)])
+        .inc();
+}
+
+/// The `type` label used for a wsts message in the signer's metrics
+pub fn message_type_label(msg: &Message) -> &'static str {
+    match msg {
+        Message::DkgBegin(_) => "DkgBegin",
+        Message::DkgPrivateBegin(_) => "DkgPrivateBegin",
+        Message::DkgEndBegin(_) => "DkgEndBegin",
+        Message::DkgEnd(_) => "DkgEnd",
+        Message::DkgPublicShares(_) => "DkgPublicShares",
+        Message::DkgPrivateShares(_) => "DkgPrivateShares",
+        Message::NonceRequest(_) => "NonceRequest",
+        Message::NonceResponse(_) => "NonceResponse",
+        Message::SignatureShareRequest(_) => "SignatureShareRequest",
+        Message::SignatureShareResponse(_) => "SignatureShareResponse",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use libsigner::one_of_each_message_type;
+
+    use super::*;
+
+    #[test]
+    fn message_type_label_names_every_message_type() {
+        let labels: Vec<_> = one_of_each_message_type()
+            .iter()
+            .map(message_type_label)
+            .collect();
+        assert_eq!(
+            labels,
+            [
+                "DkgBegin",
+                "DkgPrivateBegin",
+                "DkgEndBegin",
+                "DkgEnd",
+                "DkgPublicShares",
+                "DkgPrivateShares",
+                "NonceRequest",
+                "NonceResponse",
+                "SignatureShareRequest",
+                "SignatureShareResponse",
+            ]
+        );
+    }
+}
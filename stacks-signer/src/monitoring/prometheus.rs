@@ -0,0 +1,66 @@
+// Copyright (C) 2013-2020 Blockstack PBC, a public benefit corporation
+// Copyright (C) 2020-2024 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::net::SocketAddr;
+
+use lazy_static::lazy_static;
+use prometheus::{gather, opts, register_int_counter_vec, Encoder, IntCounterVec, TextEncoder};
+use slog::{slog_info, slog_warn};
+use stacks_common::{info, warn};
+use tiny_http::{Header as HttpHeader, Response as HttpResponse, Server as HttpServer};
+
+use super::MonitoringError;
+
+lazy_static! {
+    pub static ref SIGNER_MESSAGES_PROCESSED: IntCounterVec = register_int_counter_vec!(
+        opts!(
+            "stacks_signer_messages_processed",
+            "Total number of verified wsts messages processed by the signer, by message type"
+        ),
+        &["type"]
+    )
+    .unwrap();
+}
+
+/// Serve the default registry's metrics over HTTP at `bind_address`.  Every request gets the
+/// metrics, regardless of its method or path.  Only returns if the server can't be started.
+pub fn start_serving_prometheus_metrics(bind_address: SocketAddr) -> Result<(), MonitoringError> {
+    let server = HttpServer::http(bind_address).map_err(|e| {
+        warn!("Prometheus monitoring: unable to bind {bind_address}, will not spawn prometheus endpoint service: {e:?}");
+        MonitoringError::AlreadyBound
+    })?;
+    info!("Prometheus monitoring: server listening on http://{bind_address}");
+
+    let content_type = HttpHeader::from_bytes(
+        &b"Content-Type"[..],
+        TextEncoder::new().format_type().as_bytes(),
+    )
+    .map_err(|_| MonitoringError::InvalidHeader)?;
+
+    for request in server.incoming_requests() {
+        let encoder = TextEncoder::new();
+        let mut buffer = vec![];
+        if let Err(e) = encoder.encode(&gather(), &mut buffer) {
+            warn!("Prometheus monitoring: failed to encode metrics: {e:?}");
+            continue;
+        }
+        let response = HttpResponse::from_data(buffer).with_header(content_type.clone());
+        if let Err(e) = request.respond(response) {
+            warn!("Prometheus monitoring: failed to send metrics: {e:?}");
+        }
+    }
+    Ok(())
+}
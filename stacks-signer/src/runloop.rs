@@ -14,7 +14,9 @@ use std::collections::VecDeque;
 //
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::Sender;
+use std::sync::Arc;
 use std::time::Duration;
 
 use blockstack_lib::burnchains::PoxConstants;
@@ -51,6 +53,23 @@ pub enum State {
     RegisteredSigners,
 }
 
+/// A shareable flag that tells the runloop to stop. Once set, the next pass of the runloop
+/// resets every signer to Idle and terminates the loop.
+#[derive(Clone, Debug, Default)]
+pub struct AbortSignal(Arc<AtomicBool>);
+
+impl AbortSignal {
+    /// Ask the runloop to stop
+    pub fn abort(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    /// Has the runloop been asked to stop?
+    pub fn is_aborted(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
 /// The current reward cycle info
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
 pub struct RewardCycleInfo {
@@ -103,6 +122,8 @@ pub struct RunLoop {
     pub commands: VecDeque<RunLoopCommand>,
     /// The current reward cycle info. Only None if the runloop is uninitialized
     pub current_reward_cycle_info: Option<RewardCycleInfo>,
+    /// Set to stop the runloop
+    pub abort_signal: AbortSignal,
 }
 
 impl From<GlobalConfig> for RunLoop {
@@ -116,6 +137,7 @@ impl From<GlobalConfig> for RunLoop {
             state: State::Uninitialized,
             commands: VecDeque::new(),
             current_reward_cycle_info: None,
+            abort_signal: AbortSignal::default(),
         }
     }
 }
@@ -214,6 +236,7 @@ impl RunLoop {
             sign_timeout: self.config.sign_timeout,
             tx_fee_ustx: self.config.tx_fee_ustx,
             db_path: self.config.db_path.clone(),
+            chunk_versions_path: self.config.chunk_versions_path.clone(),
         })
     }
 
@@ -341,6 +364,14 @@ impl SignerRunLoop<Vec<OperationResult>, RunLoopCommand> for RunLoop {
             "Running one pass for the signer. state={:?}, cmd={cmd:?}, event={event:?}",
             self.state
         );
+        if self.abort_signal.is_aborted() {
+            info!("Signer runloop aborted. Resetting signers and stopping.");
+            for signer in self.stacks_signers.values_mut() {
+                signer.abort_operation();
+            }
+            // An empty result set tells the main loop to stop
+            return Some(vec![]);
+        }
         if let Some(cmd) = cmd {
             self.commands.push_back(cmd);
         }
@@ -407,6 +438,8 @@ impl SignerRunLoop<Vec<OperationResult>, RunLoopCommand> for RunLoop {
             ) {
                 error!("{signer}: errored processing event: {e}");
             }
+            // Do not let a stalled coordinator wedge the signer forever
+            signer.check_operation_timeout(self.config.event_timeout);
             if let Some(command) = self.commands.pop_front() {
                 let reward_cycle = command.reward_cycle;
                 if signer.reward_cycle != reward_cycle {
@@ -433,9 +466,19 @@ impl SignerRunLoop<Vec<OperationResult>, RunLoopCommand> for RunLoop {
 }
 #[cfg(test)]
 mod tests {
+    use std::sync::mpsc::channel;
+    use std::thread::sleep;
+    use std::time::{Duration, Instant};
+
     use blockstack_lib::chainstate::stacks::boot::NakamotoSignerEntry;
-    use libsigner::SignerEntries;
+    use libsigner::{SignerEntries, SignerRunLoop};
     use stacks_common::types::chainstate::{StacksPrivateKey, StacksPublicKey};
+    use wsts::curve::point::Point;
+
+    use super::{RewardCycleInfo, RunLoop, State};
+    use crate::client::tests::generate_signer_config;
+    use crate::config::GlobalConfig;
+    use crate::signer::{Signer, State as SignerState};
 
     #[test]
     fn parse_nakamoto_signer_entries_test() {
@@ -462,4 +505,76 @@ mod tests {
             (0..nmb_signers).map(|id| id as u32).collect::<Vec<_>>()
         );
     }
+
+    #[test]
+    fn stalled_operation_times_out_to_idle() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let signer_config = generate_signer_config(&config, 5, 20);
+        let reward_cycle = signer_config.reward_cycle;
+        let mut signer = Signer::from(signer_config);
+        // Avoid querying the stacks node for DKG info
+        signer.approved_aggregate_public_key = Some(Point::new());
+        signer.state = SignerState::OperationInProgress;
+        signer.operation_start = Some(Instant::now());
+
+        let mut runloop = RunLoop::from(config);
+        runloop.set_event_timeout(Duration::from_millis(100));
+        runloop.state = State::RegisteredSigners;
+        runloop.current_reward_cycle_info = Some(RewardCycleInfo {
+            reward_cycle,
+            reward_phase_block_length: 10,
+            prepare_phase_block_length: 5,
+            first_burnchain_block_height: 0,
+            last_burnchain_block_height: 0,
+        });
+        runloop.stacks_signers.insert(reward_cycle % 2, signer);
+
+        let (res_send, _res_recv) = channel();
+        // Not enough time has passed yet. Should still be waiting on the operation.
+        runloop.run_one_pass(None, None, res_send.clone());
+        let signer = runloop.stacks_signers.get(&(reward_cycle % 2)).unwrap();
+        assert_eq!(signer.state, SignerState::OperationInProgress);
+
+        sleep(Duration::from_millis(200));
+        runloop.run_one_pass(None, None, res_send);
+        let signer = runloop.stacks_signers.get(&(reward_cycle % 2)).unwrap();
+        assert_eq!(signer.state, SignerState::Idle);
+        assert!(signer.operation_start.is_none());
+    }
+
+    #[test]
+    fn abort_signal_stops_runloop() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let signer_config = generate_signer_config(&config, 5, 20);
+        let reward_cycle = signer_config.reward_cycle;
+        let mut signer = Signer::from(signer_config);
+        // Avoid querying the stacks node for DKG info
+        signer.approved_aggregate_public_key = Some(Point::new());
+        signer.state = SignerState::OperationInProgress;
+        signer.operation_start = Some(Instant::now());
+
+        let mut runloop = RunLoop::from(config);
+        runloop.state = State::RegisteredSigners;
+        runloop.current_reward_cycle_info = Some(RewardCycleInfo {
+            reward_cycle,
+            reward_phase_block_length: 10,
+            prepare_phase_block_length: 5,
+            first_burnchain_block_height: 0,
+            last_burnchain_block_height: 0,
+        });
+        runloop.stacks_signers.insert(reward_cycle % 2, signer);
+        let abort_signal = runloop.abort_signal.clone();
+
+        let (res_send, _res_recv) = channel();
+        assert!(runloop.run_one_pass(None, None, res_send.clone()).is_none());
+        let signer = runloop.stacks_signers.get(&(reward_cycle % 2)).unwrap();
+        assert_eq!(signer.state, SignerState::OperationInProgress);
+
+        abort_signal.abort();
+        let results = runloop.run_one_pass(None, None, res_send);
+        assert!(matches!(results, Some(results) if results.is_empty()));
+        let signer = runloop.stacks_signers.get(&(reward_cycle % 2)).unwrap();
+        assert_eq!(signer.state, SignerState::Idle);
+        assert!(signer.operation_start.is_none());
+    }
 }
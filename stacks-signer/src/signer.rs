@@ -16,7 +16,7 @@
 use std::collections::VecDeque;
 use std::path::PathBuf;
 use std::sync::mpsc::Sender;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use blockstack_lib::chainstate::burn::ConsensusHashExtensions;
 use blockstack_lib::chainstate::nakamoto::signer_set::NakamotoSigners;
@@ -52,6 +52,7 @@ use wsts::v2;
 use crate::client::{retry_with_exponential_backoff, ClientError, StackerDB, StacksClient};
 use crate::config::SignerConfig;
 use crate::coordinator::CoordinatorSelector;
+use crate::monitoring;
 use crate::signerdb::SignerDb;
 
 /// The signer StackerDB slot ID, purposefully wrapped to prevent conflation with SignerID
@@ -171,6 +172,8 @@ pub struct Signer {
     pub db_path: PathBuf,
     /// SignerDB for state management
     pub signer_db: SignerDb,
+    /// The time at which the in-progress DKG or Sign operation started
+    pub operation_start: Option<Instant>,
 }
 
 impl std::fmt::Display for Signer {
@@ -300,6 +303,7 @@ impl From<SignerConfig> for Signer {
             miner_key: None,
             db_path: signer_config.db_path,
             signer_db,
+            operation_start: None,
         }
     }
 }
@@ -322,6 +326,7 @@ impl Signer {
             );
             self.coordinator.state = CoordinatorState::Idle;
             self.state = State::Idle;
+            self.operation_start = None;
         }
     }
 
@@ -329,14 +334,51 @@ impl Signer {
     fn finish_operation(&mut self) {
         self.state = State::Idle;
         self.coordinator_selector.last_message_time = None;
+        self.operation_start = None;
     }
 
     /// Update operation
     fn update_operation(&mut self) {
+        if self.state == State::Idle {
+            self.operation_start = Some(Instant::now());
+        }
         self.state = State::OperationInProgress;
         self.coordinator_selector.last_message_time = Some(Instant::now());
     }
 
+    /// Reset the coordinator and return to Idle if the in-progress operation has not
+    ///  produced any results within the given timeout. Returns true if the operation timed out.
+    pub fn check_operation_timeout(&mut self, timeout: Duration) -> bool {
+        if self.state != State::OperationInProgress {
+            return false;
+        }
+        let Some(operation_start) = self.operation_start else {
+            return false;
+        };
+        if operation_start.elapsed() < timeout {
+            return false;
+        }
+        error!(
+            "{self}: Operation timed out after {timeout:?} without any results. Resetting to Idle.";
+            "coordinator_state" => ?self.coordinator.state,
+        );
+        self.coordinator.reset();
+        self.finish_operation();
+        true
+    }
+
+    /// Abandon any in-progress operation: reset the coordinator and return to Idle
+    pub fn abort_operation(&mut self) {
+        if self.state == State::OperationInProgress {
+            info!(
+                "{self}: Aborting in-progress operation.";
+                "coordinator_state" => ?self.coordinator.state,
+            );
+        }
+        self.coordinator.reset();
+        self.finish_operation();
+    }
+
     /// Execute the given command and update state accordingly
     fn execute_command(&mut self, stacks_client: &StacksClient, command: &Command) {
         match command {
@@ -925,6 +967,7 @@ impl Signer {
     ) -> Option<Packet> {
         // We only care about verified wsts packets. Ignore anything else.
         if packet.verify(&self.state_machine.public_keys, coordinator_public_key) {
+            monitoring::increment_messages_processed(&packet.msg);
             match &mut packet.msg {
                 Message::SignatureShareRequest(request) => {
                     if !self.validate_signature_share_request(request) {
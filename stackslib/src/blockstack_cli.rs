@@ -155,11 +155,14 @@ The decode-header command decodes a serialized Stacks header and prints it to st
 The header, if given, must be a hex string.  Alternatively, you may pass `-` instead, and the
 raw binary header will be read from stdin.";
 
-const DECODE_BLOCK_USAGE: &str = "blockstack-cli (options) decode-block [block-path-or-stdin]
+const DECODE_BLOCK_USAGE: &str =
+    "blockstack-cli (options) decode-block [--txids-only] [block-path-or-stdin]
 
 The decode-block command decodes a serialized Stacks block and prints it to stdout as JSON.
 The block, if given, must be a hex string.  Alternatively, you may pass `-` instead, and the
-raw binary block will be read from stdin.";
+raw binary block will be read from stdin.
+
+If --txids-only is given, only the txids of the block's transactions are printed, one per line.";
 
 const DECODE_MICROBLOCK_USAGE: &str = "blockstack-cli (options) decode-microblock [microblock-path-or-stdin]
 
@@ -407,18 +410,15 @@ fn handle_contract_publish(
     );
     unsigned_tx.anchor_mode = anchor_mode;
 
-    let mut unsigned_tx_bytes = vec![];
-    unsigned_tx
-        .consensus_serialize(&mut unsigned_tx_bytes)
+    let unsigned_tx_hex = unsigned_tx
+        .consensus_serialize_to_hex()
         .expect("FATAL: invalid transaction");
-    let signed_tx =
-        sign_transaction_single_sig_standard(&to_hex(&unsigned_tx_bytes), &sk_publisher)?;
+    let signed_tx = sign_transaction_single_sig_standard(&unsigned_tx_hex, &sk_publisher)?;
 
-    let mut signed_tx_bytes = vec![];
-    signed_tx
-        .consensus_serialize(&mut signed_tx_bytes)
+    let signed_tx_hex = signed_tx
+        .consensus_serialize_to_hex()
         .expect("FATAL: invalid signed transaction");
-    Ok(to_hex(&signed_tx_bytes))
+    Ok(signed_tx_hex)
 }
 
 fn handle_contract_call(
@@ -494,17 +494,15 @@ fn handle_contract_call(
     );
     unsigned_tx.anchor_mode = anchor_mode;
 
-    let mut unsigned_tx_bytes = vec![];
-    unsigned_tx
-        .consensus_serialize(&mut unsigned_tx_bytes)
+    let unsigned_tx_hex = unsigned_tx
+        .consensus_serialize_to_hex()
         .expect("FATAL: invalid transaction");
-    let signed_tx = sign_transaction_single_sig_standard(&to_hex(&unsigned_tx_bytes), &sk_origin)?;
+    let signed_tx = sign_transaction_single_sig_standard(&unsigned_tx_hex, &sk_origin)?;
 
-    let mut signed_tx_bytes = vec![];
-    signed_tx
-        .consensus_serialize(&mut signed_tx_bytes)
+    let signed_tx_hex = signed_tx
+        .consensus_serialize_to_hex()
         .expect("FATAL: invalid signed transaction");
-    Ok(to_hex(&signed_tx_bytes))
+    Ok(signed_tx_hex)
 }
 
 fn handle_token_transfer(
@@ -556,17 +554,15 @@ fn handle_token_transfer(
     );
     unsigned_tx.anchor_mode = anchor_mode;
 
-    let mut unsigned_tx_bytes = vec![];
-    unsigned_tx
-        .consensus_serialize(&mut unsigned_tx_bytes)
+    let unsigned_tx_hex = unsigned_tx
+        .consensus_serialize_to_hex()
         .expect("FATAL: invalid transaction");
-    let signed_tx = sign_transaction_single_sig_standard(&to_hex(&unsigned_tx_bytes), &sk_origin)?;
+    let signed_tx = sign_transaction_single_sig_standard(&unsigned_tx_hex, &sk_origin)?;
 
-    let mut signed_tx_bytes = vec![];
-    signed_tx
-        .consensus_serialize(&mut signed_tx_bytes)
+    let signed_tx_hex = signed_tx
+        .consensus_serialize_to_hex()
         .expect("FATAL: invalid signed transaction");
-    Ok(to_hex(&signed_tx_bytes))
+    Ok(signed_tx_hex)
 }
 
 fn generate_secret_key(args: &[String], version: TransactionVersion) -> Result<String, CliError> {
@@ -717,6 +713,13 @@ fn decode_header(args: &[String], _version: TransactionVersion) -> Result<String
 }
 
 fn decode_block(args: &[String], _version: TransactionVersion) -> Result<String, CliError> {
+    let mut args = args.to_vec();
+    let txids_only = if let Some(ix) = args.iter().position(|x| x == "--txids-only") {
+        args.remove(ix);
+        true
+    } else {
+        false
+    };
     if (args.len() >= 1 && args[0] == "-h") || args.len() != 1 {
         return Err(CliError::Message(format!(
             "Usage: {}\n",
@@ -739,6 +742,12 @@ fn decode_block(args: &[String], _version: TransactionVersion) -> Result<String,
     let mut debug_cursor = LogReader::from_reader(&mut cursor);
 
     match StacksBlock::consensus_deserialize(&mut debug_cursor) {
+        Ok(block) if txids_only => Ok(block
+            .txs
+            .iter()
+            .map(|tx| tx.txid().to_string())
+            .collect::<Vec<_>>()
+            .join("\n")),
         Ok(block) => Ok(serde_json::to_string(&block).expect("Failed to serialize block to JSON")),
         Err(e) => {
             let mut ret = String::new();
@@ -1210,6 +1219,24 @@ mod test {
         eprintln!("result:\n{}", result);
     }
 
+    #[test]
+    fn decode_block_txids_only() {
+        let block_hex = "000000000000395f800000000000000179cb51f6bbd6d90cb257616e77a495919667c3772dd08ea7c4f5c372739490bc91da6609c5c95c96f612dbc8cab2f7a0d8bfb83abdb630167579ccc36b66c03c1d0d250cd3b3615c03afcdaef313dbd30d3d5b0fd10ed5acbc35d042abfba66cdfc32881c5a665ad9685a2eb6e0c131fb400000000000000000000000000000000000000000000000000000000000000000000e87f28593f66d77ae3c57abd4e5ae0e632b837b2596be14c2b2572cd4d0015229976eb5c4a5b08816b31f485513d2e6501f6cd29ee240a2c4056b1f7cc32c2e118ef6499e0fcc575da75fca8cc409e5c884eb3450000000180800000000400403e2ff80a8a8ecacfb827dcf6adddd21fdd4c3c000000000000017800000000000000000000f3f497268f8a12e318f96ba4f1ad3ed2485e87cefe75b88bf735bb1bbb7db754746e6a244ba869183a2ab73002c6465936b7d9b059ffc5a94488bee7b5afb33c010200000000040000000000000000000000000000000000000000000000000000000000000000";
+        let block =
+            StacksBlock::consensus_deserialize(&mut &hex_bytes(block_hex).unwrap()[..]).unwrap();
+        let expected: Vec<_> = block.txs.iter().map(|tx| tx.txid().to_string()).collect();
+        assert_eq!(expected.len(), 1);
+
+        let block_args = ["decode-block", "--txids-only", block_hex];
+        let result = main_handler(to_string_vec(&block_args)).unwrap();
+        assert_eq!(result.lines().collect::<Vec<_>>(), expected);
+
+        // flag may come after the block too
+        let block_args = ["decode-block", block_hex, "--txids-only"];
+        let result = main_handler(to_string_vec(&block_args)).unwrap();
+        assert_eq!(result.lines().collect::<Vec<_>>(), expected);
+    }
+
     #[test]
     fn simple_decode_header() {
         let header_args = [
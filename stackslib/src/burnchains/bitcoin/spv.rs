@@ -639,6 +639,55 @@ impl SpvClient {
         return Ok(());
     }
 
+    /// Walk every stored header and check that it links to its parent and that each difficulty
+    /// interval has valid proof-of-work.
+    /// Returns the height of the first header that fails a continuity check, or the first height
+    /// of the first difficulty interval that fails the proof-of-work check.  Returns None if every
+    /// header checks out.
+    pub fn find_first_bad_header(&self) -> Result<Option<u64>, btc_error> {
+        let num_headers = self.get_headers_height()?;
+        let mut prev_header: Option<LoneBlockHeader> = None;
+        let mut interval = 0;
+        while interval * BLOCK_DIFFICULTY_CHUNK_SIZE < num_headers {
+            let start_height = interval * BLOCK_DIFFICULTY_CHUNK_SIZE;
+            let headers =
+                self.read_block_headers(start_height, start_height + BLOCK_DIFFICULTY_CHUNK_SIZE)?;
+            for (i, header) in headers.iter().enumerate() {
+                let height = start_height + (i as u64);
+                if let Some(prev_header) = prev_header.as_ref() {
+                    if header.header.prev_blockhash != prev_header.header.bitcoin_hash() {
+                        warn!(
+                            "Bad SPV header for block {}: header prev_blockhash {} != prev_header hash {}",
+                            height,
+                            header.header.prev_blockhash,
+                            prev_header.header.bitcoin_hash()
+                        );
+                        return Ok(Some(height));
+                    }
+                }
+                prev_header = Some(header.clone());
+            }
+            if (headers.len() as u64) < BLOCK_DIFFICULTY_CHUNK_SIZE
+                && start_height + (headers.len() as u64) < num_headers
+            {
+                // gap in the headers table
+                return Ok(Some(start_height + (headers.len() as u64)));
+            }
+
+            match self.validate_header_work(interval, interval + 1) {
+                Ok(()) => {}
+                Err(btc_error::InvalidPoW) => {
+                    return Ok(Some(start_height));
+                }
+                Err(e) => {
+                    return Err(e);
+                }
+            }
+            interval += 1;
+        }
+        Ok(None)
+    }
+
     /// Report how many block headers (+ 1) we have downloaded to the given path.
     pub fn get_headers_height(&self) -> Result<u64, btc_error> {
         let max = self.get_highest_header_height()?;
@@ -692,6 +741,24 @@ impl SpvClient {
         .map_err(|e| e.into())
     }
 
+    /// Read the block header with a given burnchain header hash.
+    /// This is a single lookup on the headers table's hash index, so it does not scan the file.
+    /// Returns None if no stored header has this hash.
+    pub fn read_block_header_by_hash(
+        &self,
+        burn_header_hash: &BurnchainHeaderHash,
+    ) -> Result<Option<LoneBlockHeader>, btc_error> {
+        let header_opt: Option<BlockHeader> = query_row(
+            &self.headers_db,
+            "SELECT * FROM headers WHERE hash = ?1",
+            &[burn_header_hash],
+        )?;
+        Ok(header_opt.map(|h| LoneBlockHeader {
+            header: h,
+            tx_count: VarInt(0),
+        }))
+    }
+
     /// Get a range of block headers from a file.
     /// If the range falls of the end of the headers file, then the returned array will be
     /// truncated to not include them (note that this method can return an empty list of the
@@ -1438,8 +1505,70 @@ mod test {
         );
         assert_eq!(
             spv_client.read_block_headers(0, 10).unwrap(),
-            vec![genesis_regtest_header, first_regtest_header]
+            vec![genesis_regtest_header.clone(), first_regtest_header.clone()]
+        );
+
+        let first_hash =
+            BurnchainHeaderHash::from_bitcoin_hash(&first_regtest_header.header.bitcoin_hash());
+        assert_eq!(
+            spv_client
+                .read_block_header_by_hash(&first_hash)
+                .unwrap()
+                .unwrap(),
+            first_regtest_header
         );
+        assert!(spv_client
+            .read_block_header_by_hash(&BurnchainHeaderHash([0x11; 32]))
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_spv_find_first_bad_header() {
+        if fs::metadata("/tmp/test-spv-find-first-bad-header.dat").is_ok() {
+            fs::remove_file("/tmp/test-spv-find-first-bad-header.dat").unwrap();
+        }
+
+        let genesis_regtest_header = get_genesis_regtest_header();
+        let first_regtest_header = BlockHeader {
+            bits: 545259519,
+            merkle_root: Sha256dHash::from_hex(
+                "20bee96458517fc5082a9720ce6207b5742f2b18e4e0a7e7373342725d80f88c",
+            )
+            .unwrap(),
+            nonce: 2,
+            prev_blockhash: genesis_regtest_header.header.bitcoin_hash(),
+            time: 1587626881,
+            version: 0x20000000,
+        };
+        let mut bad_second_header = first_regtest_header.clone();
+        bad_second_header.prev_blockhash = genesis_regtest_header.header.bitcoin_hash();
+
+        let mut spv_client = SpvClient::new(
+            "/tmp/test-spv-find-first-bad-header.dat",
+            0,
+            None,
+            BitcoinNetworkType::Regtest,
+            true,
+            false,
+        )
+        .unwrap();
+        assert_eq!(spv_client.find_first_bad_header().unwrap(), None);
+
+        {
+            let mut tx = spv_client.tx_begin().unwrap();
+            SpvClient::insert_block_header(&mut tx, first_regtest_header, 1).unwrap();
+            tx.commit().unwrap();
+        }
+        assert_eq!(spv_client.find_first_bad_header().unwrap(), None);
+
+        // header 2 doesn't build on header 1
+        {
+            let mut tx = spv_client.tx_begin().unwrap();
+            SpvClient::insert_block_header(&mut tx, bad_second_header, 2).unwrap();
+            tx.commit().unwrap();
+        }
+        assert_eq!(spv_client.find_first_bad_header().unwrap(), Some(2));
     }
 
     #[test]
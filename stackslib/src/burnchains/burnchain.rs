@@ -468,6 +468,7 @@ impl Burnchain {
             .is_reward_cycle_start(self.first_block_height, burn_height)
     }
 
+    /// Get the burn block height at which the given reward cycle begins
     pub fn reward_cycle_to_block_height(&self, reward_cycle: u64) -> u64 {
         self.pox_constants
             .reward_cycle_to_block_height(self.first_block_height, reward_cycle)
@@ -485,6 +486,8 @@ impl Burnchain {
         Some(cycle + next_bump)
     }
 
+    /// Get the reward cycle which contains the given burn block height.
+    /// Returns None if the height is before `first_block_height`.
     pub fn block_height_to_reward_cycle(&self, block_height: u64) -> Option<u64> {
         self.pox_constants
             .block_height_to_reward_cycle(self.first_block_height, block_height)
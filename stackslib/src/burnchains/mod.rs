@@ -760,6 +760,17 @@ impl From<btc_error> for Error {
 }
 
 impl BurnchainView {
+    /// Is the given burn block height at or below our stable tip?
+    pub fn is_stable(&self, height: u64) -> bool {
+        height <= self.burn_stable_block_height
+    }
+
+    /// How many blocks have been mined on top of the given burn block height?
+    /// Returns 0 for heights at or beyond our chain tip.
+    pub fn confirmations(&self, height: u64) -> u64 {
+        self.burn_block_height.saturating_sub(height)
+    }
+
     #[cfg(test)]
     pub fn make_test_data(&mut self) {
         let oldest_height = if self.burn_stable_block_height < MAX_NEIGHBOR_BLOCK_DELAY {
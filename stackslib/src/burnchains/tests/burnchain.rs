@@ -14,6 +14,8 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+use std::collections::HashMap;
+
 use rand::rngs::ThreadRng;
 use rand::thread_rng;
 use rand_chacha::ChaChaRng;
@@ -914,3 +916,24 @@ fn test_burn_snapshot_sequence() {
         prev_snapshot = snapshot;
     }
 }
+
+#[test]
+fn test_burnchain_view_stability() {
+    let chain_view = BurnchainView {
+        burn_block_height: 100,
+        burn_block_hash: BurnchainHeaderHash([0x01; 32]),
+        burn_stable_block_height: 93,
+        burn_stable_block_hash: BurnchainHeaderHash([0x02; 32]),
+        last_burn_block_hashes: HashMap::new(),
+        rc_consensus_hash: ConsensusHash([0x03; 20]),
+    };
+
+    assert!(chain_view.is_stable(0));
+    assert!(chain_view.is_stable(93));
+    assert!(!chain_view.is_stable(94));
+    assert!(!chain_view.is_stable(100));
+
+    assert_eq!(chain_view.confirmations(93), 7);
+    assert_eq!(chain_view.confirmations(100), 0);
+    assert_eq!(chain_view.confirmations(101), 0);
+}
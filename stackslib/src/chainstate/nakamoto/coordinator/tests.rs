@@ -718,6 +718,20 @@ fn test_nakamoto_chainstate_getters() {
         &blocks.last().unwrap().header
     );
 
+    // the canonical chain tip is the last Nakamoto block, which is also the highest one known
+    {
+        let chainstate = &peer.stacks_node.as_mut().unwrap().chainstate;
+        let sort_db = peer.sortdb.as_mut().unwrap();
+        assert_eq!(
+            chainstate.get_canonical_chain_tip(sort_db).unwrap(),
+            Some(tip.index_block_hash())
+        );
+        assert_eq!(
+            chainstate.get_highest_known_block_id().unwrap(),
+            Some(tip.index_block_hash())
+        );
+    }
+
     let sort_tip = {
         let sort_db = peer.sortdb.as_ref().unwrap();
         SortitionDB::get_canonical_burn_chain_tip(sort_db.conn()).unwrap()
@@ -924,6 +924,86 @@ impl StacksMicroblock {
         }
         return true;
     }
+
+    /// Decode a consensus-encoded vector of microblocks one microblock at a time, without
+    /// buffering the whole stream in RAM.
+    pub fn consensus_decode_stream<R: Read>(reader: R) -> StacksMicroblockStream<R> {
+        StacksMicroblockStream {
+            fd: reader,
+            remaining: None,
+            bytes_read: 0,
+            last_offset: 0,
+            failed: false,
+        }
+    }
+}
+
+/// Iterator over a consensus-encoded `Vec<StacksMicroblock>`.  The length prefix is read on the
+/// first call to `next()`, and each microblock is decoded as it is requested.  Iteration stops
+/// after the first error.
+pub struct StacksMicroblockStream<R: Read> {
+    fd: R,
+    /// number of microblocks left to decode, once the length prefix has been read
+    remaining: Option<u32>,
+    /// number of bytes consumed from `fd` so far
+    bytes_read: u64,
+    /// offset at which the last microblock (or the length prefix) began
+    last_offset: u64,
+    /// whether or not we encountered an error
+    failed: bool,
+}
+
+impl<R: Read> StacksMicroblockStream<R> {
+    /// Total number of bytes consumed from the underlying reader
+    pub fn bytes_read(&self) -> u64 {
+        self.bytes_read
+    }
+
+    /// Byte offset at which the most recently decoded item began.  If `next()` just returned an
+    /// error, this is where the malformed microblock starts.
+    pub fn last_offset(&self) -> u64 {
+        self.last_offset
+    }
+
+    /// Decode the next item from the stream, tracking how many bytes it took
+    fn read_next_counted<T: StacksMessageCodec>(&mut self) -> Result<T, net_error> {
+        let mut bound_read = BoundReader::from_reader(&mut self.fd, u64::MAX);
+        let res = read_next(&mut bound_read);
+        self.last_offset = self.bytes_read;
+        self.bytes_read += bound_read.num_read();
+        res.map_err(net_error::from)
+    }
+}
+
+impl<R: Read> Iterator for StacksMicroblockStream<R> {
+    type Item = Result<StacksMicroblock, net_error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        let remaining = match self.remaining {
+            Some(remaining) => remaining,
+            None => match self.read_next_counted::<u32>() {
+                Ok(len) => len,
+                Err(e) => {
+                    self.failed = true;
+                    return Some(Err(e));
+                }
+            },
+        };
+
+        if remaining == 0 {
+            self.remaining = Some(0);
+            return None;
+        }
+
+        let res = self.read_next_counted::<StacksMicroblock>();
+        self.failed = res.is_err();
+        self.remaining = Some(remaining - 1);
+        Some(res)
+    }
 }
 
 #[cfg(test)]
@@ -1200,6 +1280,62 @@ mod test {
         }
     }
 
+    #[test]
+    fn codec_stacks_microblock_stream() {
+        let all_txs = codec_all_transactions(
+            &TransactionVersion::Testnet,
+            0x80000000,
+            &TransactionAnchorMode::OffChainOnly,
+            &TransactionPostConditionMode::Allow,
+        );
+        let txs: Vec<_> = all_txs
+            .into_iter()
+            .filter(|tx| !matches!(tx.payload, TransactionPayload::Coinbase(..)))
+            .collect();
+
+        let mblocks: Vec<_> = txs
+            .chunks(3)
+            .map(|txs| StacksMicroblock::first_unsigned(&BlockHeaderHash([0x11; 32]), txs.to_vec()))
+            .collect();
+
+        let mut stream_bytes = vec![];
+        mblocks.consensus_serialize(&mut stream_bytes).unwrap();
+
+        let decoded: Vec<_> = StacksMicroblock::consensus_decode_stream(&stream_bytes[..])
+            .map(|res| res.unwrap())
+            .collect();
+        assert_eq!(decoded, mblocks);
+
+        // empty stream
+        let mut empty_bytes = vec![];
+        Vec::<StacksMicroblock>::new()
+            .consensus_serialize(&mut empty_bytes)
+            .unwrap();
+        assert_eq!(
+            StacksMicroblock::consensus_decode_stream(&empty_bytes[..]).count(),
+            0
+        );
+
+        // truncate the last microblock; everything before it still decodes, and the error
+        // reports where the truncated microblock began
+        let mut last_bytes = vec![];
+        mblocks
+            .last()
+            .unwrap()
+            .consensus_serialize(&mut last_bytes)
+            .unwrap();
+        let last_offset = (stream_bytes.len() - last_bytes.len()) as u64;
+        let truncated = &stream_bytes[..stream_bytes.len() - 1];
+
+        let mut stream = StacksMicroblock::consensus_decode_stream(truncated);
+        for mblock in mblocks[..mblocks.len() - 1].iter() {
+            assert_eq!(&stream.next().unwrap().unwrap(), mblock);
+        }
+        assert!(stream.next().unwrap().is_err());
+        assert_eq!(stream.last_offset(), last_offset);
+        assert!(stream.next().is_none());
+    }
+
     #[test]
     fn stacks_microblock_sign_verify() {
         let privk = StacksPrivateKey::from_hex(
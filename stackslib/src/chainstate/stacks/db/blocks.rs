@@ -29,7 +29,8 @@ use clarity::vm::contracts::Contract;
 use clarity::vm::costs::LimitedCostTracker;
 use clarity::vm::database::{BurnStateDB, ClarityDatabase, NULL_BURN_STATE_DB};
 use clarity::vm::types::{
-    AssetIdentifier, BuffData, PrincipalData, QualifiedContractIdentifier, SequenceData,
+    AssetIdentifier, BuffData, FixedFunction, FunctionType, PrincipalData,
+    QualifiedContractIdentifier, SequenceData,
     StacksAddressExtensions as ClarityStacksAddressExtensions, StandardPrincipalData, TupleData,
     TypeSignature, Value,
 };
@@ -116,12 +117,18 @@ pub enum MemPoolRejection {
     SerializationFailure(codec_error),
     DeserializationFailure(codec_error),
     FailedToValidate(Error),
+    /// The fee paid, and the minimum fee this transaction must pay to be admitted
     FeeTooLow(u64, u64),
     BadNonces(TransactionNonceMismatch),
     NotEnoughFunds(u128, u128),
     NoSuchContract,
     NoSuchPublicFunction,
     BadFunctionArgument(CheckError),
+    /// The contract-call passes the wrong number of arguments to the function
+    BadFunctionArity {
+        expected: usize,
+        got: usize,
+    },
     ContractAlreadyExists(QualifiedContractIdentifier),
     PoisonMicroblocksDoNotConflict,
     NoAnchorBlockWithPubkeyHash(Hash160),
@@ -143,6 +150,8 @@ pub enum MemPoolRejection {
     DBError(db_error),
     EstimatorError(EstimatorError),
     TemporarilyBlacklisted,
+    /// The mempool already has this exact transaction
+    AlreadyKnown(Txid),
     Other(String),
 }
 
@@ -277,6 +286,12 @@ impl MemPoolRejection {
                 "BadFunctionArgument",
                 Some(json!({"message": e.to_string()})),
             ),
+            BadFunctionArity { expected, got } => (
+                "BadFunctionArity",
+                Some(json!({
+                    "expected": expected,
+                    "got": got})),
+            ),
             ConflictingNonceInMempool => ("ConflictingNonceInMempool", None),
             ContractAlreadyExists(id) => (
                 "ContractAlreadyExists",
@@ -295,6 +310,7 @@ impl MemPoolRejection {
                 Some(json!({"message": e.to_string()})),
             ),
             TemporarilyBlacklisted => ("TemporarilyBlacklisted", None),
+            AlreadyKnown(_txid) => ("AlreadyKnown", None),
             Other(s) => ("ServerFailureOther", Some(json!({ "message": s }))),
         };
         let mut result = json!({
@@ -6603,6 +6619,16 @@ impl StacksChainState {
         }
     }
 
+    /// Get the minimum fee the mempool will accept for a transaction of the given serialized
+    /// size, given the minimum fee and minimum fee rate.
+    /// NOTE: not consensus-critical; only used for mempool admission
+    pub fn get_minimum_mempool_tx_fee(tx_size: u64) -> u64 {
+        cmp::max(
+            MINIMUM_TX_FEE,
+            tx_size.saturating_mul(MINIMUM_TX_FEE_RATE_PER_BYTE),
+        )
+    }
+
     /// Check to see if a transaction can be (potentially) appended on top of a given chain tip.
     /// Note that this only checks the transaction against the _anchored chain tip_, not the
     /// unconfirmed microblock stream trailing off of it.
@@ -6615,13 +6641,7 @@ impl StacksChainState {
         tx_size: u64,
     ) -> Result<(), MemPoolRejection> {
         let is_mainnet = self.clarity_state.is_mainnet();
-        StacksChainState::can_admit_mempool_semantic(tx, is_mainnet)?;
-
-        if matches!(tx.payload, TransactionPayload::PoisonMicroblock(..)) {
-            return Err(MemPoolRejection::Other(
-                "PoisonMicroblock transactions not accepted via mempool".into(),
-            ));
-        }
+        StacksChainState::can_admit_mempool_static(tx, is_mainnet)?;
 
         let conf = self.config();
 
@@ -6638,6 +6658,62 @@ impl StacksChainState {
         }
     }
 
+    /// Check to see if each of a batch of transactions can be (potentially) appended on top of a
+    /// given chain tip.  This is the same as calling `will_admit_mempool_tx()` on each
+    /// transaction, except that the chain tip's state is only opened once.  Each transaction is
+    /// checked independently; a failure on one does not affect the others.
+    /// Returns one result per transaction, in the same order as `txs`.
+    pub fn will_admit_mempool_txs(
+        &mut self,
+        burn_state_db: &dyn BurnStateDB,
+        current_consensus_hash: &ConsensusHash,
+        current_block: &BlockHeaderHash,
+        txs: &[(StacksTransaction, u64)],
+    ) -> Vec<Result<(), MemPoolRejection>> {
+        let is_mainnet = self.clarity_state.is_mainnet();
+        let conf = self.config();
+
+        let current_tip =
+            StacksChainState::get_parent_index_block(current_consensus_hash, current_block);
+        let results_opt = self.with_read_only_clarity_tx(burn_state_db, &current_tip, |conn| {
+            txs.iter()
+                .map(|(tx, tx_size)| {
+                    StacksChainState::can_admit_mempool_static(tx, is_mainnet)?;
+                    StacksChainState::can_include_tx(conn, &conf, false, tx, *tx_size)
+                })
+                .collect::<Vec<_>>()
+        });
+        match results_opt {
+            Some(results) => results,
+            None => txs
+                .iter()
+                .map(|(tx, _)| {
+                    StacksChainState::can_admit_mempool_static(tx, is_mainnet)?;
+                    Err(MemPoolRejection::NoSuchChainTip(
+                        current_consensus_hash.clone(),
+                        current_block.clone(),
+                    ))
+                })
+                .collect(),
+        }
+    }
+
+    /// Checks for mempool admission that don't require chain state: the transaction's semantic
+    /// validity, and whether or not it's a kind of transaction the mempool accepts at all.
+    fn can_admit_mempool_static(
+        tx: &StacksTransaction,
+        is_mainnet: bool,
+    ) -> Result<(), MemPoolRejection> {
+        StacksChainState::can_admit_mempool_semantic(tx, is_mainnet)?;
+
+        if matches!(tx.payload, TransactionPayload::PoisonMicroblock(..)) {
+            return Err(MemPoolRejection::Other(
+                "PoisonMicroblock transactions not accepted via mempool".into(),
+            ));
+        }
+        Ok(())
+    }
+
     /// Given an outstanding clarity connection, can we append the tx to the chain state?
     /// Used when determining whether a transaction can be added to the mempool.
     fn can_include_tx<T: ClarityConnection>(
@@ -6656,11 +6732,9 @@ impl StacksChainState {
         // 3: it must pay a tx fee
         let fee = tx.get_tx_fee();
 
-        if fee < MINIMUM_TX_FEE || fee / tx_size < MINIMUM_TX_FEE_RATE_PER_BYTE {
-            return Err(MemPoolRejection::FeeTooLow(
-                fee,
-                cmp::max(MINIMUM_TX_FEE, tx_size * MINIMUM_TX_FEE_RATE_PER_BYTE),
-            ));
+        let minimum_fee = StacksChainState::get_minimum_mempool_tx_fee(tx_size);
+        if fee < minimum_fee {
+            return Err(MemPoolRejection::FeeTooLow(fee, minimum_fee));
         }
 
         // 4: the account nonces must be correct
@@ -6826,6 +6900,14 @@ impl StacksChainState {
                         .get_public_function_type(&contract_identifier, &function_name, &epoch)
                         .map_err(|_e| MemPoolRejection::NoSuchContract)?
                         .ok_or_else(|| MemPoolRejection::NoSuchPublicFunction)?;
+                    if let FunctionType::Fixed(FixedFunction { ref args, .. }) = function_type {
+                        if args.len() != function_args.len() {
+                            return Err(MemPoolRejection::BadFunctionArity {
+                                expected: args.len(),
+                                got: function_args.len(),
+                            });
+                        }
+                    }
                     let clarity_version = db
                         .get_clarity_version(&contract_identifier)
                         .map_err(|_e| MemPoolRejection::NoSuchContract)?;
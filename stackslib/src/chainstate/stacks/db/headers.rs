@@ -24,6 +24,7 @@ use rusqlite::types::ToSql;
 use rusqlite::{OptionalExtension, Row};
 use stacks_common::types::chainstate::{StacksBlockId, StacksWorkScore};
 
+use crate::chainstate::burn::db::sortdb::SortitionDB;
 use crate::chainstate::burn::ConsensusHash;
 use crate::chainstate::nakamoto::NakamotoChainState;
 use crate::chainstate::stacks::db::*;
@@ -395,6 +396,58 @@ impl StacksChainState {
             .map_err(|e| e.into())
     }
 
+    /// Get the index block hash of the canonical Stacks chain tip, whether it is an epoch 2.x or a
+    /// Nakamoto block.  The tip is the one the sortition DB considers canonical, so it is always
+    /// on the canonical fork.  Tooling which only has the chainstate open can use
+    /// `get_highest_known_block_id()` instead.
+    /// Returns None only if the canonical tip has not been processed.
+    pub fn get_canonical_chain_tip(
+        &self,
+        sortdb: &SortitionDB,
+    ) -> Result<Option<StacksBlockId>, Error> {
+        Ok(
+            NakamotoChainState::get_canonical_block_header(self.db(), sortdb)?
+                .map(|header| header.index_block_hash()),
+        )
+    }
+
+    /// Get the header of the highest processed Stacks block, whether it is an epoch 2.x or a
+    /// Nakamoto block.  Ties are broken by preferring Nakamoto blocks, then the block with the
+    /// highest burnchain height, and then arbitrarily (by index block hash).
+    /// This does not consult the sortition DB, so it can be used by tooling that only has the
+    /// chainstate open -- but for the same reason, the block it returns is not necessarily on the
+    /// canonical fork.  Use `NakamotoChainState::get_canonical_block_header()` for that.
+    /// Returns None only if no block (not even the boot block) has been processed.
+    pub fn get_highest_known_block_header(&self) -> Result<Option<StacksHeaderInfo>, Error> {
+        let nakamoto_qry = "SELECT * FROM nakamoto_block_headers ORDER BY block_height DESC, burn_header_height DESC, index_block_hash ASC LIMIT 1";
+        let nakamoto_header: Option<StacksHeaderInfo> =
+            query_row(self.db(), nakamoto_qry, NO_PARAMS).map_err(Error::DBError)?;
+
+        let epoch2_qry = "SELECT * FROM block_headers ORDER BY block_height DESC, burn_header_height DESC, index_block_hash ASC LIMIT 1";
+        let epoch2_header: Option<StacksHeaderInfo> =
+            query_row(self.db(), epoch2_qry, NO_PARAMS).map_err(Error::DBError)?;
+
+        let header = match (nakamoto_header, epoch2_header) {
+            (Some(nakamoto_header), Some(epoch2_header)) => {
+                if nakamoto_header.stacks_block_height >= epoch2_header.stacks_block_height {
+                    Some(nakamoto_header)
+                } else {
+                    Some(epoch2_header)
+                }
+            }
+            (nakamoto_header, epoch2_header) => nakamoto_header.or(epoch2_header),
+        };
+        Ok(header)
+    }
+
+    /// Get the index block hash of the highest processed Stacks block.
+    /// See `get_highest_known_block_header()` for how the block is chosen.
+    pub fn get_highest_known_block_id(&self) -> Result<Option<StacksBlockId>, Error> {
+        Ok(self
+            .get_highest_known_block_header()?
+            .map(|header| header.index_block_hash()))
+    }
+
     /// Get the highest known header affirmation weight
     pub fn get_max_affirmation_weight_at_height(
         conn: &Connection,
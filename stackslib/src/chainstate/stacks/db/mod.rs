@@ -2672,6 +2672,7 @@ pub mod test {
     use super::*;
     use crate::chainstate::stacks::db::*;
     use crate::chainstate::stacks::*;
+    use crate::core::tests::make_block;
     use crate::util_lib::boot::boot_code_test_addr;
 
     pub fn instantiate_chainstate(
@@ -2754,6 +2755,38 @@ pub mod test {
         }
     }
 
+    #[test]
+    fn test_get_highest_known_block() {
+        let mut chainstate = instantiate_chainstate(false, 0x80000000, function_name!());
+
+        // only the boot block so far
+        let genesis_id =
+            StacksBlockId::new(&FIRST_BURNCHAIN_CONSENSUS_HASH, &FIRST_STACKS_BLOCK_HASH);
+        assert_eq!(
+            chainstate.get_highest_known_block_id().unwrap(),
+            Some(genesis_id)
+        );
+
+        let genesis = (
+            FIRST_BURNCHAIN_CONSENSUS_HASH.clone(),
+            FIRST_STACKS_BLOCK_HASH.clone(),
+        );
+        let b_1 = make_block(&mut chainstate, ConsensusHash([0x1; 20]), &genesis, 1, 1);
+        let b_2 = make_block(&mut chainstate, ConsensusHash([0x2; 20]), &b_1, 2, 2);
+        // a shorter fork, processed last
+        let _c_1 = make_block(&mut chainstate, ConsensusHash([0x3; 20]), &genesis, 3, 1);
+
+        let tip = chainstate.get_highest_known_block_id().unwrap().unwrap();
+        assert_eq!(tip, StacksBlockId::new(&b_2.0, &b_2.1));
+
+        let tip_header = chainstate
+            .get_highest_known_block_header()
+            .unwrap()
+            .unwrap();
+        assert_eq!(tip_header.stacks_block_height, 2);
+        assert_eq!(tip_header.consensus_hash, b_2.0);
+    }
+
     #[test]
     fn test_chainstate_sampled_genesis_consistency() {
         // Test root hash for the test chainstate data set
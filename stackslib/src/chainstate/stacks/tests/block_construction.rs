@@ -48,6 +48,7 @@ use crate::chainstate::burn::operations::{
 use crate::chainstate::burn::*;
 use crate::chainstate::coordinator::Error as CoordinatorError;
 use crate::chainstate::stacks::db::blocks::test::store_staging_block;
+use crate::chainstate::stacks::db::blocks::MemPoolRejection;
 use crate::chainstate::stacks::db::test::*;
 use crate::chainstate::stacks::db::*;
 use crate::chainstate::stacks::events::StacksTransactionReceipt;
@@ -57,7 +58,7 @@ use crate::chainstate::stacks::tests::*;
 use crate::chainstate::stacks::{
     Error as ChainstateError, C32_ADDRESS_VERSION_TESTNET_SINGLESIG, *,
 };
-use crate::core::mempool::MemPoolWalkSettings;
+use crate::core::mempool::{MemPoolAdmitter, MemPoolWalkSettings};
 use crate::core::tests::make_block;
 use crate::core::{FIRST_BURNCHAIN_CONSENSUS_HASH, *};
 use crate::cost_estimates::metrics::UnitMetric;
@@ -290,6 +291,279 @@ fn test_build_anchored_blocks_stx_transfers_single() {
     }
 }
 
+#[test]
+fn test_mempool_rejects_already_known_tx() {
+    let privk = StacksPrivateKey::from_hex(
+        "42faca653724860da7a41bfcef7e6ba78db55146f6900de8cb2a9f760ffac70c01",
+    )
+    .unwrap();
+    let addr = StacksAddress::from_public_keys(
+        C32_ADDRESS_VERSION_TESTNET_SINGLESIG,
+        &AddressHashMode::SerializeP2PKH,
+        1,
+        &vec![StacksPublicKey::from_private(&privk)],
+    )
+    .unwrap();
+
+    let mut peer_config = TestPeerConfig::new(function_name!(), 2040, 2041);
+    peer_config.initial_balances = vec![(addr.to_account_principal(), 1000000000)];
+    let burnchain = peer_config.burnchain.clone();
+
+    let mut peer = TestPeer::new(peer_config);
+
+    let chainstate_path = peer.chainstate_path.clone();
+
+    let recipient_addr_str = "ST1RFD5Q2QPK3E0F08HG9XDX7SSC7CNRS0QR0SGEV";
+    let recipient = StacksAddress::from_string(recipient_addr_str).unwrap();
+
+    for tenure_id in 0..2 {
+        let tip = SortitionDB::get_canonical_burn_chain_tip(&peer.sortdb.as_ref().unwrap().conn())
+            .unwrap();
+
+        let (burn_ops, stacks_block, microblocks) = peer.make_tenure(
+            |ref mut miner,
+             ref mut sortdb,
+             ref mut chainstate,
+             vrf_proof,
+             ref parent_opt,
+             ref parent_microblock_header_opt| {
+                let parent_tip = match parent_opt {
+                    None => StacksChainState::get_genesis_header_info(chainstate.db()).unwrap(),
+                    Some(block) => {
+                        let ic = sortdb.index_conn();
+                        let snapshot = SortitionDB::get_block_snapshot_for_winning_stacks_block(
+                            &ic,
+                            &tip.sortition_id,
+                            &block.block_hash(),
+                        )
+                        .unwrap()
+                        .unwrap(); // succeeds because we don't fork
+                        StacksChainState::get_anchored_block_header_info(
+                            chainstate.db(),
+                            &snapshot.consensus_hash,
+                            &snapshot.winning_stacks_block_hash,
+                        )
+                        .unwrap()
+                        .unwrap()
+                    }
+                };
+
+                let parent_header_hash = parent_tip.anchored_header.block_hash();
+                let parent_consensus_hash = parent_tip.consensus_hash.clone();
+
+                let mut mempool =
+                    MemPoolDB::open_test(false, 0x80000000, &chainstate_path).unwrap();
+
+                let coinbase_tx = make_coinbase(miner, tenure_id);
+
+                if tenure_id > 0 {
+                    let stx_transfer = make_user_stacks_transfer(
+                        &privk,
+                        0,
+                        200,
+                        &recipient.to_account_principal(),
+                        1,
+                    );
+
+                    mempool
+                        .submit(
+                            chainstate,
+                            sortdb,
+                            &parent_consensus_hash,
+                            &parent_header_hash,
+                            &stx_transfer,
+                            None,
+                            &ExecutionCost::max_value(),
+                            &StacksEpochId::Epoch20,
+                        )
+                        .unwrap();
+
+                    // resubmitting the same transaction is detected as a duplicate
+                    match mempool.submit(
+                        chainstate,
+                        sortdb,
+                        &parent_consensus_hash,
+                        &parent_header_hash,
+                        &stx_transfer,
+                        None,
+                        &ExecutionCost::max_value(),
+                        &StacksEpochId::Epoch20,
+                    ) {
+                        Err(MemPoolRejection::AlreadyKnown(txid)) => {
+                            assert_eq!(txid, stx_transfer.txid());
+                        }
+                        res => {
+                            panic!("Expected AlreadyKnown, got {:?}", &res);
+                        }
+                    }
+                }
+
+                let anchored_block = StacksBlockBuilder::build_anchored_block(
+                    chainstate,
+                    &sortdb.index_conn(),
+                    &mut mempool,
+                    &parent_tip,
+                    tip.total_burn,
+                    vrf_proof,
+                    Hash160([tenure_id as u8; 20]),
+                    &coinbase_tx,
+                    BlockBuilderSettings::max_value(),
+                    None,
+                    &burnchain,
+                )
+                .unwrap();
+                (anchored_block.0, vec![])
+            },
+        );
+
+        peer.next_burnchain_block(burn_ops.clone());
+        peer.process_stacks_epoch_at_tip(&stacks_block, &microblocks);
+    }
+}
+
+#[test]
+fn test_mempool_admitter_batch() {
+    let privk = StacksPrivateKey::from_hex(
+        "42faca653724860da7a41bfcef7e6ba78db55146f6900de8cb2a9f760ffac70c01",
+    )
+    .unwrap();
+    let addr = StacksAddress::from_public_keys(
+        C32_ADDRESS_VERSION_TESTNET_SINGLESIG,
+        &AddressHashMode::SerializeP2PKH,
+        1,
+        &vec![StacksPublicKey::from_private(&privk)],
+    )
+    .unwrap();
+
+    let mut peer_config = TestPeerConfig::new(function_name!(), 2042, 2043);
+    peer_config.initial_balances = vec![(addr.to_account_principal(), 1000000000)];
+    let burnchain = peer_config.burnchain.clone();
+
+    let mut peer = TestPeer::new(peer_config);
+
+    let chainstate_path = peer.chainstate_path.clone();
+
+    let recipient_addr_str = "ST1RFD5Q2QPK3E0F08HG9XDX7SSC7CNRS0QR0SGEV";
+    let recipient = StacksAddress::from_string(recipient_addr_str).unwrap();
+
+    for tenure_id in 0..2 {
+        let tip = SortitionDB::get_canonical_burn_chain_tip(&peer.sortdb.as_ref().unwrap().conn())
+            .unwrap();
+
+        let (burn_ops, stacks_block, microblocks) = peer.make_tenure(
+            |ref mut miner,
+             ref mut sortdb,
+             ref mut chainstate,
+             vrf_proof,
+             ref parent_opt,
+             ref parent_microblock_header_opt| {
+                let parent_tip = match parent_opt {
+                    None => StacksChainState::get_genesis_header_info(chainstate.db()).unwrap(),
+                    Some(block) => {
+                        let ic = sortdb.index_conn();
+                        let snapshot = SortitionDB::get_block_snapshot_for_winning_stacks_block(
+                            &ic,
+                            &tip.sortition_id,
+                            &block.block_hash(),
+                        )
+                        .unwrap()
+                        .unwrap(); // succeeds because we don't fork
+                        StacksChainState::get_anchored_block_header_info(
+                            chainstate.db(),
+                            &snapshot.consensus_hash,
+                            &snapshot.winning_stacks_block_hash,
+                        )
+                        .unwrap()
+                        .unwrap()
+                    }
+                };
+
+                let parent_header_hash = parent_tip.anchored_header.block_hash();
+                let parent_consensus_hash = parent_tip.consensus_hash.clone();
+
+                let mut mempool =
+                    MemPoolDB::open_test(false, 0x80000000, &chainstate_path).unwrap();
+
+                let coinbase_tx = make_coinbase(miner, tenure_id);
+
+                if tenure_id > 0 {
+                    let good_tx = make_user_stacks_transfer(
+                        &privk,
+                        0,
+                        200,
+                        &recipient.to_account_principal(),
+                        1,
+                    );
+                    let no_fee_tx = make_user_stacks_transfer(
+                        &privk,
+                        0,
+                        0,
+                        &recipient.to_account_principal(),
+                        2,
+                    );
+                    let other_good_tx = make_user_stacks_transfer(
+                        &privk,
+                        0,
+                        300,
+                        &recipient.to_account_principal(),
+                        3,
+                    );
+
+                    let good_tx_bytes = good_tx.serialize_to_vec();
+                    let no_fee_tx_bytes = no_fee_tx.serialize_to_vec();
+                    let other_good_tx_bytes = other_good_tx.serialize_to_vec();
+                    let garbage_bytes = vec![0xff, 0xff, 0xff];
+
+                    let mut admitter =
+                        MemPoolAdmitter::new(parent_header_hash.clone(), parent_consensus_hash);
+                    let mut results = admitter.will_admit_txs(
+                        chainstate,
+                        sortdb,
+                        vec![
+                            &good_tx_bytes[..],
+                            &garbage_bytes[..],
+                            &no_fee_tx_bytes[..],
+                            &other_good_tx_bytes[..],
+                        ],
+                    );
+                    assert_eq!(results.len(), 4);
+
+                    // failures don't abort the batch
+                    assert_eq!(results.remove(0).unwrap(), good_tx);
+                    match results.remove(0) {
+                        Err(MemPoolRejection::DeserializationFailure(_)) => {}
+                        res => panic!("Expected DeserializationFailure, got {:?}", &res),
+                    }
+                    match results.remove(0) {
+                        Err(MemPoolRejection::FeeTooLow(0, _)) => {}
+                        res => panic!("Expected FeeTooLow, got {:?}", &res),
+                    }
+                    assert_eq!(results.remove(0).unwrap(), other_good_tx);
+                }
+
+                let anchored_block = StacksBlockBuilder::build_anchored_block(
+                    chainstate,
+                    &sortdb.index_conn(),
+                    &mut mempool,
+                    &parent_tip,
+                    tip.total_burn,
+                    vrf_proof,
+                    Hash160([tenure_id as u8; 20]),
+                    &coinbase_tx,
+                    BlockBuilderSettings::max_value(),
+                    None,
+                    &burnchain,
+                )
+                .unwrap();
+                (anchored_block.0, vec![])
+            },
+        );
+
+        peer.next_burnchain_block(burn_ops.clone());
+        peer.process_stacks_epoch_at_tip(&stacks_block, &microblocks);
+    }
+}
+
 #[test]
 fn test_build_anchored_blocks_empty_with_builder_timeout() {
     let privk = StacksPrivateKey::from_hex(
@@ -3678,6 +3678,35 @@ mod test {
         }
     }
 
+    #[test]
+    fn tx_stacks_transaction_deserialize_exact() {
+        let all_txs = codec_all_transactions(
+            &TransactionVersion::Mainnet,
+            0,
+            &TransactionAnchorMode::OnChainOnly,
+            &TransactionPostConditionMode::Deny,
+        );
+        for tx in all_txs.iter() {
+            let mut tx_bytes = tx.serialize_to_vec();
+            let decoded: StacksTransaction =
+                consensus_deserialize_exact(&mut &tx_bytes[..]).unwrap();
+            assert_eq!(&decoded, tx);
+
+            // one trailing byte is caught
+            tx_bytes.push(0x00);
+            match consensus_deserialize_exact::<StacksTransaction, _>(&mut &tx_bytes[..]) {
+                Err(net_error::TrailingBytes(1)) => {}
+                x => panic!("Expected TrailingBytes(1), got {:?}", &x),
+            }
+
+            // but the plain decoder ignores it
+            assert_eq!(
+                &StacksTransaction::consensus_deserialize(&mut &tx_bytes[..]).unwrap(),
+                tx
+            );
+        }
+    }
+
     fn tx_stacks_transaction_test_txs(auth: &TransactionAuth) -> Vec<StacksTransaction> {
         let header_1 = StacksMicroblockHeader {
             version: 0x12,
@@ -55,10 +55,12 @@ use crate::clarity::vm::database::{
     BurnStateDB, ClarityDatabase, HeadersDB, STXBalance, SqliteConnection, NULL_BURN_STATE_DB,
 };
 use crate::clarity::vm::errors::{Error, InterpreterResult, RuntimeErrorType};
+#[cfg(feature = "developer-mode")]
+use crate::clarity::vm::trace::{TraceCollector, TraceEntry};
 use crate::clarity::vm::types::{OptionalData, PrincipalData, QualifiedContractIdentifier};
 use crate::clarity::vm::{
-    analysis, ast, eval_all, ClarityVersion, ContractContext, ContractName, SymbolicExpression,
-    SymbolicExpressionType, Value,
+    analysis, ast, eval_all, ClarityVersion, ContractContext, ContractName, EvalHook,
+    SymbolicExpression, SymbolicExpressionType, Value,
 };
 use crate::clarity_vm::database::marf::{MarfedKV, WritableMarfStore};
 use crate::clarity_vm::database::MemoryBackingStore;
@@ -119,6 +121,9 @@ where command is one of:
   eval_at_block      like `eval_at_chaintip`, but accepts a index-block-hash to evaluate at,
                      must be passed eval string via stdin.
   eval_raw           to typecheck and evaluate an expression without a contract or database context.
+                     Pass --cost-limit with a JSON execution cost to abort once it is exceeded.
+                     Pass --trace to print each evaluated expression and its cost (developer-mode
+                     builds only; other builds reject it with an error).
   repl               to typecheck and evaluate expressions in a stdin/stdout loop.
   execute            to execute a public function of a defined contract.
   generate_address   to generate a random Stacks public address for testing purposes.
@@ -453,17 +458,95 @@ where
 /// Execute program in a transient environment. To be used only by CLI tools
 ///  for program evaluation, not by consensus critical code.
 pub fn vm_execute(program: &str, clarity_version: ClarityVersion) -> Result<Option<Value>, Error> {
+    let mut marf = MemoryBackingStore::new();
+    vm_execute_in(
+        program,
+        clarity_version,
+        false,
+        marf.as_clarity_db(),
+        LimitedCostTracker::new_free(),
+        None,
+    )
+}
+
+/// Execute program in a transient environment, like `vm_execute`, but abort with
+///  `CheckErrors::CostBalanceExceeded` once it costs more than `limit`.  The boot code is
+///  installed into the transient environment so that the cost contracts can be loaded.
+///  To be used only by CLI tools for program evaluation, not by consensus critical code.
+pub fn vm_execute_with_limit(
+    program: &str,
+    clarity_version: ClarityVersion,
+    limit: ExecutionCost,
+) -> Result<Option<Value>, Error> {
+    vm_execute_limited(program, clarity_version, false, limit, None)
+}
+
+/// Execute program in a transient environment with the boot code installed for `mainnet`,
+///  charging its costs against `limit` and passing each evaluation to `eval_hook`, if given.
+fn vm_execute_limited(
+    program: &str,
+    clarity_version: ClarityVersion,
+    mainnet: bool,
+    limit: ExecutionCost,
+    eval_hook: Option<&mut dyn EvalHook>,
+) -> Result<Option<Value>, Error> {
+    let mut marf = transient_store_with_boot_code(mainnet);
+    let mut conn = marf.as_clarity_db();
+    let cost_track = LimitedCostTracker::new(
+        mainnet,
+        default_chain_id(mainnet),
+        limit,
+        &mut conn,
+        DEFAULT_CLI_EPOCH,
+    )?;
+    vm_execute_in(
+        program,
+        clarity_version,
+        mainnet,
+        conn,
+        cost_track,
+        eval_hook.map(|hook| hook as &mut dyn EvalHook),
+    )
+}
+
+/// Execute program in a transient environment, like `vm_execute`, and record each expression
+///  it evaluates along with its result and cost.  The trace is returned even if the program
+///  fails, since that is when it is most useful.  Costs are tracked against an unbounded limit.
+///  To be used only by CLI tools for program evaluation, not by consensus critical code.
+#[cfg(feature = "developer-mode")]
+pub fn vm_execute_traced(
+    program: &str,
+    clarity_version: ClarityVersion,
+) -> (Result<Option<Value>, Error>, Vec<TraceEntry>) {
+    let mut tracer = TraceCollector::new();
+    let result = vm_execute_limited(
+        program,
+        clarity_version,
+        false,
+        ExecutionCost::max_value(),
+        Some(&mut tracer),
+    );
+    (result, tracer.into_entries())
+}
+
+fn vm_execute_in<'a>(
+    program: &str,
+    clarity_version: ClarityVersion,
+    mainnet: bool,
+    conn: ClarityDatabase<'a>,
+    cost_track: LimitedCostTracker,
+    eval_hook: Option<&'a mut dyn EvalHook>,
+) -> Result<Option<Value>, Error> {
     let contract_id = QualifiedContractIdentifier::transient();
     let mut contract_context = ContractContext::new(contract_id.clone(), clarity_version);
-    let mut marf = MemoryBackingStore::new();
-    let conn = marf.as_clarity_db();
     let mut global_context = GlobalContext::new(
-        false,
-        default_chain_id(false),
+        mainnet,
+        default_chain_id(mainnet),
         conn,
-        LimitedCostTracker::new_free(),
+        cost_track,
         DEFAULT_CLI_EPOCH,
     );
+    global_context.eval_hooks = eval_hook.map(|hook| vec![hook]);
     global_context.execute(|g| {
         let parsed = ast::build_ast_with_rules(
             &contract_id,
@@ -478,6 +561,78 @@ pub fn vm_execute(program: &str, clarity_version: ClarityVersion) -> Result<Opti
     })
 }
 
+/// Make an in-memory store with the boot code installed, for evaluating programs outside of a
+///  VM state database while still tracking their costs.
+fn transient_store_with_boot_code(mainnet: bool) -> MemoryBackingStore {
+    let header_db = CLIHeadersDB::new_memory(mainnet);
+    let mut marf = MemoryBackingStore::new();
+    install_boot_code(&header_db, &mut marf);
+    marf
+}
+
+/// Typecheck and evaluate `content` for the `eval_raw` command, on mainnet.  With a
+///  `cost_limit`, the program is evaluated by `vm_execute_limited` and aborts once it costs more
+///  than the limit.  With an `eval_hook` but no limit, it is evaluated against an unbounded
+///  limit, so that the costs the hook observes are real.  Otherwise, it is evaluated for free.
+fn eval_raw_program(
+    content: &str,
+    cost_limit: Option<ExecutionCost>,
+    eval_hook: Option<&mut dyn EvalHook>,
+) -> (i32, serde_json::Value) {
+    let contract_id = QualifiedContractIdentifier::transient();
+    let mut analysis_marf = MemoryBackingStore::new();
+    let mut ast = friendly_expect(
+        parse(&contract_id, content, ClarityVersion::Clarity2),
+        "Failed to parse program.",
+    );
+    if let Err((error, _)) = run_analysis_free(&contract_id, &mut ast, &mut analysis_marf, true) {
+        return (
+            1,
+            json!({
+                "error": {
+                    "analysis": serde_json::to_value(&format!("{}", error)).unwrap()
+                }
+            }),
+        );
+    }
+
+    let result = if cost_limit.is_some() || eval_hook.is_some() {
+        let limit = cost_limit.unwrap_or_else(ExecutionCost::max_value);
+        vm_execute_limited(content, ClarityVersion::Clarity2, true, limit, eval_hook)
+    } else {
+        let mut marf = MemoryBackingStore::new();
+        let mut vm_env = OwnedEnvironment::new_free(
+            true,
+            default_chain_id(true),
+            marf.as_clarity_db(),
+            DEFAULT_CLI_EPOCH,
+        );
+        let mut placeholder_context =
+            ContractContext::new(contract_id.clone(), ClarityVersion::Clarity2);
+        vm_env
+            .get_exec_environment(None, None, &mut placeholder_context)
+            .eval_raw_with_rules(content, ASTRules::PrecheckSize)
+            .map(Some)
+    };
+
+    match result {
+        Ok(x) => (
+            0,
+            json!({
+                "output": serde_json::to_value(&x).unwrap()
+            }),
+        ),
+        Err(error) => (
+            1,
+            json!({
+                "error": {
+                    "runtime": serde_json::to_value(&format!("{}", error)).unwrap()
+                }
+            }),
+        ),
+    }
+}
+
 fn save_coverage(
     coverage_folder: Option<String>,
     coverage: Option<CoverageReporter>,
@@ -1280,6 +1435,38 @@ pub fn invoke_command(invoked_by: &str, args: &[String]) -> (i32, Option<serde_j
             }
         }
         "eval_raw" => {
+            let mut argv: Vec<String> = args.into_iter().map(|x| x.clone()).collect();
+            let cost_limit = if let Ok(optarg) = consume_arg(&mut argv, &["--cost-limit"], true) {
+                optarg.map(|optarg_str| {
+                    friendly_expect(
+                        serde_json::from_str::<ExecutionCost>(&optarg_str),
+                        &format!("Error parsing cost limit '{}'", &optarg_str),
+                    )
+                })
+            } else {
+                eprintln!("Expected argument for --cost-limit");
+                panic_test!();
+            };
+
+            #[cfg(feature = "developer-mode")]
+            let mut tracer = if let Ok(Some(_)) = consume_arg(&mut argv, &["--trace"], false) {
+                Some(TraceCollector::new())
+            } else {
+                None
+            };
+
+            #[cfg(not(feature = "developer-mode"))]
+            if let Ok(Some(_)) = consume_arg(&mut argv, &["--trace"], false) {
+                return (
+                    1,
+                    Some(json!({
+                        "error": {
+                            "usage": "--trace requires a build with the developer-mode feature"
+                        }
+                    })),
+                );
+            }
+
             let content: String = {
                 let mut buffer = String::new();
                 friendly_expect(
@@ -1289,56 +1476,21 @@ pub fn invoke_command(invoked_by: &str, args: &[String]) -> (i32, Option<serde_j
                 buffer
             };
 
-            let mut analysis_marf = MemoryBackingStore::new();
-            let mut marf = MemoryBackingStore::new();
-            let mut vm_env = OwnedEnvironment::new_free(
-                true,
-                default_chain_id(true),
-                marf.as_clarity_db(),
-                DEFAULT_CLI_EPOCH,
+            #[cfg(feature = "developer-mode")]
+            let (exit, mut result_json) = eval_raw_program(
+                &content,
+                cost_limit,
+                tracer.as_mut().map(|tracer| tracer as &mut dyn EvalHook),
             );
+            #[cfg(not(feature = "developer-mode"))]
+            let (exit, result_json) = eval_raw_program(&content, cost_limit, None);
 
-            let contract_id = QualifiedContractIdentifier::transient();
-            let mut placeholder_context = ContractContext::new(
-                QualifiedContractIdentifier::transient(),
-                ClarityVersion::Clarity2,
-            );
-
-            let mut ast = friendly_expect(
-                parse(&contract_id, &content, ClarityVersion::Clarity2),
-                "Failed to parse program.",
-            );
-            match run_analysis_free(&contract_id, &mut ast, &mut analysis_marf, true) {
-                Ok(_) => {
-                    let result = vm_env
-                        .get_exec_environment(None, None, &mut placeholder_context)
-                        .eval_raw_with_rules(&content, ASTRules::PrecheckSize);
-                    match result {
-                        Ok(x) => (
-                            0,
-                            Some(json!({
-                                "output": serde_json::to_value(&x).unwrap()
-                            })),
-                        ),
-                        Err(error) => (
-                            1,
-                            Some(json!({
-                                "error": {
-                                    "runtime": serde_json::to_value(&format!("{}", error)).unwrap()
-                                }
-                            })),
-                        ),
-                    }
-                }
-                Err((error, _)) => (
-                    1,
-                    Some(json!({
-                        "error": {
-                            "analysis": serde_json::to_value(&format!("{}", error)).unwrap()
-                        }
-                    })),
-                ),
+            #[cfg(feature = "developer-mode")]
+            if let Some(tracer) = tracer {
+                result_json["trace"] = serde_json::to_value(tracer.entries()).unwrap();
             }
+
+            (exit, Some(result_json))
         }
         "eval" => {
             let mut argv: Vec<String> = args.into_iter().map(|x| x.clone()).collect();
@@ -1905,6 +2057,7 @@ pub fn invoke_command(invoked_by: &str, args: &[String]) -> (i32, Option<serde_j
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::clarity::vm::errors::CheckErrors;
 
     #[test]
     fn test_initial_alloc() {
@@ -1953,6 +2106,114 @@ mod test {
         assert_eq!(exit, 0);
     }
 
+    #[test]
+    fn test_vm_execute_with_limit() {
+        let program = "(define-data-var x int 0) (var-set x 1) (+ (var-get x) 1)";
+
+        let unlimited = vm_execute(program, ClarityVersion::Clarity2).unwrap();
+        assert_eq!(unlimited, Some(Value::Int(2)));
+
+        let limited = vm_execute_with_limit(
+            program,
+            ClarityVersion::Clarity2,
+            HELIUM_BLOCK_LIMIT_20.clone(),
+        )
+        .unwrap();
+        assert_eq!(limited, unlimited);
+
+        let mut tiny_limit = HELIUM_BLOCK_LIMIT_20.clone();
+        tiny_limit.runtime = 1;
+        let err = vm_execute_with_limit(program, ClarityVersion::Clarity2, tiny_limit).unwrap_err();
+        match err {
+            Error::Unchecked(CheckErrors::CostBalanceExceeded(..)) => {}
+            _ => panic!("Expected CostBalanceExceeded, got {:?}", &err),
+        }
+    }
+
+    #[test]
+    fn test_eval_raw_cost_limit() {
+        let program = "(if is-in-mainnet (+ 1 (* 2 3)) 0)";
+
+        // the program is evaluated on mainnet whether or not it has a cost limit
+        let (exit, result) = eval_raw_program(program, None, None);
+        assert_eq!(exit, 0);
+        assert_eq!(
+            result["output"],
+            serde_json::to_value(&Value::Int(7)).unwrap()
+        );
+
+        let (exit, result) = eval_raw_program(program, Some(BLOCK_LIMIT_MAINNET_205.clone()), None);
+        assert_eq!(exit, 0);
+        assert_eq!(
+            result["output"],
+            serde_json::to_value(&Value::Int(7)).unwrap()
+        );
+
+        let mut tiny_limit = BLOCK_LIMIT_MAINNET_205.clone();
+        tiny_limit.runtime = 1;
+        let (exit, result) = eval_raw_program(program, Some(tiny_limit), None);
+        assert_eq!(exit, 1);
+        assert!(result["error"]["runtime"]
+            .as_str()
+            .unwrap()
+            .contains("CostBalanceExceeded"));
+    }
+
+    #[cfg(not(feature = "developer-mode"))]
+    #[test]
+    fn test_eval_raw_trace_requires_developer_mode() {
+        let invoked = invoke_command("test", &["eval_raw".to_string(), "--trace".to_string()]);
+
+        let exit = invoked.0;
+        let result = invoked.1.unwrap();
+
+        assert_eq!(exit, 1);
+        assert!(result["error"]["usage"]
+            .as_str()
+            .unwrap()
+            .contains("developer-mode"));
+    }
+
+    #[cfg(feature = "developer-mode")]
+    #[test]
+    fn test_vm_execute_traced() {
+        let (result, trace) = vm_execute_traced("(+ 1 (* 2 3))", ClarityVersion::Clarity2);
+        assert_eq!(result.unwrap(), Some(Value::Int(7)));
+
+        // sub-expressions finish first, and the whole expression costs at least as much as
+        // its parts
+        let last = trace.last().unwrap();
+        assert_eq!(last.result, Ok(Value::Int(7)));
+        let mul = trace
+            .iter()
+            .find(|entry| entry.result == Ok(Value::Int(6)))
+            .unwrap();
+        assert!(mul.cost.runtime > 0);
+        assert!(last.cost.runtime > mul.cost.runtime);
+
+        // a failing program still produces a trace, which ends with the error
+        let (result, trace) =
+            vm_execute_traced("(+ 1 (unwrap-panic none))", ClarityVersion::Clarity2);
+        assert!(result.is_err());
+        assert!(trace.last().unwrap().result.is_err());
+        assert!(trace.iter().any(|entry| entry.result == Ok(Value::Int(1))));
+    }
+
+    #[cfg(feature = "developer-mode")]
+    #[test]
+    fn test_eval_raw_trace_costs() {
+        // without a cost limit, a traced program is still charged, so the trace reports real
+        // costs
+        let mut tracer = TraceCollector::new();
+        let (exit, result) = eval_raw_program("(+ 1 (* 2 3))", None, Some(&mut tracer));
+        assert_eq!(exit, 0);
+        assert_eq!(
+            result["output"],
+            serde_json::to_value(Value::Int(7)).unwrap()
+        );
+        assert!(tracer.entries().last().unwrap().cost.runtime > 0);
+    }
+
     #[test]
     fn test_init_mainnet() {
         let db_name = format!("/tmp/db_{}", rand::thread_rng().gen::<i32>());
@@ -0,0 +1,548 @@
+// Copyright (C) 2013-2020 Blockstack PBC, a public benefit corporation
+// Copyright (C) 2020 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Measure the cost of analyzing and deploying a simple use of each native function.  Used by
+//! the `costgen` command, and by the cost unit tests.
+
+use std::collections::BTreeMap;
+
+use clarity::vm::ast::ASTRules;
+use clarity::vm::clarity::TransactionConnection;
+use clarity::vm::costs::ExecutionCost;
+use clarity::vm::database::{BurnStateDB, NULL_HEADER_DB};
+use clarity::vm::functions::NativeFunctions;
+use clarity::vm::types::{PrincipalData, QualifiedContractIdentifier, TupleData};
+use clarity::vm::{ClarityVersion, ContractName};
+use stacks_common::consts::{CHAIN_ID_MAINNET, CHAIN_ID_TESTNET};
+use stacks_common::types::chainstate::{
+    BurnchainHeaderHash, ConsensusHash, SortitionId, StacksBlockId,
+};
+use stacks_common::types::{StacksEpochId, PEER_VERSION_EPOCH_2_0};
+
+use crate::chainstate::stacks::index::ClarityMarfTrieId;
+use crate::clarity_vm::clarity::{ClarityConnection, ClarityInstance};
+use crate::clarity_vm::database::marf::MarfedKV;
+use crate::core::StacksEpoch;
+
+/// Principal which deploys all of the cost-measurement contracts
+const COSTGEN_DEPLOYER: &str = "SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR";
+
+/// Burnchain state which always reports a single, fixed epoch
+struct FixedEpochBurnStateDB {
+    epoch_id: StacksEpochId,
+}
+
+impl BurnStateDB for FixedEpochBurnStateDB {
+    fn get_burn_block_height(&self, _sortition_id: &SortitionId) -> Option<u32> {
+        None
+    }
+
+    fn get_burn_header_hash(
+        &self,
+        _height: u32,
+        _sortition_id: &SortitionId,
+    ) -> Option<BurnchainHeaderHash> {
+        None
+    }
+
+    fn get_stacks_epoch(&self, _height: u32) -> Option<StacksEpoch> {
+        Some(StacksEpoch {
+            epoch_id: self.epoch_id,
+            start_height: 0,
+            end_height: u64::MAX,
+            block_limit: ExecutionCost::max_value(),
+            network_epoch: PEER_VERSION_EPOCH_2_0,
+        })
+    }
+
+    fn get_stacks_epoch_by_epoch_id(&self, _epoch_id: &StacksEpochId) -> Option<StacksEpoch> {
+        self.get_stacks_epoch(0)
+    }
+
+    fn get_v1_unlock_height(&self) -> u32 {
+        u32::MAX
+    }
+
+    fn get_v2_unlock_height(&self) -> u32 {
+        u32::MAX
+    }
+
+    fn get_v3_unlock_height(&self) -> u32 {
+        u32::MAX
+    }
+
+    fn get_pox_3_activation_height(&self) -> u32 {
+        u32::MAX
+    }
+
+    fn get_pox_4_activation_height(&self) -> u32 {
+        u32::MAX
+    }
+
+    fn get_pox_prepare_length(&self) -> u32 {
+        1
+    }
+
+    fn get_pox_reward_cycle_length(&self) -> u32 {
+        1
+    }
+
+    fn get_pox_rejection_fraction(&self) -> u64 {
+        1
+    }
+
+    fn get_burn_start_height(&self) -> u32 {
+        0
+    }
+
+    fn get_sortition_id_from_consensus_hash(
+        &self,
+        _consensus_hash: &ConsensusHash,
+    ) -> Option<SortitionId> {
+        None
+    }
+
+    fn get_ast_rules(&self, _height: u32) -> ASTRules {
+        ASTRules::PrecheckSize
+    }
+
+    fn get_pox_payout_addrs(
+        &self,
+        _height: u32,
+        _sortition_id: &SortitionId,
+    ) -> Option<(Vec<TupleData>, u128)> {
+        None
+    }
+}
+
+/// A short Clarity expression which exercises the given native function
+pub fn get_simple_test(function: &NativeFunctions) -> &'static str {
+    use clarity::vm::functions::NativeFunctions::*;
+    match function {
+        Add => "(+ 1 1)",
+        ToUInt => "(to-uint 1)",
+        ToInt => "(to-int u1)",
+        Subtract => "(- 1 1)",
+        Multiply => "(* 1 1)",
+        Divide => "(/ 1 1)",
+        CmpGeq => "(>= 2 1)",
+        CmpLeq => "(<= 2 1)",
+        CmpLess => "(< 2 1)",
+        CmpGreater => "(> 2 1)",
+        Modulo => "(mod 2 1)",
+        Power => "(pow 2 3)",
+        Sqrti => "(sqrti 81)",
+        Log2 => "(log2 8)",
+        BitwiseXor => "(xor 1 2)",
+        And => "(and true false)",
+        Or => "(or true false)",
+        Not => "(not true)",
+        Equals => "(is-eq 1 2)",
+        If => "(if true (+ 1 2) 2)",
+        Let => "(let ((x 1)) x)",
+        FetchVar => "(var-get var-foo)",
+        SetVar => "(var-set var-foo 1)",
+        Map => "(map not list-foo)",
+        Filter => "(filter not list-foo)",
+        BuffToIntLe => "(buff-to-int-le 0x00000000000000000000000000000001)",
+        BuffToUIntLe => "(buff-to-uint-le 0x00000000000000000000000000000001)",
+        BuffToIntBe => "(buff-to-int-be 0x00000000000000000000000000000001)",
+        BuffToUIntBe => "(buff-to-uint-be 0x00000000000000000000000000000001)",
+        IsStandard => "(is-standard 'STB44HYPYAT2BB2QE513NSP81HTMYWBJP02HPGK6)",
+        PrincipalDestruct => "(principal-destruct? 'STB44HYPYAT2BB2QE513NSP81HTMYWBJP02HPGK6)",
+        PrincipalConstruct => "(principal-construct? 0x1a 0x164247d6f2b425ac5771423ae6c80c754f7172b0)",
+        StringToInt => r#"(string-to-int? "-1")"#,
+        StringToUInt => r#"(string-to-uint? "1")"#,
+        IntToAscii => r#"(int-to-ascii 1)"#,
+        IntToUtf8 => r#"(int-to-utf8 1)"#,
+        Fold => "(fold + list-bar 0)",
+        Append => "(append list-bar 1)",
+        Concat => "(concat list-bar list-bar)",
+        AsMaxLen => "(as-max-len? list-bar u3)",
+        Len => "(len list-bar)",
+        ElementAt => "(element-at list-bar u2)",
+        ElementAtAlias => "(element-at? list-bar u2)",
+        IndexOf => "(index-of list-bar 1)",
+        IndexOfAlias => "(index-of? list-bar 1)",
+        ListCons => "(list 1 2 3 4)",
+        FetchEntry => "(map-get? map-foo {a: 1})",
+        SetEntry => "(map-set map-foo {a: 1} {b: 2})",
+        InsertEntry => "(map-insert map-foo {a: 2} {b: 2})",
+        DeleteEntry => "(map-delete map-foo {a: 1})",
+        TupleCons => "(tuple (a 1))",
+        TupleGet => "(get a tuple-foo)",
+        TupleMerge => "(merge {a: 1, b: 2} {b: 1})",
+        Begin => "(begin 1)",
+        Hash160 => "(hash160 1)",
+        Sha256 => "(sha256 1)",
+        Sha512 => "(sha512 1)",
+        Sha512Trunc256 => "(sha512/256 1)",
+        Keccak256 => "(keccak256 1)",
+        Secp256k1Recover => "(secp256k1-recover? 0xde5b9eb9e7c5592930eb2e30a01369c36586d872082ed8181ee83d2a0ec20f04 0x8738487ebe69b93d8e51583be8eee50bb4213fc49c767d329632730cc193b873554428fc936ca3569afc15f1c9365f6591d6251a89fee9c9ac661116824d3a1301)",
+        Secp256k1Verify => "(secp256k1-verify 0xde5b9eb9e7c5592930eb2e30a01369c36586d872082ed8181ee83d2a0ec20f04 0x8738487ebe69b93d8e51583be8eee50bb4213fc49c767d329632730cc193b873554428fc936ca3569afc15f1c9365f6591d6251a89fee9c9ac661116824d3a1301 0x03adb8de4bfb65db2cfd6120d55c6526ae9c52e675db7e47308636534ba7786110)",
+        Print => "(print 1)",
+        ContractCall => "(contract-call? .contract-other foo-exec 1)",
+        ContractOf => "(contract-of contract)",
+        PrincipalOf => "(principal-of? 0x03adb8de4bfb65db2cfd6120d55c6526ae9c52e675db7e47308636534ba7786110)",
+        AsContract => "(as-contract 1)",
+        GetBlockInfo => "(get-block-info? time u1)",
+        GetBurnBlockInfo => "(get-block-info? time u1)", // TODO: use get-burn-block-info here once API is settled enough to change the mocked burn state DB in this file
+        ConsOkay => "(ok 1)",
+        ConsError => "(err 1)",
+        ConsSome => "(some 1)",
+        DefaultTo => "(default-to 1 none)",
+        Asserts => "(asserts! true (err 1))",
+        UnwrapRet => "(unwrap! (ok 1) (err 1))",
+        UnwrapErrRet => "(unwrap-err! (err 1) (ok 1))",
+        Unwrap => "(unwrap-panic (ok 1))",
+        UnwrapErr => "(unwrap-err-panic (err 1))",
+        Match => "(match (some 1) x (+ x 1) 1)",
+        TryRet => "(try! (if true (ok 1) (err 1)))",
+        IsOkay => "(is-ok (ok 1))",
+        IsNone => "(is-none none)",
+        IsErr => "(is-err (err 1))",
+        IsSome => "(is-some (some 1))",
+        MintAsset => "(ft-mint? ft-foo u1 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
+        MintToken => "(nft-mint? nft-foo 1 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
+        GetTokenBalance => "(ft-get-balance ft-foo 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
+        GetAssetOwner => "(nft-get-owner? nft-foo 1)",
+        TransferToken => "(ft-transfer? ft-foo u1 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
+        TransferAsset => "(nft-transfer? nft-foo 1 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
+        BurnToken => "(ft-burn? ft-foo u1 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
+        BurnAsset => "(nft-burn? nft-foo 1 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
+        GetTokenSupply => "(ft-get-supply ft-foo)",
+        AtBlock => "(at-block 0x55c9861be5cff984a20ce6d99d4aa65941412889bdc665094136429b84f8c2ee 1)",   // first stacksblockid
+        GetStxBalance => "(stx-get-balance 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
+        StxTransfer => r#"(stx-transfer? u1 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)"#,
+        StxTransferMemo => r#"(stx-transfer-memo? u1 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR 0x89995432)"#,
+        StxBurn => "(stx-burn? u1 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
+        StxGetAccount => "(stx-account 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
+        BitwiseAnd => "(bit-and 2 3)",
+        BitwiseOr => "(bit-or 2 3)",
+        BitwiseNot => "(bit-not 3)",
+        BitwiseLShift => "(bit-shift-left 2 u1)",
+        BitwiseRShift => "(bit-shift-right 2 u1)",
+        BitwiseXor2 => "(bit-xor 1 2)",
+        Slice => "(slice? str-foo u1 u1)",
+        ToConsensusBuff => "(to-consensus-buff? u1)",
+        FromConsensusBuff => "(from-consensus-buff? bool 0x03)",
+        ReplaceAt => "(replace-at? list-bar u0 5)",
+    }
+}
+
+/// Instantiate a Clarity VM in the given epoch, with the contracts that the simple tests refer to
+pub fn setup_tracked_cost_test(
+    use_mainnet: bool,
+    epoch: StacksEpochId,
+    version: ClarityVersion,
+) -> ClarityInstance {
+    let marf = MarfedKV::temporary();
+    let chain_id = if use_mainnet {
+        CHAIN_ID_MAINNET
+    } else {
+        CHAIN_ID_TESTNET
+    };
+    let mut clarity_instance = ClarityInstance::new(use_mainnet, chain_id, marf);
+
+    let p1_principal = match PrincipalData::parse(COSTGEN_DEPLOYER) {
+        Ok(PrincipalData::Standard(data)) => data,
+        _ => panic!("BUG: failed to parse costgen deployer"),
+    };
+
+    let contract_trait = "(define-trait trait-1 (
+                            (foo-exec (int) (response int int))
+                          ))";
+    let contract_other = "(impl-trait .contract-trait.trait-1)
+                          (define-map map-foo { a: int } { b: int })
+                          (define-public (foo-exec (a int)) (ok 1))";
+
+    let other_contract_id =
+        QualifiedContractIdentifier::new(p1_principal.clone(), "contract-other".into());
+    let trait_contract_id =
+        QualifiedContractIdentifier::new(p1_principal.clone(), "contract-trait".into());
+
+    let burn_state_db = FixedEpochBurnStateDB { epoch_id: epoch };
+    clarity_instance
+        .begin_test_genesis_block(
+            &StacksBlockId::sentinel(),
+            &StacksBlockId([0 as u8; 32]),
+            &NULL_HEADER_DB,
+            &burn_state_db,
+        )
+        .commit_to_block(&StacksBlockId([0 as u8; 32]));
+
+    {
+        let mut conn = clarity_instance.begin_block(
+            &StacksBlockId([0 as u8; 32]),
+            &StacksBlockId([1 as u8; 32]),
+            &NULL_HEADER_DB,
+            &burn_state_db,
+        );
+
+        if epoch > StacksEpochId::Epoch20 {
+            conn.initialize_epoch_2_05().unwrap();
+        }
+        if epoch > StacksEpochId::Epoch2_05 {
+            conn.initialize_epoch_2_1().unwrap();
+        }
+        if epoch > StacksEpochId::Epoch21 {
+            conn.initialize_epoch_2_2().unwrap();
+        }
+        if epoch > StacksEpochId::Epoch22 {
+            conn.initialize_epoch_2_3().unwrap();
+        }
+        if epoch > StacksEpochId::Epoch23 {
+            conn.initialize_epoch_2_4().unwrap();
+        }
+        if epoch > StacksEpochId::Epoch24 {
+            conn.initialize_epoch_2_5().unwrap();
+        }
+        if epoch > StacksEpochId::Epoch25 {
+            conn.initialize_epoch_3_0().unwrap();
+        }
+
+        conn.commit_to_block(&StacksBlockId([1 as u8; 32]));
+    }
+
+    {
+        let mut conn = clarity_instance.begin_block(
+            &StacksBlockId([1 as u8; 32]),
+            &StacksBlockId([2 as u8; 32]),
+            &NULL_HEADER_DB,
+            &burn_state_db,
+        );
+
+        assert_eq!(
+            conn.with_clarity_db_readonly(|db| db.get_clarity_epoch_version().unwrap()),
+            epoch
+        );
+
+        conn.as_transaction(|conn| {
+            let (ct_ast, ct_analysis) = conn
+                .analyze_smart_contract(
+                    &trait_contract_id,
+                    version,
+                    contract_trait,
+                    ASTRules::PrecheckSize,
+                )
+                .unwrap();
+            conn.initialize_smart_contract(
+                &trait_contract_id,
+                version,
+                &ct_ast,
+                contract_trait,
+                None,
+                |_, _| false,
+            )
+            .unwrap();
+            conn.save_analysis(&trait_contract_id, &ct_analysis)
+                .unwrap();
+        });
+
+        conn.commit_to_block(&StacksBlockId([2 as u8; 32]));
+    }
+
+    {
+        let mut conn = clarity_instance.begin_block(
+            &StacksBlockId([2 as u8; 32]),
+            &StacksBlockId([3 as u8; 32]),
+            &NULL_HEADER_DB,
+            &burn_state_db,
+        );
+
+        conn.as_transaction(|conn| {
+            let (ct_ast, ct_analysis) = conn
+                .analyze_smart_contract(
+                    &other_contract_id,
+                    version,
+                    contract_other,
+                    ASTRules::PrecheckSize,
+                )
+                .unwrap();
+            conn.initialize_smart_contract(
+                &other_contract_id,
+                version,
+                &ct_ast,
+                contract_other,
+                None,
+                |_, _| false,
+            )
+            .unwrap();
+            conn.save_analysis(&other_contract_id, &ct_analysis)
+                .unwrap();
+        });
+
+        conn.commit_to_block(&StacksBlockId([3 as u8; 32]));
+    }
+
+    clarity_instance
+}
+
+/// Analyze and deploy a contract which uses `prog`, and return the total cost of its block
+pub fn test_tracked_costs(
+    prog: &str,
+    epoch: StacksEpochId,
+    version: ClarityVersion,
+    prog_id: usize,
+    clarity_instance: &mut ClarityInstance,
+) -> ExecutionCost {
+    let contract_self = format!(
+        "(define-map map-foo {{ a: int }} {{ b: int }})
+        (define-non-fungible-token nft-foo int)
+        (define-fungible-token ft-foo)
+        (define-data-var var-foo int 0)
+        (define-constant tuple-foo (tuple (a 1)))
+        (define-constant list-foo (list true))
+        (define-constant list-bar (list 1))
+        (define-constant str-foo \"foobar\")
+        (use-trait trait-1 .contract-trait.trait-1)
+        (define-public (execute (contract <trait-1>)) (ok {}))",
+        prog
+    );
+    tracked_contract_cost(&contract_self, epoch, version, prog_id, clarity_instance)
+}
+
+/// The block in which `tracked_contract_cost` deploys program `prog_id`.  It is distinct for
+/// every `prog_id`, and from the blocks built by `setup_tracked_cost_test`.
+fn tracked_block_id(prog_id: usize) -> StacksBlockId {
+    let mut bytes = [4u8; 32];
+    bytes[24..].copy_from_slice(&(prog_id as u64).to_be_bytes());
+    StacksBlockId(bytes)
+}
+
+/// Analyze and deploy `contract_src` in its own block, and return the total cost of that block
+pub fn tracked_contract_cost(
+    contract_src: &str,
+    epoch: StacksEpochId,
+    version: ClarityVersion,
+    prog_id: usize,
+    clarity_instance: &mut ClarityInstance,
+) -> ExecutionCost {
+    let p1_principal = match PrincipalData::parse(COSTGEN_DEPLOYER) {
+        Ok(PrincipalData::Standard(data)) => data,
+        _ => panic!("BUG: failed to parse costgen deployer"),
+    };
+
+    let self_contract_id = QualifiedContractIdentifier::new(
+        p1_principal.clone(),
+        ContractName::try_from(format!("self-{}", prog_id)).unwrap(),
+    );
+
+    let burn_state_db = FixedEpochBurnStateDB { epoch_id: epoch };
+    let block_id = tracked_block_id(prog_id);
+
+    {
+        let mut conn = clarity_instance.begin_block(
+            &StacksBlockId([3 as u8; 32]),
+            &block_id,
+            &NULL_HEADER_DB,
+            &burn_state_db,
+        );
+
+        conn.as_transaction(|conn| {
+            let (ct_ast, ct_analysis) = conn
+                .analyze_smart_contract(
+                    &self_contract_id,
+                    version,
+                    contract_src,
+                    ASTRules::PrecheckSize,
+                )
+                .unwrap();
+            conn.initialize_smart_contract(
+                &self_contract_id,
+                version,
+                &ct_ast,
+                contract_src,
+                None,
+                |_, _| false,
+            )
+            .unwrap();
+            conn.save_analysis(&self_contract_id, &ct_analysis).unwrap();
+        });
+
+        conn.commit_to_block(&block_id)
+            .get_total()
+    }
+}
+
+/// Measure the cost of each native function that is available in `epoch`, using the simple
+/// tests from `get_simple_test()`.  Returns a JSON object mapping each function's name to its
+/// `ExecutionCost`, along with the cost of a contract which uses no natives at all under the key
+/// `"baseline"`.
+pub fn make_json_cost_table(use_mainnet: bool, epoch: StacksEpochId) -> String {
+    let version = ClarityVersion::default_for_epoch(epoch);
+    let mut clarity_instance = setup_tracked_cost_test(use_mainnet, epoch, version);
+
+    let mut costs = BTreeMap::new();
+    costs.insert(
+        "baseline".to_string(),
+        test_tracked_costs("1", epoch, version, 0, &mut clarity_instance),
+    );
+
+    for (ix, f) in NativeFunctions::ALL.iter().enumerate() {
+        if f.get_version() > version {
+            continue;
+        }
+        let cost = test_tracked_costs(
+            get_simple_test(f),
+            epoch,
+            version,
+            ix + 1,
+            &mut clarity_instance,
+        );
+        costs.insert(f.get_name(), cost);
+    }
+
+    serde_json::to_string(&costs).expect("Failed to serialize cost table")
+}
+
+/// Analyze and deploy `prog` as a whole contract in `epoch`, and panic if any dimension of the
+/// resulting cost exceeds the corresponding dimension of `budget`.  The panic message names each
+/// dimension that went over, and by how much.
+pub fn assert_contract_cost_under(
+    prog: &str,
+    budget: &ExecutionCost,
+    use_mainnet: bool,
+    epoch: StacksEpochId,
+) {
+    let version = ClarityVersion::default_for_epoch(epoch);
+    let mut clarity_instance = setup_tracked_cost_test(use_mainnet, epoch, version);
+    let cost = tracked_contract_cost(prog, epoch, version, 0, &mut clarity_instance);
+
+    let dimensions = [
+        ("runtime", cost.runtime, budget.runtime),
+        ("read_count", cost.read_count, budget.read_count),
+        ("read_length", cost.read_length, budget.read_length),
+        ("write_count", cost.write_count, budget.write_count),
+        ("write_length", cost.write_length, budget.write_length),
+    ];
+    let overflows: Vec<_> = dimensions
+        .iter()
+        .filter(|(_, used, limit)| used > limit)
+        .map(|(name, used, limit)| {
+            format!("{}: {} > {} (over by {})", name, used, limit, used - limit)
+        })
+        .collect();
+
+    if !overflows.is_empty() {
+        panic!(
+            "Contract cost {} exceeds budget {}:\n  {}",
+            cost,
+            budget,
+            overflows.join("\n  ")
+        );
+    }
+}
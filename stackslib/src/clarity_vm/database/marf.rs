@@ -73,7 +73,7 @@ impl MarfedKV {
 
         SqliteConnection::initialize_conn(&tx)?;
         tx.commit()
-            .map_err(|err| InterpreterError::SqliteError(IncomparableError { err }))?;
+            .map_err(|err| InterpreterError::SqliteError(IncomparableError::from(err)))?;
 
         Ok(marf)
     }
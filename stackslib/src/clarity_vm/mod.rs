@@ -3,6 +3,9 @@ pub mod clarity;
 
 pub mod special;
 
+/// Measure the costs of the Clarity native functions
+pub mod costgen;
+
 /// Stacks blockchain specific Clarity database implementations and wrappers
 pub mod database;
 
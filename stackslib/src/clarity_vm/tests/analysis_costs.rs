@@ -14,6 +14,8 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+use std::collections::HashMap;
+
 use clarity::vm::ast::ASTRules;
 use clarity::vm::clarity::TransactionConnection;
 use clarity::vm::contexts::{
@@ -33,6 +35,7 @@ use clarity::vm::types::{
     AssetIdentifier, PrincipalData, QualifiedContractIdentifier, ResponseData, Value,
 };
 use clarity::vm::{execute as vm_execute, ClarityVersion, ContractName};
+use rstest::rstest;
 use stacks_common::types::chainstate::{BlockHeaderHash, StacksBlockId};
 use stacks_common::types::StacksEpochId;
 use stacks_common::util::hash::hex_bytes;
@@ -40,281 +43,83 @@ use stacks_common::util::hash::hex_bytes;
 use crate::chainstate::stacks::index::storage::TrieFileStorage;
 use crate::chainstate::stacks::index::ClarityMarfTrieId;
 use crate::clarity_vm::clarity::{ClarityConnection, ClarityInstance};
+use crate::clarity_vm::costgen::{
+    assert_contract_cost_under, get_simple_test, make_json_cost_table, setup_tracked_cost_test,
+    test_tracked_costs,
+};
 use crate::clarity_vm::database::marf::MarfedKV;
-use crate::clarity_vm::tests::costs::get_simple_test;
 use crate::clarity_vm::tests::simple_tests::with_marfed_environment;
 
-fn setup_tracked_cost_test(
-    use_mainnet: bool,
-    epoch: StacksEpochId,
-    version: ClarityVersion,
-) -> ClarityInstance {
-    let marf = MarfedKV::temporary();
-    let chain_id = test_only_mainnet_to_chain_id(use_mainnet);
-    let mut clarity_instance = ClarityInstance::new(use_mainnet, chain_id, marf);
-
-    let p1 = vm_execute("'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR")
-        .unwrap()
-        .unwrap();
-
-    let p1_principal = match p1 {
-        Value::Principal(PrincipalData::Standard(ref data)) => data.clone(),
-        _ => panic!(),
-    };
-
-    let contract_trait = "(define-trait trait-1 (
-                            (foo-exec (int) (response int int))
-                          ))";
-    let contract_other = "(impl-trait .contract-trait.trait-1)
-                          (define-map map-foo { a: int } { b: int })
-                          (define-public (foo-exec (a int)) (ok 1))";
-
-    let other_contract_id =
-        QualifiedContractIdentifier::new(p1_principal.clone(), "contract-other".into());
-    let trait_contract_id =
-        QualifiedContractIdentifier::new(p1_principal.clone(), "contract-trait".into());
-
-    let burn_state_db = UnitTestBurnStateDB {
-        epoch_id: epoch,
-        ast_rules: ASTRules::PrecheckSize,
-    };
-    clarity_instance
-        .begin_test_genesis_block(
-            &StacksBlockId::sentinel(),
-            &StacksBlockId([0 as u8; 32]),
-            &TEST_HEADER_DB,
-            &burn_state_db,
-        )
-        .commit_block();
-
-    {
-        let mut conn = clarity_instance.begin_block(
-            &StacksBlockId([0 as u8; 32]),
-            &StacksBlockId([1 as u8; 32]),
-            &TEST_HEADER_DB,
-            &burn_state_db,
-        );
-
-        if epoch > StacksEpochId::Epoch20 {
-            conn.initialize_epoch_2_05().unwrap();
-        }
-        if epoch > StacksEpochId::Epoch2_05 {
-            conn.initialize_epoch_2_1().unwrap();
+// test that analyzing and deploying each native costs more than the baseline, for every Clarity
+//  version available in the given epoch
+#[rstest]
+#[case::epoch_20_mainnet(true, StacksEpochId::Epoch20)]
+#[case::epoch_20_testnet(false, StacksEpochId::Epoch20)]
+#[case::epoch_205_mainnet(true, StacksEpochId::Epoch2_05)]
+#[case::epoch_205_testnet(false, StacksEpochId::Epoch2_05)]
+#[case::epoch_21_mainnet(true, StacksEpochId::Epoch21)]
+#[case::epoch_21_testnet(false, StacksEpochId::Epoch21)]
+fn test_all_for_epoch(#[case] use_mainnet: bool, #[case] epoch: StacksEpochId) {
+    for version in [ClarityVersion::Clarity1, ClarityVersion::Clarity2] {
+        if version > ClarityVersion::default_for_epoch(epoch) {
+            continue;
         }
 
-        conn.commit_block();
-    }
-
-    {
-        let mut conn = clarity_instance.begin_block(
-            &StacksBlockId([1 as u8; 32]),
-            &StacksBlockId([2 as u8; 32]),
-            &TEST_HEADER_DB,
-            &burn_state_db,
-        );
-
-        assert_eq!(
-            conn.with_clarity_db_readonly(|db| db.get_clarity_epoch_version().unwrap()),
-            epoch
-        );
+        let mut instance = setup_tracked_cost_test(use_mainnet, epoch, version);
+        let baseline = test_tracked_costs("1", epoch, version, 0, &mut instance);
 
-        conn.as_transaction(|conn| {
-            let (ct_ast, ct_analysis) = conn
-                .analyze_smart_contract(
-                    &trait_contract_id,
-                    version,
-                    contract_trait,
-                    ASTRules::PrecheckSize,
-                )
-                .unwrap();
-            conn.initialize_smart_contract(
-                &trait_contract_id,
-                version,
-                &ct_ast,
-                contract_trait,
-                None,
-                |_, _| false,
-            )
-            .unwrap();
-            conn.save_analysis(&trait_contract_id, &ct_analysis)
-                .unwrap();
-        });
+        for (ix, f) in NativeFunctions::ALL.iter().enumerate() {
+            if version < f.get_version() {
+                continue;
+            }
 
-        conn.commit_block();
-    }
-
-    {
-        let mut conn = clarity_instance.begin_block(
-            &StacksBlockId([2 as u8; 32]),
-            &StacksBlockId([3 as u8; 32]),
-            &TEST_HEADER_DB,
-            &burn_state_db,
-        );
-
-        conn.as_transaction(|conn| {
-            let (ct_ast, ct_analysis) = conn
-                .analyze_smart_contract(
-                    &other_contract_id,
-                    version,
-                    contract_other,
-                    ASTRules::PrecheckSize,
-                )
-                .unwrap();
-            conn.initialize_smart_contract(
-                &other_contract_id,
-                version,
-                &ct_ast,
-                contract_other,
-                None,
-                |_, _| false,
-            )
-            .unwrap();
-            conn.save_analysis(&other_contract_id, &ct_analysis)
-                .unwrap();
-        });
-
-        conn.commit_block();
-    }
-
-    clarity_instance
-}
-
-fn test_tracked_costs(
-    prog: &str,
-    epoch: StacksEpochId,
-    version: ClarityVersion,
-    prog_id: usize,
-    clarity_instance: &mut ClarityInstance,
-) -> ExecutionCost {
-    let contract_self = format!(
-        "(define-map map-foo {{ a: int }} {{ b: int }})
-        (define-non-fungible-token nft-foo int)
-        (define-fungible-token ft-foo)
-        (define-data-var var-foo int 0)
-        (define-constant tuple-foo (tuple (a 1)))
-        (define-constant list-foo (list true))
-        (define-constant list-bar (list 1))
-        (define-constant str-foo \"foobar\")
-        (use-trait trait-1 .contract-trait.trait-1)
-        (define-public (execute (contract <trait-1>)) (ok {}))",
-        prog
-    );
-
-    let p1 = vm_execute("'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR")
-        .unwrap()
-        .unwrap();
-
-    let p1_principal = match p1 {
-        Value::Principal(PrincipalData::Standard(ref data)) => data.clone(),
-        _ => panic!(),
-    };
-
-    let self_contract_id = QualifiedContractIdentifier::new(
-        p1_principal.clone(),
-        ContractName::try_from(format!("self-{}", prog_id)).unwrap(),
-    );
-
-    let burn_state_db = UnitTestBurnStateDB {
-        epoch_id: epoch,
-        ast_rules: ASTRules::PrecheckSize,
-    };
-
-    {
-        let mut conn = clarity_instance.begin_block(
-            &StacksBlockId([3 as u8; 32]),
-            &StacksBlockId([4 + prog_id as u8; 32]),
-            &TEST_HEADER_DB,
-            &burn_state_db,
-        );
-
-        conn.as_transaction(|conn| {
-            let (ct_ast, ct_analysis) = conn
-                .analyze_smart_contract(
-                    &self_contract_id,
-                    version,
-                    &contract_self,
-                    ASTRules::PrecheckSize,
-                )
-                .unwrap();
-            conn.initialize_smart_contract(
-                &self_contract_id,
-                version,
-                &ct_ast,
-                &contract_self,
-                None,
-                |_, _| false,
-            )
-            .unwrap();
-            conn.save_analysis(&self_contract_id, &ct_analysis).unwrap();
-        });
-
-        conn.commit_block().get_total()
+            let test = get_simple_test(f);
+            let cost = test_tracked_costs(test, epoch, version, ix + 1, &mut instance);
+            assert!(cost.exceeds(&baseline));
+        }
     }
 }
 
-fn epoch_21_test_all(use_mainnet: bool, version: ClarityVersion) {
-    let mut instance = setup_tracked_cost_test(use_mainnet, StacksEpochId::Epoch21, version);
-
-    let baseline = test_tracked_costs("1", StacksEpochId::Epoch21, version, 0, &mut instance);
-
-    for (ix, f) in NativeFunctions::ALL.iter().enumerate() {
+// test that the costgen table has an entry for every native in the latest epoch, and that each
+//  one costs more than the baseline
+#[test]
+fn test_json_cost_table() {
+    let epoch = StacksEpochId::latest();
+    let version = ClarityVersion::default_for_epoch(epoch);
+    let table: HashMap<String, ExecutionCost> =
+        serde_json::from_str(&make_json_cost_table(true, epoch)).unwrap();
+
+    let baseline = table.get("baseline").unwrap();
+    for f in NativeFunctions::ALL.iter() {
         if version < f.get_version() {
             continue;
         }
-
-        let test = get_simple_test(f);
-        let cost = test_tracked_costs(test, StacksEpochId::Epoch21, version, ix + 1, &mut instance);
-        assert!(cost.exceeds(&baseline));
+        let cost = table
+            .get(&f.get_name())
+            .unwrap_or_else(|| panic!("No cost for {}", f.get_name()));
+        assert!(cost.exceeds(baseline));
     }
 }
 
 #[test]
-fn epoch_21_test_all_mainnet() {
-    epoch_21_test_all(true, ClarityVersion::Clarity1);
-    epoch_21_test_all(true, ClarityVersion::Clarity2);
-}
-
-#[test]
-fn epoch_21_test_all_testnet() {
-    epoch_21_test_all(false, ClarityVersion::Clarity1);
-    epoch_21_test_all(false, ClarityVersion::Clarity2);
-}
-
-fn epoch_205_test_all(use_mainnet: bool) {
-    let mut instance = setup_tracked_cost_test(
-        use_mainnet,
-        StacksEpochId::Epoch2_05,
-        ClarityVersion::Clarity1,
-    );
-    let baseline = test_tracked_costs(
-        "1",
-        StacksEpochId::Epoch2_05,
-        ClarityVersion::Clarity1,
-        0,
-        &mut instance,
+fn test_contract_cost_under_budget() {
+    assert_contract_cost_under(
+        "(define-data-var var-foo int 0) (define-public (foo) (ok (var-get var-foo)))",
+        &ExecutionCost::max_value(),
+        false,
+        StacksEpochId::Epoch21,
     );
-
-    for (ix, f) in NativeFunctions::ALL.iter().enumerate() {
-        if f.get_version() == ClarityVersion::Clarity1 {
-            let test = get_simple_test(f);
-            let cost = test_tracked_costs(
-                test,
-                StacksEpochId::Epoch2_05,
-                ClarityVersion::Clarity1,
-                ix + 1,
-                &mut instance,
-            );
-            assert!(cost.exceeds(&baseline));
-        }
-    }
-}
-
-#[test]
-fn epoch_205_test_all_mainnet() {
-    epoch_205_test_all(true);
 }
 
 #[test]
-fn epoch_205_test_all_testnet() {
-    epoch_205_test_all(false);
+#[should_panic(expected = "runtime: ")]
+fn test_contract_cost_over_budget() {
+    let mut budget = ExecutionCost::max_value();
+    budget.runtime = 1;
+    assert_contract_cost_under(
+        "(define-data-var var-foo int 0) (define-public (foo) (ok (var-get var-foo)))",
+        &budget,
+        false,
+        StacksEpochId::Epoch21,
+    );
 }
@@ -27,7 +27,7 @@ use clarity::vm::costs::{ClarityCostFunctionReference, ExecutionCost, LimitedCos
 use clarity::vm::database::{ClarityDatabase, MemoryBackingStore};
 use clarity::vm::errors::{CheckErrors, Error, RuntimeErrorType};
 use clarity::vm::events::StacksTransactionEvent;
-use clarity::vm::functions::NativeFunctions;
+use clarity::vm::functions::{CostCategory, NativeFunctions};
 use clarity::vm::representations::SymbolicExpression;
 use clarity::vm::test_util::{
     execute, execute_on_network, symbols_from_values, TEST_BURN_STATE_DB, TEST_BURN_STATE_DB_21,
@@ -39,6 +39,7 @@ use clarity::vm::types::{
 };
 use clarity::vm::{ClarityVersion, ContractName};
 use lazy_static::lazy_static;
+use rstest::rstest;
 use stacks_common::types::chainstate::{BlockHeaderHash, StacksBlockId};
 use stacks_common::types::StacksEpochId;
 use stacks_common::util::hash::hex_bytes;
@@ -46,6 +47,7 @@ use stacks_common::util::hash::hex_bytes;
 use crate::chainstate::stacks::index::storage::TrieFileStorage;
 use crate::chainstate::stacks::index::ClarityMarfTrieId;
 use crate::clarity_vm::clarity::ClarityInstance;
+use crate::clarity_vm::costgen::get_simple_test;
 use crate::clarity_vm::database::marf::MarfedKV;
 use crate::core::{FIRST_BURNCHAIN_CONSENSUS_HASH, FIRST_STACKS_BLOCK_HASH};
 use crate::util_lib::boot::boot_code_id;
@@ -57,120 +59,6 @@ lazy_static! {
         boot_code_id("cost-voting", false);
 }
 
-pub fn get_simple_test(function: &NativeFunctions) -> &'static str {
-    use clarity::vm::functions::NativeFunctions::*;
-    match function {
-        Add => "(+ 1 1)",
-        ToUInt => "(to-uint 1)",
-        ToInt => "(to-int u1)",
-        Subtract => "(- 1 1)",
-        Multiply => "(* 1 1)",
-        Divide => "(/ 1 1)",
-        CmpGeq => "(>= 2 1)",
-        CmpLeq => "(<= 2 1)",
-        CmpLess => "(< 2 1)",
-        CmpGreater => "(> 2 1)",
-        Modulo => "(mod 2 1)",
-        Power => "(pow 2 3)",
-        Sqrti => "(sqrti 81)",
-        Log2 => "(log2 8)",
-        BitwiseXor => "(xor 1 2)",
-        And => "(and true false)",
-        Or => "(or true false)",
-        Not => "(not true)",
-        Equals => "(is-eq 1 2)",
-        If => "(if true (+ 1 2) 2)",
-        Let => "(let ((x 1)) x)",
-        FetchVar => "(var-get var-foo)",
-        SetVar => "(var-set var-foo 1)",
-        Map => "(map not list-foo)",
-        Filter => "(filter not list-foo)",
-        BuffToIntLe => "(buff-to-int-le 0x00000000000000000000000000000001)",
-        BuffToUIntLe => "(buff-to-uint-le 0x00000000000000000000000000000001)",
-        BuffToIntBe => "(buff-to-int-be 0x00000000000000000000000000000001)",
-        BuffToUIntBe => "(buff-to-uint-be 0x00000000000000000000000000000001)",
-        IsStandard => "(is-standard 'STB44HYPYAT2BB2QE513NSP81HTMYWBJP02HPGK6)",
-        PrincipalDestruct => "(principal-destruct? 'STB44HYPYAT2BB2QE513NSP81HTMYWBJP02HPGK6)",
-        PrincipalConstruct => "(principal-construct? 0x1a 0x164247d6f2b425ac5771423ae6c80c754f7172b0)",
-        StringToInt => r#"(string-to-int? "-1")"#,
-        StringToUInt => r#"(string-to-uint? "1")"#,
-        IntToAscii => r#"(int-to-ascii 1)"#,
-        IntToUtf8 => r#"(int-to-utf8 1)"#,
-        Fold => "(fold + list-bar 0)",
-        Append => "(append list-bar 1)",
-        Concat => "(concat list-bar list-bar)",
-        AsMaxLen => "(as-max-len? list-bar u3)",
-        Len => "(len list-bar)",
-        ElementAt => "(element-at list-bar u2)",
-        ElementAtAlias => "(element-at? list-bar u2)",
-        IndexOf => "(index-of list-bar 1)",
-        IndexOfAlias => "(index-of? list-bar 1)",
-        ListCons => "(list 1 2 3 4)",
-        FetchEntry => "(map-get? map-foo {a: 1})",
-        SetEntry => "(map-set map-foo {a: 1} {b: 2})",
-        InsertEntry => "(map-insert map-foo {a: 2} {b: 2})",
-        DeleteEntry => "(map-delete map-foo {a: 1})",
-        TupleCons => "(tuple (a 1))",
-        TupleGet => "(get a tuple-foo)",
-        TupleMerge => "(merge {a: 1, b: 2} {b: 1})",
-        Begin => "(begin 1)",
-        Hash160 => "(hash160 1)",
-        Sha256 => "(sha256 1)",
-        Sha512 => "(sha512 1)",
-        Sha512Trunc256 => "(sha512/256 1)",
-        Keccak256 => "(keccak256 1)",
-        Secp256k1Recover => "(secp256k1-recover? 0xde5b9eb9e7c5592930eb2e30a01369c36586d872082ed8181ee83d2a0ec20f04 0x8738487ebe69b93d8e51583be8eee50bb4213fc49c767d329632730cc193b873554428fc936ca3569afc15f1c9365f6591d6251a89fee9c9ac661116824d3a1301)",
-        Secp256k1Verify => "(secp256k1-verify 0xde5b9eb9e7c5592930eb2e30a01369c36586d872082ed8181ee83d2a0ec20f04 0x8738487ebe69b93d8e51583be8eee50bb4213fc49c767d329632730cc193b873554428fc936ca3569afc15f1c9365f6591d6251a89fee9c9ac661116824d3a1301 0x03adb8de4bfb65db2cfd6120d55c6526ae9c52e675db7e47308636534ba7786110)",
-        Print => "(print 1)",
-        ContractCall => "(contract-call? .contract-other foo-exec 1)",
-        ContractOf => "(contract-of contract)",
-        PrincipalOf => "(principal-of? 0x03adb8de4bfb65db2cfd6120d55c6526ae9c52e675db7e47308636534ba7786110)",
-        AsContract => "(as-contract 1)",
-        GetBlockInfo => "(get-block-info? time u1)",
-        GetBurnBlockInfo => "(get-block-info? time u1)", // TODO: use get-burn-block-info here once API is settled enough to change the mocked burn state DB in this file
-        ConsOkay => "(ok 1)",
-        ConsError => "(err 1)",
-        ConsSome => "(some 1)",
-        DefaultTo => "(default-to 1 none)",
-        Asserts => "(asserts! true (err 1))",
-        UnwrapRet => "(unwrap! (ok 1) (err 1))",
-        UnwrapErrRet => "(unwrap-err! (err 1) (ok 1))",
-        Unwrap => "(unwrap-panic (ok 1))",
-        UnwrapErr => "(unwrap-err-panic (err 1))",
-        Match => "(match (some 1) x (+ x 1) 1)",
-        TryRet => "(try! (if true (ok 1) (err 1)))",
-        IsOkay => "(is-ok (ok 1))",
-        IsNone => "(is-none none)",
-        IsErr => "(is-err (err 1))",
-        IsSome => "(is-some (some 1))",
-        MintAsset => "(ft-mint? ft-foo u1 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
-        MintToken => "(nft-mint? nft-foo 1 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
-        GetTokenBalance => "(ft-get-balance ft-foo 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
-        GetAssetOwner => "(nft-get-owner? nft-foo 1)",
-        TransferToken => "(ft-transfer? ft-foo u1 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
-        TransferAsset => "(nft-transfer? nft-foo 1 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
-        BurnToken => "(ft-burn? ft-foo u1 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
-        BurnAsset => "(nft-burn? nft-foo 1 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
-        GetTokenSupply => "(ft-get-supply ft-foo)",
-        AtBlock => "(at-block 0x55c9861be5cff984a20ce6d99d4aa65941412889bdc665094136429b84f8c2ee 1)",   // first stacksblockid
-        GetStxBalance => "(stx-get-balance 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
-        StxTransfer => r#"(stx-transfer? u1 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)"#,
-        StxTransferMemo => r#"(stx-transfer-memo? u1 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR 0x89995432)"#,
-        StxBurn => "(stx-burn? u1 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
-        StxGetAccount => "(stx-account 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
-        BitwiseAnd => "(bit-and 2 3)",
-        BitwiseOr => "(bit-or 2 3)",
-        BitwiseNot => "(bit-not 3)",
-        BitwiseLShift => "(bit-shift-left 2 u1)",
-        BitwiseRShift => "(bit-shift-right 2 u1)",
-        BitwiseXor2 => "(bit-xor 1 2)",
-        Slice => "(slice? str-foo u1 u1)",
-        ToConsensusBuff => "(to-consensus-buff? u1)",
-        FromConsensusBuff => "(from-consensus-buff? bool 0x03)",
-        ReplaceAt => "(replace-at? list-bar u0 5)",
-    }
-}
-
 fn execute_transaction(
     env: &mut OwnedEnvironment,
     issuer: PrincipalData,
@@ -942,70 +830,113 @@ fn test_program_cost(
 }
 
 // test each individual cost function can be correctly invoked as
-//  Clarity code executes in Epoch 2.00
-fn epoch_20_205_test_all(use_mainnet: bool, epoch: StacksEpochId) {
+//  Clarity code executes in the given epoch, using that epoch's default Clarity version
+#[rstest]
+#[case::epoch_20_mainnet(true, StacksEpochId::Epoch20)]
+#[case::epoch_20_testnet(false, StacksEpochId::Epoch20)]
+#[case::epoch_205_mainnet(true, StacksEpochId::Epoch2_05)]
+#[case::epoch_205_testnet(false, StacksEpochId::Epoch2_05)]
+#[case::epoch_21_mainnet(true, StacksEpochId::Epoch21)]
+#[case::epoch_21_testnet(false, StacksEpochId::Epoch21)]
+fn test_all_for_epoch(#[case] use_mainnet: bool, #[case] epoch: StacksEpochId) {
+    let version = ClarityVersion::default_for_epoch(epoch);
     with_owned_env(epoch, use_mainnet, |mut owned_env| {
-        setup_cost_tracked_test(use_mainnet, ClarityVersion::Clarity1, &mut owned_env);
+        setup_cost_tracked_test(use_mainnet, version, &mut owned_env);
 
-        let baseline = test_program_cost("1", ClarityVersion::Clarity1, &mut owned_env, 0);
+        let baseline = test_program_cost("1", version, &mut owned_env, 0);
 
         for (ix, f) in NativeFunctions::ALL.iter().enumerate() {
-            // Note: The 2.0 and 2.05 test assumes Clarity1.
-            if f.get_version() == ClarityVersion::Clarity1 {
+            if f.get_version() <= version {
                 let test = get_simple_test(f);
-                let cost =
-                    test_program_cost(test, ClarityVersion::Clarity1, &mut owned_env, ix + 1);
+                let cost = test_program_cost(test, version, &mut owned_env, ix + 1);
                 assert!(cost.exceeds(&baseline));
             }
         }
     })
 }
 
-#[test]
-fn epoch_20_test_all_mainnet() {
-    epoch_20_205_test_all(true, StacksEpochId::Epoch20)
-}
+// test that each native that reads or writes the database charges for doing so, beyond whatever
+//  runtime it charges
+fn epoch_21_database_costs(use_mainnet: bool) {
+    with_owned_env(StacksEpochId::Epoch21, use_mainnet, |mut owned_env| {
+        setup_cost_tracked_test(use_mainnet, ClarityVersion::Clarity2, &mut owned_env);
 
-#[test]
-fn epoch_20_test_all_testnet() {
-    epoch_20_205_test_all(false, StacksEpochId::Epoch20)
+        let baseline = test_program_cost("1", ClarityVersion::Clarity2, &mut owned_env, 0);
+
+        for (ix, f) in NativeFunctions::ALL.iter().enumerate() {
+            let category = f.cost_category();
+            if category != CostCategory::DatabaseRead && category != CostCategory::DatabaseWrite {
+                continue;
+            }
+            let test = get_simple_test(f);
+            let cost = test_program_cost(test, ClarityVersion::Clarity2, &mut owned_env, ix + 1);
+            if category == CostCategory::DatabaseWrite {
+                assert!(
+                    cost.write_count > baseline.write_count,
+                    "{} does not charge for its writes",
+                    f.get_name()
+                );
+            } else {
+                assert!(
+                    cost.read_count > baseline.read_count,
+                    "{} does not charge for its reads",
+                    f.get_name()
+                );
+            }
+        }
+    })
 }
 
 #[test]
-fn epoch_205_test_all_mainnet() {
-    epoch_20_205_test_all(true, StacksEpochId::Epoch2_05)
+fn epoch_21_database_costs_mainnet() {
+    epoch_21_database_costs(true)
 }
 
 #[test]
-fn epoch_205_test_all_testnet() {
-    epoch_20_205_test_all(false, StacksEpochId::Epoch2_05)
+fn epoch_21_database_costs_testnet() {
+    epoch_21_database_costs(false)
 }
 
-// test each individual cost function can be correctly invoked as
-//  Clarity code executes in Epoch 2.1
-fn epoch_21_test_all(use_mainnet: bool) {
+// test that the token transfer natives charge for each balance or owner record that they read
+//  and write, and not just for runtime
+fn epoch_21_transfer_costs(use_mainnet: bool) {
     with_owned_env(StacksEpochId::Epoch21, use_mainnet, |mut owned_env| {
         setup_cost_tracked_test(use_mainnet, ClarityVersion::Clarity2, &mut owned_env);
 
         let baseline = test_program_cost("1", ClarityVersion::Clarity2, &mut owned_env, 0);
 
-        for (ix, f) in NativeFunctions::ALL.iter().enumerate() {
-            // Note: Include Clarity2 functions for Epoch21.
-            let test = get_simple_test(f);
-            let cost = test_program_cost(test, ClarityVersion::Clarity2, &mut owned_env, ix + 1);
-            assert!(cost.exceeds(&baseline));
-        }
+        // ft-transfer? reads and writes both the sender's and the recipient's balances
+        let ft_cost = test_program_cost(
+            get_simple_test(&NativeFunctions::TransferToken),
+            ClarityVersion::Clarity2,
+            &mut owned_env,
+            1,
+        );
+        assert!(ft_cost.runtime > baseline.runtime);
+        assert!(ft_cost.read_count >= baseline.read_count + 2);
+        assert!(ft_cost.write_count >= baseline.write_count + 2);
+
+        // nft-transfer? reads and writes the asset's owner
+        let nft_cost = test_program_cost(
+            get_simple_test(&NativeFunctions::TransferAsset),
+            ClarityVersion::Clarity2,
+            &mut owned_env,
+            2,
+        );
+        assert!(nft_cost.runtime > baseline.runtime);
+        assert!(nft_cost.read_count >= baseline.read_count + 1);
+        assert!(nft_cost.write_count >= baseline.write_count + 1);
     })
 }
 
 #[test]
-fn epoch_21_test_all_mainnet() {
-    epoch_21_test_all(true)
+fn epoch_21_transfer_costs_mainnet() {
+    epoch_21_transfer_costs(true)
 }
 
 #[test]
-fn epoch_21_test_all_testnet() {
-    epoch_21_test_all(false)
+fn epoch_21_transfer_costs_testnet() {
+    epoch_21_transfer_costs(false)
 }
 
 fn test_cost_contract_short_circuits(use_mainnet: bool, clarity_version: ClarityVersion) {
@@ -329,6 +329,63 @@ impl MemPoolAdmitter {
             tx_size,
         )
     }
+
+    /// Decode and validate a batch of transactions against the same chain view.
+    /// Each transaction is checked independently, so a failure on one does not abort the batch.
+    /// Returns one result per reader, in order.
+    pub fn will_admit_txs<R: Read>(
+        &mut self,
+        chainstate: &mut StacksChainState,
+        sortdb: &SortitionDB,
+        txs: Vec<R>,
+    ) -> Vec<Result<StacksTransaction, MemPoolRejection>> {
+        let mut results = Vec::with_capacity(txs.len());
+        let mut decoded = vec![];
+        for mut tx_reader in txs.into_iter() {
+            let mut tx_bytes = vec![];
+            let tx_res = tx_reader
+                .read_to_end(&mut tx_bytes)
+                .map_err(|e| MemPoolRejection::DeserializationFailure(codec_error::ReadError(e)))
+                .and_then(|_| {
+                    let mut tx_slice = &tx_bytes[..];
+                    let tx = StacksTransaction::consensus_deserialize(&mut tx_slice)
+                        .map_err(MemPoolRejection::DeserializationFailure)?;
+                    let tx_size = (tx_bytes.len() - tx_slice.len()) as u64;
+                    Ok((tx, tx_size))
+                });
+            match tx_res {
+                Ok(tx_and_size) => {
+                    results.push(None);
+                    decoded.push(tx_and_size);
+                }
+                Err(e) => {
+                    results.push(Some(Err(e)));
+                }
+            }
+        }
+
+        let mut admit_results = chainstate
+            .will_admit_mempool_txs(
+                &sortdb.index_conn(),
+                &self.cur_consensus_hash,
+                &self.cur_block,
+                &decoded,
+            )
+            .into_iter()
+            .zip(decoded.into_iter())
+            .map(|(admit_res, (tx, _))| admit_res.map(|_| tx));
+
+        results
+            .into_iter()
+            .map(|result_opt| {
+                result_opt.unwrap_or_else(|| {
+                    admit_results
+                        .next()
+                        .expect("BUG: fewer admission results than decoded transactions")
+                })
+            })
+            .collect()
+    }
 }
 
 pub enum MemPoolDropReason {
@@ -2009,6 +2066,21 @@ impl MemPoolDB {
         Ok(rows)
     }
 
+    /// Get up to `limit` transactions across all tips, ordered by fee rate (highest first).
+    /// Transactions with the same fee rate are ordered by sender, and then by ascending origin
+    /// nonce, so that a sender's transactions come out in the order they can be mined.
+    /// Transactions without a fee rate estimate are returned last.
+    pub fn get_txs_by_fee_desc(
+        conn: &DBConn,
+        limit: usize,
+    ) -> Result<Vec<StacksTransaction>, db_error> {
+        let sql = "SELECT * FROM mempool ORDER BY fee_rate DESC, origin_address ASC, origin_nonce ASC LIMIT ?1";
+        let limit = i64::try_from(limit).map_err(|_| db_error::Overflow)?;
+        let args: &[&dyn ToSql] = &[&limit];
+        let rows = query_rows::<MemPoolTxInfo, _>(conn, sql, args)?;
+        Ok(rows.into_iter().map(|info| info.tx).collect())
+    }
+
     /// Get a transaction's metadata, given address and nonce, and whether the address is used as a sponsor or an origin.
     /// Faster than getting the MemPoolTxInfo, since no deserialization will be needed.
     /// Used to see if there exists a transaction with this info, so as to implement replace-by-fee
@@ -2276,6 +2348,12 @@ impl MemPoolDB {
             block_hash
         );
 
+        let txid = tx.txid();
+        if do_admission_checks && MemPoolDB::db_has_tx(mempool_tx, &txid)? {
+            // cheap check before doing any validation
+            return Err(MemPoolRejection::AlreadyKnown(txid));
+        }
+
         let block_id = StacksBlockId::new(consensus_hash, block_hash);
         let height = match NakamotoChainState::get_block_header(chainstate.db(), &block_id) {
             Ok(Some(header)) => header.stacks_block_height,
@@ -2297,7 +2375,6 @@ impl MemPoolDB {
             }
         };
 
-        let txid = tx.txid();
         let mut tx_data = vec![];
         tx.consensus_serialize(&mut tx_data)
             .map_err(MemPoolRejection::SerializationFailure)?;
@@ -2595,6 +2672,24 @@ impl MemPoolDB {
         Ok(())
     }
 
+    /// Drop transactions that were accepted into the mempool more than `ttl` seconds before
+    /// `now`, so that stale transactions do not linger indefinitely.  Like `drop_txs()`, this
+    /// does not update the bloom filter.
+    /// Returns the txids of the expired transactions.
+    pub fn expire_old_txs(&mut self, now: u64, ttl: u64) -> Result<Vec<Txid>, db_error> {
+        let cutoff = now.saturating_sub(ttl);
+        let mempool_tx = self.tx_begin()?;
+        let sql = "SELECT txid FROM mempool WHERE accept_time < ?1";
+        let args: &[&dyn ToSql] = &[&u64_to_sql(cutoff)?];
+        let txids: Vec<Txid> = query_rows(&mempool_tx, sql, args)?;
+        MemPoolDB::inner_drop_txs(&mempool_tx, &txids)?;
+        mempool_tx.commit()?;
+        if !txids.is_empty() {
+            debug!("Expired {} mempool transactions", txids.len(); "cutoff" => cutoff);
+        }
+        Ok(txids)
+    }
+
     /// Drop and blacklist transactions, so we don't re-broadcast them or re-fetch them.
     /// Do *NOT* remove them from the bloom filter.  This will cause them to continue to be
     /// reported as present, which is exactly what we want because we don't want these transactions
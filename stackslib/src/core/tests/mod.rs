@@ -2800,3 +2800,161 @@ fn test_filter_txs_by_type() {
         },
     );
 }
+
+#[test]
+fn test_get_txs_by_fee_desc() {
+    let mut chainstate = instantiate_chainstate(false, 0x80000000, function_name!());
+    let chainstate_path = chainstate_path(function_name!());
+    let mut mempool = MemPoolDB::open_test(false, 0x80000000, &chainstate_path).unwrap();
+
+    let addr = StacksAddress {
+        version: 1,
+        bytes: Hash160([0xff; 20]),
+    };
+    let block_height = 10;
+
+    // (sender, nonce, fee rate)
+    let sender_keys = [
+        StacksPrivateKey::new(),
+        StacksPrivateKey::new(),
+        StacksPrivateKey::new(),
+    ];
+    let tx_specs = [
+        (0, 1, Some(200.0)),
+        (2, 0, None),
+        (0, 0, Some(200.0)),
+        (1, 0, Some(300.0)),
+    ];
+
+    let mut mempool_tx = mempool.tx_begin().unwrap();
+    let mut txs = vec![];
+    for (sender, nonce, fee_rate) in tx_specs.iter() {
+        let mut tx = StacksTransaction {
+            version: TransactionVersion::Testnet,
+            chain_id: 0x80000000,
+            auth: TransactionAuth::from_p2pkh(&sender_keys[*sender]).unwrap(),
+            anchor_mode: TransactionAnchorMode::Any,
+            post_condition_mode: TransactionPostConditionMode::Allow,
+            post_conditions: vec![],
+            payload: TransactionPayload::TokenTransfer(
+                addr.to_account_principal(),
+                123,
+                TokenTransferMemo([0u8; 34]),
+            ),
+        };
+        tx.set_tx_fee(1000);
+        tx.set_origin_nonce(*nonce);
+
+        let txid = tx.txid();
+        let origin_addr = tx.origin_address();
+        MemPoolDB::try_add_tx(
+            &mut mempool_tx,
+            &mut chainstate,
+            &ConsensusHash([0x1 + (block_height as u8); 20]),
+            &BlockHeaderHash([0x2 + (block_height as u8); 32]),
+            txid.clone(),
+            tx.serialize_to_vec(),
+            tx.get_tx_fee(),
+            block_height,
+            &origin_addr,
+            *nonce,
+            &origin_addr,
+            *nonce,
+            None,
+        )
+        .unwrap();
+
+        mempool_tx
+            .execute(
+                "UPDATE mempool SET fee_rate = ? WHERE txid = ?",
+                rusqlite::params![fee_rate, &txid],
+            )
+            .unwrap();
+        txs.push(tx);
+    }
+    mempool_tx.commit().unwrap();
+
+    // highest fee rate first, then sender 0's txs in nonce order, then the unestimated tx
+    let expected: Vec<_> = [3, 2, 0, 1].iter().map(|i| txs[*i].txid()).collect();
+    let ordered: Vec<_> = MemPoolDB::get_txs_by_fee_desc(mempool.conn(), 10)
+        .unwrap()
+        .iter()
+        .map(|tx| tx.txid())
+        .collect();
+    assert_eq!(ordered, expected);
+
+    let ordered: Vec<_> = MemPoolDB::get_txs_by_fee_desc(mempool.conn(), 2)
+        .unwrap()
+        .iter()
+        .map(|tx| tx.txid())
+        .collect();
+    assert_eq!(ordered, expected[..2].to_vec());
+}
+
+#[test]
+fn test_expire_old_txs() {
+    let mut chainstate = instantiate_chainstate(false, 0x80000000, function_name!());
+    let chainstate_path = chainstate_path(function_name!());
+    let mut mempool = MemPoolDB::open_test(false, 0x80000000, &chainstate_path).unwrap();
+
+    let addr = StacksAddress {
+        version: 1,
+        bytes: Hash160([0xff; 20]),
+    };
+    let block_height = 10;
+    let ttl = 60;
+
+    let pk = StacksPrivateKey::new();
+    let mut tx = StacksTransaction {
+        version: TransactionVersion::Testnet,
+        chain_id: 0x80000000,
+        auth: TransactionAuth::from_p2pkh(&pk).unwrap(),
+        anchor_mode: TransactionAnchorMode::Any,
+        post_condition_mode: TransactionPostConditionMode::Allow,
+        post_conditions: vec![],
+        payload: TransactionPayload::TokenTransfer(
+            addr.to_account_principal(),
+            123,
+            TokenTransferMemo([0u8; 34]),
+        ),
+    };
+    tx.set_tx_fee(1000);
+    tx.set_origin_nonce(0);
+    let txid = tx.txid();
+    let origin_addr = tx.origin_address();
+
+    let mut mempool_tx = mempool.tx_begin().unwrap();
+    MemPoolDB::try_add_tx(
+        &mut mempool_tx,
+        &mut chainstate,
+        &ConsensusHash([0x1 + (block_height as u8); 20]),
+        &BlockHeaderHash([0x2 + (block_height as u8); 32]),
+        txid.clone(),
+        tx.serialize_to_vec(),
+        tx.get_tx_fee(),
+        block_height,
+        &origin_addr,
+        0,
+        &origin_addr,
+        0,
+        None,
+    )
+    .unwrap();
+    mempool_tx.commit().unwrap();
+
+    let accept_time = MemPoolDB::get_tx(mempool.conn(), &txid)
+        .unwrap()
+        .unwrap()
+        .metadata
+        .accept_time;
+
+    // not old enough yet
+    let expired = mempool.expire_old_txs(accept_time + ttl, ttl).unwrap();
+    assert!(expired.is_empty());
+    assert!(mempool.has_tx(&txid));
+
+    // advance past the TTL
+    let expired = mempool.expire_old_txs(accept_time + ttl + 1, ttl).unwrap();
+    assert_eq!(expired, vec![txid.clone()]);
+    assert!(!mempool.has_tx(&txid));
+}
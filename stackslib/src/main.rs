@@ -66,6 +66,7 @@ use blockstack_lib::clarity_cli::vm_execute;
 use blockstack_lib::core::{MemPoolDB, *};
 use blockstack_lib::cost_estimates::metrics::UnitMetric;
 use blockstack_lib::cost_estimates::UnitEstimator;
+use blockstack_lib::net::codec::consensus_deserialize_exact;
 use blockstack_lib::net::db::LocalPeer;
 use blockstack_lib::net::p2p::PeerNetwork;
 use blockstack_lib::net::relay::Relayer;
@@ -118,12 +119,15 @@ fn main() {
         process::exit(0);
     }
 
-    if argv[1] == "decode-bitcoin-header" {
+    if argv[1] == "decode-bitcoin-header" || argv[1] == "decode-bitcoin-header-by-hash" {
+        let by_hash = argv[1] == "decode-bitcoin-header-by-hash";
+        let usage = if by_hash {
+            "decode-bitcoin-header-by-hash [-t|-r] BLOCK_HASH PATH"
+        } else {
+            "decode-bitcoin-header [-t|-r] BLOCK_HEIGHT PATH"
+        };
         if argv.len() < 4 {
-            eprintln!(
-                "Usage: {} decode-bitcoin-header [-t|-r] BLOCK_HEIGHT PATH",
-                argv[0]
-            );
+            eprintln!("Usage: {} {}", argv[0], usage);
             process::exit(1);
         }
 
@@ -141,10 +145,7 @@ fn main() {
         }
         if regtest && testnet {
             // don't allow both
-            eprintln!(
-                "Usage: {} decode-bitcoin-header [-t|-r] BLOCK_HEIGHT PATH",
-                argv[0]
-            );
+            eprintln!("Usage: {} {}", argv[0], usage);
             process::exit(1);
         }
         if idx > 0 {
@@ -159,15 +160,23 @@ fn main() {
             BitcoinNetworkType::Mainnet
         };
 
-        let height = argv[2].parse::<u64>().expect("Invalid block height");
         let headers_path = &argv[3];
-
-        let spv_client = spv::SpvClient::new(headers_path, 0, Some(height), mode, false, false)
-            .expect("FATAL: could not instantiate SPV client");
-        match spv_client
-            .read_block_header(height)
-            .expect("FATAL: could not read block header database")
-        {
+        let header_opt = if by_hash {
+            let hash = BurnchainHeaderHash::from_hex(&argv[2]).expect("Invalid block hash");
+            let spv_client = spv::SpvClient::new(headers_path, 0, None, mode, false, false)
+                .expect("FATAL: could not instantiate SPV client");
+            spv_client
+                .read_block_header_by_hash(&hash)
+                .expect("FATAL: could not read block header database")
+        } else {
+            let height = argv[2].parse::<u64>().expect("Invalid block height");
+            let spv_client = spv::SpvClient::new(headers_path, 0, Some(height), mode, false, false)
+                .expect("FATAL: could not instantiate SPV client");
+            spv_client
+                .read_block_header(height)
+                .expect("FATAL: could not read block header database")
+        };
+        match header_opt {
             Some(header) => {
                 println!("{:#?}", header);
                 process::exit(0);
@@ -179,6 +188,39 @@ fn main() {
         }
     }
 
+    if argv[1] == "verify-bitcoin-headers" {
+        if argv.len() < 3 {
+            eprintln!("Usage: {} verify-bitcoin-headers [-t|-r] PATH", argv[0]);
+            process::exit(1);
+        }
+
+        let mode = match argv[2].as_str() {
+            "-t" => BitcoinNetworkType::Testnet,
+            "-r" => BitcoinNetworkType::Regtest,
+            _ => BitcoinNetworkType::Mainnet,
+        };
+        let headers_path = argv.last().expect("FATAL: no headers path");
+
+        let spv_client = spv::SpvClient::new(headers_path, 0, None, mode, false, false)
+            .expect("FATAL: could not instantiate SPV client");
+        let num_headers = spv_client
+            .get_headers_height()
+            .expect("FATAL: could not read block header database");
+        match spv_client
+            .find_first_bad_header()
+            .expect("FATAL: could not read block header database")
+        {
+            Some(height) => {
+                eprintln!("Header continuity breaks at height {}", height);
+                process::exit(1);
+            }
+            None => {
+                println!("OK, {} headers verified", num_headers);
+                process::exit(0);
+            }
+        }
+    }
+
     if argv[1] == "decode-tx" {
         if argv.len() < 3 {
             eprintln!("Usage: {} decode-tx TRANSACTION", argv[0]);
@@ -196,7 +238,7 @@ fn main() {
         let mut cursor = io::Cursor::new(&tx_bytes);
         let mut debug_cursor = LogReader::from_reader(&mut cursor);
 
-        let tx = StacksTransaction::consensus_deserialize(&mut debug_cursor)
+        let tx: StacksTransaction = consensus_deserialize_exact(&mut debug_cursor)
             .map_err(|e| {
                 eprintln!("Failed to decode transaction: {:?}", &e);
                 eprintln!("Bytes consumed:");
@@ -215,14 +257,12 @@ fn main() {
     }
 
     if argv[1] == "decode-block" {
-        if argv.len() < 3 {
-            eprintln!("Usage: {} decode-block BLOCK_PATH", argv[0]);
+        let Some((txids_only, block_path)) = parse_decode_block_args(&argv[2..]) else {
+            eprintln!("Usage: {} decode-block [--txids-only] BLOCK_PATH", argv[0]);
             process::exit(1);
-        }
-
-        let block_path = &argv[2];
+        };
         let block_data =
-            fs::read(block_path).unwrap_or_else(|_| panic!("Failed to open {block_path}"));
+            fs::read(&block_path).unwrap_or_else(|_| panic!("Failed to open {block_path}"));
 
         let block = StacksBlock::consensus_deserialize(&mut io::Cursor::new(&block_data))
             .map_err(|_e| {
@@ -231,7 +271,13 @@ fn main() {
             })
             .unwrap();
 
-        println!("{:#?}", &block);
+        if txids_only {
+            for tx in block.txs.iter() {
+                println!("{}", tx.txid());
+            }
+        } else {
+            println!("{:#?}", &block);
+        }
         process::exit(0);
     }
 
@@ -701,23 +747,25 @@ simulating a miner.
         }
 
         let mblock_path = &argv[2];
-        let mblock_data =
-            fs::read(mblock_path).unwrap_or_else(|_| panic!("Failed to open {mblock_path}"));
-
-        let mut cursor = io::Cursor::new(&mblock_data);
-        let mut debug_cursor = LogReader::from_reader(&mut cursor);
-        let mblocks: Vec<StacksMicroblock> = Vec::consensus_deserialize(&mut debug_cursor)
-            .map_err(|e| {
-                eprintln!("Failed to decode microblocks: {:?}", &e);
-                eprintln!("Bytes consumed:");
-                for buf in debug_cursor.log().iter() {
-                    eprintln!("  {}", to_hex(buf));
+        let mblock_file =
+            File::open(mblock_path).unwrap_or_else(|_| panic!("Failed to open {mblock_path}"));
+
+        let mut mblock_stream =
+            StacksMicroblock::consensus_decode_stream(BufReader::new(mblock_file));
+        while let Some(res) = mblock_stream.next() {
+            match res {
+                Ok(mblock) => println!("{:#?}", &mblock),
+                Err(e) => {
+                    eprintln!("Failed to decode microblocks: {:?}", &e);
+                    eprintln!(
+                        "Malformed data begins at byte offset {} (consumed {} bytes)",
+                        mblock_stream.last_offset(),
+                        mblock_stream.bytes_read()
+                    );
+                    process::exit(1);
                 }
-                process::exit(1);
-            })
-            .unwrap();
-
-        println!("{:#?}", &mblocks);
+            }
+        }
         process::exit(0);
     }
 
@@ -799,6 +847,23 @@ simulating a miner.
         return;
     }
 
+    if argv[1] == "parse-clarity" {
+        if argv.len() < 3 {
+            eprintln!("Usage: {} parse-clarity [program-file.clar]", argv[0]);
+            process::exit(1);
+        }
+        let program: String = fs::read_to_string(&argv[2])
+            .unwrap_or_else(|_| panic!("Error reading file: {}", argv[2]));
+        match blockstack_lib::clarity::vm::ast::parse_to_string(&program) {
+            Ok(ast) => print!("{}", ast),
+            Err(error) => {
+                eprintln!("Parse Error: \n{}", error.diagnostic);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
     if argv[1] == "marf-get" {
         let path = &argv[2];
         let tip = BlockHeaderHash::from_hex(&argv[3]).unwrap();
@@ -855,6 +920,32 @@ simulating a miner.
         return;
     }
 
+    if argv[1] == "costgen" {
+        let epoch = match argv.get(2).map(|s| s.as_str()) {
+            None => StacksEpochId::latest(),
+            Some("2.0") => StacksEpochId::Epoch20,
+            Some("2.05") => StacksEpochId::Epoch2_05,
+            Some("2.1") => StacksEpochId::Epoch21,
+            Some("2.2") => StacksEpochId::Epoch22,
+            Some("2.3") => StacksEpochId::Epoch23,
+            Some("2.4") => StacksEpochId::Epoch24,
+            Some("2.5") => StacksEpochId::Epoch25,
+            Some("3.0") => StacksEpochId::Epoch30,
+            Some(_) => {
+                eprintln!(
+                    "Usage: {} costgen [2.0|2.05|2.1|2.2|2.3|2.4|2.5|3.0]",
+                    argv[0]
+                );
+                process::exit(1);
+            }
+        };
+        println!(
+            "{}",
+            blockstack_lib::clarity_vm::costgen::make_json_cost_table(true, epoch)
+        );
+        return;
+    }
+
     if argv[1] == "docgen_boot" {
         println!(
             "{}",
@@ -1322,6 +1413,20 @@ simulating a miner.
     }
 }
 
+/// Parse the arguments to `decode-block`, i.e. `[--txids-only] BLOCK_PATH` with the flag in any
+/// position.  Returns whether only the txids should be printed, and the block path.
+fn parse_decode_block_args(args: &[String]) -> Option<(bool, String)> {
+    let mut args = args.to_vec();
+    let txids_only = if let Some(ix) = args.iter().position(|x| x == "--txids-only") {
+        args.remove(ix);
+        true
+    } else {
+        false
+    };
+    let block_path = args.into_iter().next()?;
+    Some((txids_only, block_path))
+}
+
 fn tip_mine() {
     let argv: Vec<String> = env::args().collect();
     if argv.len() < 6 {
@@ -1731,3 +1836,30 @@ fn replay_block(stacks_path: &str, index_block_hash_hex: &str) {
         }
     };
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn to_string_vec(x: &[&str]) -> Vec<String> {
+        x.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn decode_block_args_txids_only_anywhere() {
+        assert_eq!(
+            parse_decode_block_args(&to_string_vec(&["block.bin"])),
+            Some((false, "block.bin".to_string()))
+        );
+        assert_eq!(
+            parse_decode_block_args(&to_string_vec(&["--txids-only", "block.bin"])),
+            Some((true, "block.bin".to_string()))
+        );
+        assert_eq!(
+            parse_decode_block_args(&to_string_vec(&["block.bin", "--txids-only"])),
+            Some((true, "block.bin".to_string()))
+        );
+        assert_eq!(parse_decode_block_args(&to_string_vec(&["--txids-only"])), None);
+        assert_eq!(parse_decode_block_args(&[]), None);
+    }
+}
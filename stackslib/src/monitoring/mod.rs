@@ -14,6 +14,8 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+#[cfg(feature = "monitoring_prom")]
+use std::collections::HashMap;
 use std::error::Error;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -28,9 +30,13 @@ use stacks_common::util::uint::{Uint256, Uint512};
 
 use crate::burnchains::{BurnchainSigner, Txid};
 use crate::core::MemPoolDB;
+#[cfg(feature = "monitoring_prom")]
+use crate::net::chat::NeighborStats;
 use crate::net::httpcore::{StacksHttpRequest, StacksHttpResponse};
 use crate::net::rpc::ConversationHttp;
 use crate::net::Error as net_error;
+#[cfg(feature = "monitoring_prom")]
+use crate::net::NeighborKey;
 use crate::util_lib::db::{sqlite_open, tx_busy_handler, DBConn, Error as DatabaseError};
 
 #[cfg(feature = "monitoring_prom")]
@@ -352,6 +358,91 @@ pub fn increment_contract_calls_processed() {
     prometheus::CONTRACT_CALLS_PROCESSED_COUNT.inc();
 }
 
+/// Render per-neighbor statistics in the Prometheus text exposition format, with one sample per
+/// neighbor for each metric.  Each sample is labeled with the neighbor's `ip:port`.
+#[cfg(feature = "monitoring_prom")]
+pub fn neighbor_stats_to_prometheus(stats: &HashMap<NeighborKey, NeighborStats>) -> String {
+    let metrics: [(&str, &str, &str, fn(&NeighborStats) -> u64); 10] = [
+        (
+            "stacks_peer_outbound",
+            "gauge",
+            "Whether or not we connected to the peer (1) or it connected to us (0).",
+            |s| u64::from(s.outbound),
+        ),
+        (
+            "stacks_peer_first_contact_time",
+            "gauge",
+            "Time (seconds since the epoch) we first talked to the peer.",
+            |s| s.first_contact_time,
+        ),
+        (
+            "stacks_peer_last_contact_time",
+            "gauge",
+            "Time (seconds since the epoch) we last talked to the peer.",
+            |s| s.last_contact_time,
+        ),
+        (
+            "stacks_peer_last_handshake_time",
+            "gauge",
+            "Time (seconds since the epoch) of the last handshake with the peer.",
+            |s| s.last_handshake_time,
+        ),
+        (
+            "stacks_peer_last_send_time",
+            "gauge",
+            "Time (seconds since the epoch) we last sent the peer a message.",
+            |s| s.last_send_time,
+        ),
+        (
+            "stacks_peer_last_recv_time",
+            "gauge",
+            "Time (seconds since the epoch) we last received a message from the peer.",
+            |s| s.last_recv_time,
+        ),
+        (
+            "stacks_peer_bytes_sent_total",
+            "counter",
+            "Total number of bytes sent to the peer.",
+            |s| s.bytes_tx,
+        ),
+        (
+            "stacks_peer_bytes_received_total",
+            "counter",
+            "Total number of bytes received from the peer.",
+            |s| s.bytes_rx,
+        ),
+        (
+            "stacks_peer_messages_sent_total",
+            "counter",
+            "Total number of messages sent to the peer.",
+            |s| s.msgs_tx,
+        ),
+        (
+            "stacks_peer_messages_received_total",
+            "counter",
+            "Total number of messages received from the peer.",
+            |s| s.msgs_rx,
+        ),
+    ];
+
+    // sort by address so the output is stable across scrapes
+    let mut peers: Vec<_> = stats
+        .iter()
+        .map(|(nk, stats)| (nk.to_socketaddr().to_string(), stats))
+        .collect();
+    peers.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let mut out = String::new();
+    for (name, kind, help, value) in metrics.iter() {
+        out.push_str(&format!("# HELP {} {}\n", name, help));
+        out.push_str(&format!("# TYPE {} {}\n", name, kind));
+        for (peer, stats) in peers.iter() {
+            out.push_str(&format!("{}{{peer=\"{}\"}} {}\n", name, peer, value(stats)));
+        }
+    }
+    out
+}
+
 /// Given a value (type uint256), return value/uint256::max() as an f64 value.
 /// The precision of the percentage is determined by the input `precision_points`, which is capped
 /// at a max of 15.
@@ -409,6 +500,43 @@ pub fn test_convert_uint256_to_f64() {
     assert_approx_eq!(convert_uint256_to_f64_percentage(original, 1000), 12.234567);
 }
 
+#[cfg(feature = "monitoring_prom")]
+#[test]
+pub fn test_neighbor_stats_to_prometheus() {
+    use stacks_common::types::net::PeerAddress;
+
+    let nk_1 = NeighborKey {
+        peer_version: 0x18000000,
+        network_id: 0x80000000,
+        addrbytes: PeerAddress::from_ipv4(127, 0, 0, 1),
+        port: 20444,
+    };
+    let nk_2 = NeighborKey {
+        port: 20445,
+        ..nk_1.clone()
+    };
+
+    let mut stats_1 = NeighborStats::new(true);
+    stats_1.last_contact_time = 1700000000;
+    stats_1.bytes_rx = 123;
+    let mut stats_2 = NeighborStats::new(false);
+    stats_2.last_contact_time = 1700000001;
+
+    let mut stats = HashMap::new();
+    stats.insert(nk_2, stats_2);
+    stats.insert(nk_1, stats_1);
+
+    let text = neighbor_stats_to_prometheus(&stats);
+    assert!(text.contains("# TYPE stacks_peer_last_contact_time gauge\n"));
+    assert!(text.contains(
+        "stacks_peer_last_contact_time{peer=\"127.0.0.1:20444\"} 1700000000\n\
+         stacks_peer_last_contact_time{peer=\"127.0.0.1:20445\"} 1700000001\n"
+    ));
+    assert!(text.contains("# TYPE stacks_peer_bytes_received_total counter\n"));
+    assert!(text.contains("stacks_peer_bytes_received_total{peer=\"127.0.0.1:20444\"} 123\n"));
+    assert!(text.contains("stacks_peer_outbound{peer=\"127.0.0.1:20445\"} 0\n"));
+}
+
 #[allow(unused_variables)]
 pub fn update_computed_relative_miner_score(value: Uint256) {
     #[cfg(feature = "monitoring_prom")]
@@ -60,6 +60,42 @@ pub struct AccountEntryResponse {
     pub nonce_proof: Option<String>,
 }
 
+impl AccountEntryResponse {
+    /// Build the response for an account with `balance` unlocked uSTX, no locked uSTX, and the
+    /// given `nonce`. Amounts are encoded as `0x`-prefixed, 16-byte big-endian hex. If given,
+    /// `proofs` holds the MARF proofs of the balance and nonce entries, where an empty proof
+    /// means the entry does not exist.
+    pub fn from_db(balance: u128, nonce: u64, proofs: Option<(Vec<u8>, Vec<u8>)>) -> Self {
+        let (balance_proof, nonce_proof) = match proofs {
+            Some((balance_proof, nonce_proof)) => (
+                Some(Self::format_proof(&balance_proof)),
+                Some(Self::format_proof(&nonce_proof)),
+            ),
+            None => (None, None),
+        };
+        Self {
+            balance: Self::format_amount(balance),
+            locked: Self::format_amount(0),
+            unlock_height: 0,
+            nonce,
+            balance_proof,
+            nonce_proof,
+        }
+    }
+
+    fn format_amount(amount: u128) -> String {
+        format!("0x{}", to_hex(&amount.to_be_bytes()))
+    }
+
+    fn format_proof(proof: &[u8]) -> String {
+        if proof.is_empty() {
+            "".into()
+        } else {
+            format!("0x{}", to_hex(proof))
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct RPCGetAccountRequestHandler {
     pub account: Option<PrincipalData>,
@@ -159,8 +195,8 @@ impl RPCRequestHandler for RPCGetAccountRequestHandler {
                                 .get_data_with_proof::<STXBalance>(&key)
                                 .ok()
                                 .flatten()
-                                .map(|(a, b)| (a, Some(format!("0x{}", to_hex(&b)))))
-                                .unwrap_or_else(|| (STXBalance::zero(), Some("".into())))
+                                .map(|(a, b)| (a, Some(b)))
+                                .unwrap_or_else(|| (STXBalance::zero(), Some(vec![])))
                         } else {
                             clarity_db
                                 .get_data::<STXBalance>(&key)
@@ -176,8 +212,8 @@ impl RPCRequestHandler for RPCGetAccountRequestHandler {
                                 .get_data_with_proof(&key)
                                 .ok()
                                 .flatten()
-                                .map(|(a, b)| (a, Some(format!("0x{}", to_hex(&b)))))
-                                .unwrap_or_else(|| (0, Some("".into())))
+                                .map(|(a, b)| (a, Some(b)))
+                                .unwrap_or_else(|| (0, Some(vec![])))
                         } else {
                             clarity_db
                                 .get_data(&key)
@@ -203,16 +239,14 @@ impl RPCRequestHandler for RPCGetAccountRequestHandler {
                             v3_unlock_height,
                         );
 
-                        let balance = format!("0x{}", to_hex(&unlocked.to_be_bytes()));
-                        let locked = format!("0x{}", to_hex(&locked.to_be_bytes()));
-
                         Some(AccountEntryResponse {
-                            balance,
-                            locked,
+                            locked: AccountEntryResponse::format_amount(locked),
                             unlock_height,
-                            nonce,
-                            balance_proof,
-                            nonce_proof,
+                            ..AccountEntryResponse::from_db(
+                                unlocked,
+                                nonce,
+                                balance_proof.zip(nonce_proof),
+                            )
                         })
                     })
                 })
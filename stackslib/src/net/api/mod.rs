@@ -60,6 +60,7 @@ pub mod gettransaction_unconfirmed;
 pub mod liststackerdbreplicas;
 pub mod postblock;
 pub mod postblock_proposal;
+pub mod postcontractanalysis;
 pub mod postfeerate;
 pub mod postmempoolquery;
 pub mod postmicroblock;
@@ -121,6 +122,14 @@ impl StacksHttp {
         self.register_rpc_endpoint(postblock_proposal::RPCBlockProposalRequestHandler::new(
             self.block_proposal_token.clone(),
         ));
+        if self.contract_analysis_endpoint {
+            self.register_rpc_endpoint(
+                postcontractanalysis::RPCPostContractAnalysisRequestHandler::new(
+                    self.maximum_contract_analysis_size,
+                    self.read_only_call_limit.clone(),
+                ),
+            );
+        }
         self.register_rpc_endpoint(postfeerate::RPCPostFeeRateRequestHandler::new());
         self.register_rpc_endpoint(postmempoolquery::RPCMempoolQueryRequestHandler::new());
         self.register_rpc_endpoint(postmicroblock::RPCPostMicroblockRequestHandler::new());
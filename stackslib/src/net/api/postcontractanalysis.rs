@@ -0,0 +1,322 @@
+// Copyright (C) 2013-2020 Blockstack PBC, a public benefit corporation
+// Copyright (C) 2020-2024 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use clarity::vm::analysis::contract_interface_builder::{
+    build_contract_interface, ContractInterface,
+};
+use clarity::vm::analysis::run_analysis;
+use clarity::vm::ast::{build_ast_with_rules, ASTRules};
+use clarity::vm::clarity::ClarityConnection;
+use clarity::vm::costs::{ExecutionCost, LimitedCostTracker};
+use clarity::vm::database::MemoryBackingStore;
+use clarity::vm::diagnostic::Diagnostic;
+use clarity::vm::types::QualifiedContractIdentifier;
+use clarity::vm::ClarityVersion;
+use regex::{Captures, Regex};
+use stacks_common::types::net::PeerHost;
+use stacks_common::types::StacksEpochId;
+
+use crate::net::http::{
+    parse_json, Error, HttpBadRequest, HttpContentType, HttpNotFound, HttpRequest,
+    HttpRequestContents, HttpRequestPreamble, HttpResponse, HttpResponseContents,
+    HttpResponsePayload, HttpResponsePreamble, HttpServerError,
+};
+use crate::net::httpcore::{
+    HttpPreambleExtensions, RPCRequestHandler, StacksHttpRequest, StacksHttpResponse,
+};
+use crate::net::{Error as NetError, StacksNodeState};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContractAnalysisRequestBody {
+    pub source: String,
+    /// Epoch to analyze the contract in. Defaults to the epoch of the Stacks chain tip.
+    #[serde(default)]
+    pub epoch: Option<StacksEpochId>,
+}
+
+/// Which stage of contract processing rejected the posted source
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ContractAnalysisStage {
+    #[serde(rename = "parse")]
+    Parse,
+    #[serde(rename = "analysis")]
+    Analysis,
+}
+
+/// Body of a 400 response to a contract that fails to parse or type-check
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContractAnalysisErrorResponse {
+    pub stage: ContractAnalysisStage,
+    pub diagnostic: Diagnostic,
+}
+
+/// Parse and type-check `source` as a contract in `epoch`, returning its interface.
+/// Analysis runs against an empty in-memory store, so the contract is never stored, and it
+/// cannot refer to other contracts (including boot contracts) or to their traits.
+/// Type-checking is charged to `cost_track`, and fails with `CostBalanceExceeded` once its
+/// limit is reached.
+pub fn analyze_contract_source(
+    source: &str,
+    epoch: StacksEpochId,
+    cost_track: LimitedCostTracker,
+) -> Result<ContractInterface, ContractAnalysisErrorResponse> {
+    let contract_identifier = QualifiedContractIdentifier::transient();
+    let clarity_version = ClarityVersion::default_for_epoch(epoch);
+    let mut expressions = build_ast_with_rules(
+        &contract_identifier,
+        source,
+        &mut (),
+        clarity_version,
+        epoch,
+        ASTRules::PrecheckSize,
+    )
+    .map_err(|e| ContractAnalysisErrorResponse {
+        stage: ContractAnalysisStage::Parse,
+        diagnostic: e.diagnostic,
+    })?
+    .expressions;
+
+    let mut marf = MemoryBackingStore::new();
+    let contract_analysis = run_analysis(
+        &contract_identifier,
+        &mut expressions,
+        &mut marf.as_analysis_db(),
+        false,
+        cost_track,
+        epoch,
+        clarity_version,
+        false,
+    )
+    .map_err(|(e, _)| ContractAnalysisErrorResponse {
+        stage: ContractAnalysisStage::Analysis,
+        diagnostic: e.diagnostic,
+    })?;
+
+    build_contract_interface(&contract_analysis).map_err(|e| ContractAnalysisErrorResponse {
+        stage: ContractAnalysisStage::Analysis,
+        diagnostic: e.diagnostic,
+    })
+}
+
+#[derive(Clone)]
+pub struct RPCPostContractAnalysisRequestHandler {
+    maximum_source_size: u32,
+    analysis_cost_limit: ExecutionCost,
+
+    /// Parameters
+    pub source: Option<String>,
+    pub epoch: Option<StacksEpochId>,
+}
+
+impl RPCPostContractAnalysisRequestHandler {
+    pub fn new(maximum_source_size: u32, analysis_cost_limit: ExecutionCost) -> Self {
+        Self {
+            maximum_source_size,
+            analysis_cost_limit,
+            source: None,
+            epoch: None,
+        }
+    }
+}
+
+/// Decode the HTTP request
+impl HttpRequest for RPCPostContractAnalysisRequestHandler {
+    fn verb(&self) -> &'static str {
+        "POST"
+    }
+
+    fn path_regex(&self) -> Regex {
+        Regex::new(r#"^/v2/contracts/analyze$"#).unwrap()
+    }
+
+    fn metrics_identifier(&self) -> &str {
+        "/v2/contracts/analyze"
+    }
+
+    /// Try to decode this request.
+    /// There's nothing to load here, so just make sure the request is well-formed.
+    fn try_parse_request(
+        &mut self,
+        preamble: &HttpRequestPreamble,
+        _captures: &Captures,
+        query: Option<&str>,
+        body: &[u8],
+    ) -> Result<HttpRequestContents, Error> {
+        let content_len = preamble.get_content_length();
+        if !(content_len > 0 && content_len < self.maximum_source_size) {
+            return Err(Error::DecodeError(format!(
+                "Invalid Http request: invalid body length for ContractAnalysis ({})",
+                content_len
+            )));
+        }
+
+        if preamble.content_type != Some(HttpContentType::JSON) {
+            return Err(Error::DecodeError(
+                "Invalid content-type: expected application/json".to_string(),
+            ));
+        }
+
+        let body: ContractAnalysisRequestBody = serde_json::from_slice(body)
+            .map_err(|e| Error::DecodeError(format!("Failed to parse JSON body: {}", e)))?;
+
+        if let Some(epoch) = body.epoch {
+            if epoch < StacksEpochId::Epoch20 {
+                return Err(Error::DecodeError(format!(
+                    "Cannot analyze contracts in {}",
+                    epoch
+                )));
+            }
+        }
+
+        self.source = Some(body.source);
+        self.epoch = body.epoch;
+        Ok(HttpRequestContents::new().query_string(query))
+    }
+}
+
+impl RPCRequestHandler for RPCPostContractAnalysisRequestHandler {
+    /// Reset internal state
+    fn restart(&mut self) {
+        self.source = None;
+        self.epoch = None;
+    }
+
+    /// Make the response.
+    /// Chain state is only read to load the cost functions at the requested tip.
+    fn try_handle_request(
+        &mut self,
+        preamble: HttpRequestPreamble,
+        contents: HttpRequestContents,
+        node: &mut StacksNodeState,
+    ) -> Result<(HttpResponsePreamble, HttpResponseContents), NetError> {
+        let tip = match node.load_stacks_chain_tip(&preamble, &contents) {
+            Ok(tip) => tip,
+            Err(error_resp) => {
+                return error_resp.try_into_contents().map_err(NetError::from);
+            }
+        };
+
+        let source = self
+            .source
+            .take()
+            .ok_or(NetError::SendError("`source` not set".into()))?;
+        let requested_epoch = self.epoch.take();
+
+        // analysis runs on the p2p thread, so bound it the same way as a read-only call
+        let cost_track_and_epoch =
+            node.with_node_state(|_network, sortdb, chainstate, _mempool, _rpc_args| {
+                let mainnet = chainstate.mainnet;
+                let chain_id = chainstate.chain_id;
+                // analysis only writes to its own scratch store, so only reads and runtime
+                // are bounded
+                let mut cost_limit = self.analysis_cost_limit.clone();
+                cost_limit.write_length = u64::MAX;
+                cost_limit.write_count = u64::MAX;
+
+                chainstate.maybe_read_only_clarity_tx(&sortdb.index_conn(), &tip, |clarity_tx| {
+                    let tip_epoch = clarity_tx.get_epoch();
+                    clarity_tx
+                        .with_clarity_db_readonly(|clarity_db| {
+                            LimitedCostTracker::new_mid_block(
+                                mainnet, chain_id, cost_limit, clarity_db, tip_epoch,
+                            )
+                        })
+                        .map(|cost_track| (cost_track, requested_epoch.unwrap_or(tip_epoch)))
+                })
+            });
+
+        let (cost_track, epoch) = match cost_track_and_epoch {
+            Ok(Some(Ok(cost_track_and_epoch))) => cost_track_and_epoch,
+            Ok(Some(Err(e))) => {
+                return StacksHttpResponse::new_error(
+                    &preamble,
+                    &HttpServerError::new(format!("Failed to load cost functions: {:?}", &e)),
+                )
+                .try_into_contents()
+                .map_err(NetError::from);
+            }
+            Ok(None) | Err(_) => {
+                return StacksHttpResponse::new_error(
+                    &preamble,
+                    &HttpNotFound::new("Chain tip not found".to_string()),
+                )
+                .try_into_contents()
+                .map_err(NetError::from);
+            }
+        };
+
+        let data_resp = match analyze_contract_source(&source, epoch, cost_track) {
+            Ok(interface) => interface,
+            Err(analysis_error) => {
+                let error_json = serde_json::to_value(&analysis_error).map_err(|e| {
+                    NetError::SendError(format!("Failed to encode analysis error: {:?}", &e))
+                })?;
+                return StacksHttpResponse::new_error(
+                    &preamble,
+                    &HttpBadRequest::new_json(error_json),
+                )
+                .try_into_contents()
+                .map_err(NetError::from);
+            }
+        };
+
+        let mut preamble = HttpResponsePreamble::ok_json(&preamble);
+        preamble.set_canonical_stacks_tip_height(Some(node.canonical_stacks_tip_height()));
+        let body = HttpResponseContents::try_from_json(&data_resp)?;
+        Ok((preamble, body))
+    }
+}
+
+/// Decode the HTTP response
+impl HttpResponse for RPCPostContractAnalysisRequestHandler {
+    fn try_parse_response(
+        &self,
+        preamble: &HttpResponsePreamble,
+        body: &[u8],
+    ) -> Result<HttpResponsePayload, Error> {
+        let interface: ContractInterface = parse_json(preamble, body)?;
+        Ok(HttpResponsePayload::try_from_json(interface)?)
+    }
+}
+
+impl StacksHttpRequest {
+    /// Make a new request to analyze a contract's source
+    pub fn new_post_contract_analysis(
+        host: PeerHost,
+        analysis_request: ContractAnalysisRequestBody,
+    ) -> StacksHttpRequest {
+        StacksHttpRequest::new_for_peer(
+            host,
+            "POST".into(),
+            "/v2/contracts/analyze".into(),
+            HttpRequestContents::new().payload_json(
+                serde_json::to_value(analysis_request)
+                    .expect("FATAL: failed to encode contract analysis request to JSON"),
+            ),
+        )
+        .expect("FATAL: failed to construct request from infallible data")
+    }
+}
+
+impl StacksHttpResponse {
+    pub fn decode_contract_analysis_response(self) -> Result<ContractInterface, NetError> {
+        let contents = self.get_http_payload_ok()?;
+        let contents_json: serde_json::Value = contents.try_into()?;
+        let resp: ContractInterface = serde_json::from_value(contents_json)
+            .map_err(|_e| NetError::DeserializeError("Failed to load from JSON".to_string()))?;
+        Ok(resp)
+    }
+}
@@ -204,3 +204,35 @@ fn test_try_make_response() {
     assert!(resp.balance_proof.is_some());
     assert!(resp.nonce_proof.is_some());
 }
+
+#[test]
+fn test_account_entry_response_from_db() {
+    let balance = 123_456_789_012_345_678_901_234_567_890u128;
+    let account = getaccount::AccountEntryResponse::from_db(balance, 7, None);
+    assert_eq!(account.balance.len(), 2 + 32);
+    assert_eq!(
+        u128::from_str_radix(&account.balance[2..], 16).unwrap(),
+        balance
+    );
+    assert_eq!(u128::from_str_radix(&account.locked[2..], 16).unwrap(), 0);
+    assert_eq!(account.unlock_height, 0);
+    assert_eq!(account.nonce, 7);
+    assert!(account.balance_proof.is_none());
+    assert!(account.nonce_proof.is_none());
+
+    // the JSON encoding leaves out absent proofs, and round-trips
+    let json = serde_json::to_value(&account).unwrap();
+    assert!(json.get("balance_proof").is_none());
+    let decoded: getaccount::AccountEntryResponse = serde_json::from_value(json).unwrap();
+    assert_eq!(decoded, account);
+
+    // an empty proof means the entry was not found
+    let account =
+        getaccount::AccountEntryResponse::from_db(u128::MAX, 0, Some((vec![0xab, 0xcd], vec![])));
+    assert_eq!(
+        u128::from_str_radix(&account.balance[2..], 16).unwrap(),
+        u128::MAX
+    );
+    assert_eq!(account.balance_proof, Some("0xabcd".to_string()));
+    assert_eq!(account.nonce_proof, Some("".to_string()));
+}
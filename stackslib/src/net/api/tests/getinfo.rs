@@ -103,3 +103,47 @@ fn test_try_make_response() {
     );
     let resp = response.decode_peer_info().unwrap();
 }
+
+#[test]
+fn test_request_id_propagation() {
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 33333);
+
+    // a generated request ID is visible to the handler, and is stable
+    let mut request = StacksHttpRequest::new_getinfo(addr.into(), Some(123));
+    let request_id = request.get_or_set_request_id();
+    assert_eq!(request.preamble().get_request_id(), Some(request_id));
+    assert_eq!(request.get_or_set_request_id(), request_id);
+
+    let mut requests = vec![];
+
+    // client-supplied request ID
+    let mut request = StacksHttpRequest::new_getinfo(addr.into(), Some(123));
+    request.add_header("X-Request-Id".to_string(), "12345".to_string());
+    requests.push(request);
+
+    // no request ID
+    let request = StacksHttpRequest::new_getinfo(addr.into(), Some(123));
+    assert!(request.preamble().get_request_id().is_none());
+    requests.push(request);
+
+    // a request ID that isn't a number gets replaced
+    let mut request = StacksHttpRequest::new_getinfo(addr.into(), Some(123));
+    request.add_header("X-Request-Id".to_string(), "operator-req-1".to_string());
+    requests.push(request);
+
+    let mut responses = test_rpc(function_name!(), requests);
+
+    // echoed back
+    let response = responses.remove(0);
+    assert_eq!(response.preamble().get_request_id(), Some(12345));
+
+    // generated
+    let response = responses.remove(0);
+    let request_id = response.preamble().get_request_id().unwrap();
+    assert_ne!(request_id, 0);
+
+    // replaced
+    let response = responses.remove(0);
+    let request_id = response.preamble().get_request_id().unwrap();
+    assert_ne!(request_id, 0);
+}
@@ -77,6 +77,7 @@ mod gettenureinfo;
 mod gettransaction_unconfirmed;
 mod liststackerdbreplicas;
 mod postblock;
+mod postcontractanalysis;
 mod postfeerate;
 mod postmempoolquery;
 mod postmicroblock;
@@ -258,6 +259,7 @@ impl<'a> TestRPC<'a> {
             runtime: 2000000,
         };
         peer_1_config.connection_opts.maximum_call_argument_size = 4096;
+        peer_1_config.connection_opts.contract_analysis_endpoint = true;
 
         peer_2_config.connection_opts.read_only_call_limit = ExecutionCost {
             write_length: 0,
@@ -267,6 +269,7 @@ impl<'a> TestRPC<'a> {
             runtime: 2000000,
         };
         peer_2_config.connection_opts.maximum_call_argument_size = 4096;
+        peer_2_config.connection_opts.contract_analysis_endpoint = true;
 
         // stacker DBs get initialized thru reconfiguration when the above block gets processed
         peer_1_config.add_stacker_db(
@@ -0,0 +1,250 @@
+// Copyright (C) 2013-2020 Blockstack PBC, a public benefit corporation
+// Copyright (C) 2020-2024 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+use clarity::vm::costs::ExecutionCost;
+use stacks_common::types::StacksEpochId;
+
+use super::test_rpc;
+use crate::net::api::postcontractanalysis::{
+    ContractAnalysisErrorResponse, ContractAnalysisRequestBody, ContractAnalysisStage,
+};
+use crate::net::api::*;
+use crate::net::connection::ConnectionOptions;
+use crate::net::httpcore::{RPCRequestHandler, StacksHttp, StacksHttpRequest};
+use crate::net::ProtocolFamily;
+
+const GOOD_CONTRACT: &str = "
+    (define-data-var counter uint u0)
+    (define-public (increment) (ok (var-set counter (+ (var-get counter) u1))))
+    (define-read-only (get-counter) (var-get counter))";
+
+#[test]
+fn test_try_parse_request() {
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 33333);
+    let mut http = StacksHttp::new(addr.clone(), &ConnectionOptions::default());
+
+    let request = StacksHttpRequest::new_post_contract_analysis(
+        addr.into(),
+        ContractAnalysisRequestBody {
+            source: GOOD_CONTRACT.to_string(),
+            epoch: Some(StacksEpochId::Epoch21),
+        },
+    );
+    let bytes = request.try_serialize().unwrap();
+
+    debug!("Request:\n{}\n", std::str::from_utf8(&bytes).unwrap());
+
+    let (parsed_preamble, offset) = http.read_preamble(&bytes).unwrap();
+    let mut handler = postcontractanalysis::RPCPostContractAnalysisRequestHandler::new(
+        4096,
+        ExecutionCost::max_value(),
+    );
+    let mut parsed_request = http
+        .handle_try_parse_request(
+            &mut handler,
+            &parsed_preamble.expect_request(),
+            &bytes[offset..],
+        )
+        .unwrap();
+
+    assert_eq!(handler.source, Some(GOOD_CONTRACT.to_string()));
+    assert_eq!(handler.epoch, Some(StacksEpochId::Epoch21));
+
+    // parsed request consumes headers that would not be in a constructed reqeuest
+    parsed_request.clear_headers();
+    let (preamble, contents) = parsed_request.destruct();
+
+    assert_eq!(&preamble, request.preamble());
+
+    handler.restart();
+    assert!(handler.source.is_none());
+    assert!(handler.epoch.is_none());
+
+    // epoch 1.0 has no Clarity
+    let request = StacksHttpRequest::new_post_contract_analysis(
+        addr.into(),
+        ContractAnalysisRequestBody {
+            source: GOOD_CONTRACT.to_string(),
+            epoch: Some(StacksEpochId::Epoch10),
+        },
+    );
+    let bytes = request.try_serialize().unwrap();
+    let (parsed_preamble, offset) = http.read_preamble(&bytes).unwrap();
+    let mut handler = postcontractanalysis::RPCPostContractAnalysisRequestHandler::new(
+        4096,
+        ExecutionCost::max_value(),
+    );
+    assert!(http
+        .handle_try_parse_request(
+            &mut handler,
+            &parsed_preamble.expect_request(),
+            &bytes[offset..],
+        )
+        .is_err());
+
+    // source larger than the configured maximum
+    let request = StacksHttpRequest::new_post_contract_analysis(
+        addr.into(),
+        ContractAnalysisRequestBody {
+            source: format!("{};; {}", GOOD_CONTRACT, "a".repeat(4096)),
+            epoch: Some(StacksEpochId::Epoch21),
+        },
+    );
+    let bytes = request.try_serialize().unwrap();
+    let (parsed_preamble, offset) = http.read_preamble(&bytes).unwrap();
+    let mut handler = postcontractanalysis::RPCPostContractAnalysisRequestHandler::new(
+        4096,
+        ExecutionCost::max_value(),
+    );
+    assert!(http
+        .handle_try_parse_request(
+            &mut handler,
+            &parsed_preamble.expect_request(),
+            &bytes[offset..],
+        )
+        .is_err());
+}
+
+#[test]
+fn test_endpoint_disabled_by_default() {
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 33333);
+    let request = StacksHttpRequest::new_post_contract_analysis(
+        addr.into(),
+        ContractAnalysisRequestBody {
+            source: GOOD_CONTRACT.to_string(),
+            epoch: Some(StacksEpochId::Epoch21),
+        },
+    );
+    let bytes = request.try_serialize().unwrap();
+
+    let mut http = StacksHttp::new(addr.clone(), &ConnectionOptions::default());
+    let (parsed_preamble, offset) = http.read_preamble(&bytes).unwrap();
+    assert!(http
+        .try_parse_request(&parsed_preamble.expect_request(), &bytes[offset..])
+        .is_err());
+
+    let mut conn_opts = ConnectionOptions::default();
+    conn_opts.contract_analysis_endpoint = true;
+    let mut http = StacksHttp::new(addr.clone(), &conn_opts);
+    let (parsed_preamble, offset) = http.read_preamble(&bytes).unwrap();
+    assert!(http
+        .try_parse_request(&parsed_preamble.expect_request(), &bytes[offset..])
+        .is_ok());
+}
+
+#[test]
+fn test_try_make_response() {
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 33333);
+
+    let mut requests = vec![];
+
+    // contract that passes analysis in a given epoch
+    let request = StacksHttpRequest::new_post_contract_analysis(
+        addr.into(),
+        ContractAnalysisRequestBody {
+            source: GOOD_CONTRACT.to_string(),
+            epoch: Some(StacksEpochId::Epoch21),
+        },
+    );
+    requests.push(request);
+
+    // contract that passes analysis in the current epoch
+    let request = StacksHttpRequest::new_post_contract_analysis(
+        addr.into(),
+        ContractAnalysisRequestBody {
+            source: GOOD_CONTRACT.to_string(),
+            epoch: None,
+        },
+    );
+    requests.push(request);
+
+    // contract that fails to type-check
+    let request = StacksHttpRequest::new_post_contract_analysis(
+        addr.into(),
+        ContractAnalysisRequestBody {
+            source: "(define-read-only (get-one) (foo u1))".to_string(),
+            epoch: Some(StacksEpochId::Epoch21),
+        },
+    );
+    requests.push(request);
+
+    // contract that fails to parse
+    let request = StacksHttpRequest::new_post_contract_analysis(
+        addr.into(),
+        ContractAnalysisRequestBody {
+            source: "(define-read-only (get-one) u1".to_string(),
+            epoch: Some(StacksEpochId::Epoch21),
+        },
+    );
+    requests.push(request);
+
+    let mut responses = test_rpc(function_name!(), requests);
+
+    // analysis in a given epoch
+    let response = responses.remove(0);
+    debug!(
+        "Response:\n{}\n",
+        std::str::from_utf8(&response.try_serialize().unwrap()).unwrap()
+    );
+
+    let interface = response.decode_contract_analysis_response().unwrap();
+    assert_eq!(interface.epoch, StacksEpochId::Epoch21);
+    let function_names: Vec<_> = interface
+        .functions
+        .iter()
+        .map(|f| f.name.as_str())
+        .collect();
+    assert_eq!(function_names, vec!["increment", "get-counter"]);
+
+    // analysis in the current epoch
+    let response = responses.remove(0);
+    debug!(
+        "Response:\n{}\n",
+        std::str::from_utf8(&response.try_serialize().unwrap()).unwrap()
+    );
+
+    let interface = response.decode_contract_analysis_response().unwrap();
+    assert_eq!(interface.functions.len(), 2);
+
+    // type-check failure
+    let response = responses.remove(0);
+    debug!(
+        "Response:\n{}\n",
+        std::str::from_utf8(&response.try_serialize().unwrap()).unwrap()
+    );
+
+    let (preamble, body) = response.destruct();
+    assert_eq!(preamble.status_code, 400);
+    let body_json: serde_json::Value = body.try_into().unwrap();
+    let error: ContractAnalysisErrorResponse = serde_json::from_value(body_json).unwrap();
+    assert_eq!(error.stage, ContractAnalysisStage::Analysis);
+    assert!(!error.diagnostic.spans.is_empty());
+
+    // parse failure
+    let response = responses.remove(0);
+    debug!(
+        "Response:\n{}\n",
+        std::str::from_utf8(&response.try_serialize().unwrap()).unwrap()
+    );
+
+    let (preamble, body) = response.destruct();
+    assert_eq!(preamble.status_code, 400);
+    let body_json: serde_json::Value = body.try_into().unwrap();
+    let error: ContractAnalysisErrorResponse = serde_json::from_value(body_json).unwrap();
+    assert_eq!(error.stage, ContractAnalysisStage::Parse);
+}
@@ -77,6 +77,17 @@ pub const BANDWIDTH_POINT_LIFETIME: u64 = 600;
 
 pub const MAX_PEER_HEARTBEAT_INTERVAL: usize = 3600 * 6; // 6 hours
 
+/// Weight of contact recency in NeighborStats::health_score()
+pub const HEALTH_SCORE_RECENCY_WEIGHT: f64 = 0.4;
+/// Weight of having completed a handshake in NeighborStats::health_score()
+pub const HEALTH_SCORE_HANDSHAKE_WEIGHT: f64 = 0.3;
+/// Weight of receive throughput in NeighborStats::health_score()
+pub const HEALTH_SCORE_THROUGHPUT_WEIGHT: f64 = 0.3;
+/// Number of seconds since last contact at which the recency term drops to 1/2
+pub const HEALTH_SCORE_RECENCY_SCALE: f64 = 60.0;
+/// Receive throughput, in bytes per second, at which the throughput term reaches 1/2
+pub const HEALTH_SCORE_THROUGHPUT_SCALE: f64 = 65536.0;
+
 /// Statistics on relayer hints in Stacks messages.  Used to deduce network choke points.
 #[derive(Debug, Clone)]
 pub struct RelayStats {
@@ -118,6 +129,8 @@ pub struct NeighborStats {
     pub msgs_rx: u64,
     pub msgs_rx_unsolicited: u64,
     pub msgs_err: u64,
+    /// number of messages dropped because they exceeded their type's rate limit
+    pub msgs_rate_limited: u64,
     pub healthpoints: VecDeque<NeighborHealthPoint>,
     pub msg_rx_counts: HashMap<StacksMessageID, u64>,
     /// (timestamp, num bytes)
@@ -146,6 +159,7 @@ impl NeighborStats {
             msgs_rx: 0,
             msgs_rx_unsolicited: 0,
             msgs_err: 0,
+            msgs_rate_limited: 0,
             healthpoints: VecDeque::new(),
             msg_rx_counts: HashMap::new(),
             block_push_rx_counts: VecDeque::new(),
@@ -255,6 +269,48 @@ impl NeighborStats {
         (successful as f64) / (total as f64)
     }
 
+    /// Get a composite health score for this peer, in [0.0, 1.0], as of now.
+    /// See `health_score_at()`.
+    pub fn health_score(&self) -> f64 {
+        self.health_score_at(get_epoch_time_secs())
+    }
+
+    /// Get a composite health score for this peer, in [0.0, 1.0], as of `now`.  It is the
+    /// weighted sum of three terms, each in [0.0, 1.0]:
+    /// * recency: `R / (R + age)`, where `age` is the number of seconds since we last heard from
+    /// the peer and `R` is `HEALTH_SCORE_RECENCY_SCALE` (0 if we never heard from it).
+    /// * handshake: 1 if the peer has completed a handshake with us, and 0 if not.
+    /// * throughput: `T / (T + S)`, where `T` is the bytes/sec we have received from the peer since
+    /// first contact and `S` is `HEALTH_SCORE_THROUGHPUT_SCALE`.
+    /// The weights are `HEALTH_SCORE_RECENCY_WEIGHT`, `HEALTH_SCORE_HANDSHAKE_WEIGHT`, and
+    /// `HEALTH_SCORE_THROUGHPUT_WEIGHT`.  The score depends only on these stats and `now`.
+    pub fn health_score_at(&self, now: u64) -> f64 {
+        let recency = if self.last_contact_time == 0 {
+            0.0
+        } else {
+            let age = now.saturating_sub(self.last_contact_time) as f64;
+            HEALTH_SCORE_RECENCY_SCALE / (HEALTH_SCORE_RECENCY_SCALE + age)
+        };
+
+        let handshake = if self.last_handshake_time > 0 {
+            1.0
+        } else {
+            0.0
+        };
+
+        let throughput = if self.first_contact_time == 0 {
+            0.0
+        } else {
+            let elapsed = cmp::max(1, now.saturating_sub(self.first_contact_time)) as f64;
+            let rate = (self.bytes_rx as f64) / elapsed;
+            rate / (rate + HEALTH_SCORE_THROUGHPUT_SCALE)
+        };
+
+        HEALTH_SCORE_RECENCY_WEIGHT * recency
+            + HEALTH_SCORE_HANDSHAKE_WEIGHT * handshake
+            + HEALTH_SCORE_THROUGHPUT_WEIGHT * throughput
+    }
+
     fn get_bandwidth(rx_counts: &VecDeque<(u64, u64)>, lifetime: u64) -> f64 {
         if rx_counts.len() < 2 {
             return 0.0;
@@ -304,6 +360,44 @@ impl NeighborStats {
     }
 }
 
+/// Token bucket that limits how often a peer may send us a particular message type.
+/// The bucket holds up to one second's worth of tokens, and refills continuously.
+#[derive(Debug, Clone)]
+pub struct MessageRateLimiter {
+    /// tokens added per second
+    rate: f64,
+    /// tokens currently available
+    tokens: f64,
+    /// when we last added tokens, in milliseconds since the epoch
+    last_refill_ms: u128,
+}
+
+impl MessageRateLimiter {
+    /// Make a full bucket, as of `now_ms` milliseconds since the epoch
+    pub fn new(max_per_sec: u64, now_ms: u128) -> MessageRateLimiter {
+        let rate = cmp::max(max_per_sec, 1) as f64;
+        MessageRateLimiter {
+            rate,
+            tokens: rate,
+            last_refill_ms: now_ms,
+        }
+    }
+
+    /// Take a token for a message received at `now` milliseconds since the epoch.
+    /// Returns false if there are none left (i.e. the message should be dropped).
+    pub fn try_acquire(&mut self, now: u128) -> bool {
+        let elapsed_secs = (now.saturating_sub(self.last_refill_ms) as f64) / 1000.0;
+        self.tokens = (self.tokens + elapsed_secs * self.rate).min(self.rate);
+        self.last_refill_ms = now;
+
+        if self.tokens < 1.0 {
+            return false;
+        }
+        self.tokens -= 1.0;
+        true
+    }
+}
+
 /// P2P ongoing conversation with another Stacks peer
 pub struct ConversationP2P {
     /// Instantiation timestamp in seconds since the epoch
@@ -370,6 +464,13 @@ pub struct ConversationP2P {
     /// outbound replies
     pub reply_handles: VecDeque<ReplyHandleP2P>,
 
+    /// per-message-type rate limiters, created as messages of each limited type arrive
+    rate_limiters: HashMap<StacksMessageID, MessageRateLimiter>,
+    /// if set, the rate limiters use this as the current time in milliseconds instead of the
+    /// system clock
+    #[cfg(test)]
+    rate_limit_clock_ms: Option<u128>,
+
     /// system epochs
     epochs: Vec<StacksEpoch>,
 }
@@ -582,6 +683,9 @@ impl ConversationP2P {
             reply_handles: VecDeque::new(),
 
             db_smart_contracts: vec![],
+            rate_limiters: HashMap::new(),
+            #[cfg(test)]
+            rate_limit_clock_ms: None,
 
             epochs: epochs,
         }
@@ -791,6 +895,8 @@ impl ConversationP2P {
     /// Return Ok(false) if we can't proceed, but the remote peer is not in violation of the protocol
     /// Return Err(net_error::InvalidMessage) if the remote peer returns an invalid message in
     ///     violation of the protocol
+    /// Return Err(net_error::IncompatiblePeerVersion(..)) if the remote peer's major protocol
+    ///     version differs from ours.  Minor versions may differ.
     pub fn is_preamble_valid(
         &self,
         msg: &StacksMessage,
@@ -812,7 +918,9 @@ impl ConversationP2P {
                 msg.preamble.peer_version,
                 self.version
             );
-            return Err(net_error::InvalidMessage);
+            return Err(net_error::IncompatiblePeerVersion(
+                msg.preamble.peer_version,
+            ));
         }
         if !self.has_acceptable_epoch(chain_view.burn_block_height, msg.preamble.peer_version) {
             debug!(
@@ -2424,7 +2532,7 @@ impl ConversationP2P {
         // validate message preamble
         if let Err(e) = self.is_preamble_valid(&msg, burnchain_view) {
             match e {
-                net_error::InvalidMessage => {
+                net_error::InvalidMessage | net_error::IncompatiblePeerVersion(_) => {
                     // Disconnect from this peer.  If it thinks nothing's wrong, it'll
                     // reconnect on its own.
                     // However, only count this message as error.  Drop all other queued
@@ -2651,6 +2759,60 @@ impl ConversationP2P {
         Ok((reply_opt, consume))
     }
 
+    /// The current time in milliseconds, as far as the rate limiters are concerned
+    fn rate_limit_now_ms(&self) -> u128 {
+        #[cfg(test)]
+        {
+            if let Some(now_ms) = self.rate_limit_clock_ms {
+                return now_ms;
+            }
+        }
+        get_epoch_time_ms()
+    }
+
+    /// Charge a received message against its type's rate limit, if it has one (see
+    /// ConnectionOptions::max_pings_per_sec and ConnectionOptions::max_inv_requests_per_sec).
+    /// Returns Ok(true) if the message can be handled, and Ok(false) if it must be dropped.
+    /// Returns Err(net_error::ConnectionBroken) once this peer has had more than
+    /// ConnectionOptions::max_rate_limited_msgs messages dropped.
+    fn check_message_rate(&mut self, msg_id: StacksMessageID) -> Result<bool, net_error> {
+        let max_per_sec = match msg_id {
+            StacksMessageID::Ping => self.connection.options.max_pings_per_sec,
+            StacksMessageID::GetBlocksInv
+            | StacksMessageID::GetPoxInv
+            | StacksMessageID::GetNakamotoInv => self.connection.options.max_inv_requests_per_sec,
+            _ => 0,
+        };
+        if max_per_sec == 0 {
+            return Ok(true);
+        }
+
+        let now_ms = self.rate_limit_now_ms();
+        let limiter = self
+            .rate_limiters
+            .entry(msg_id)
+            .or_insert_with(|| MessageRateLimiter::new(max_per_sec, now_ms));
+        if limiter.try_acquire(now_ms) {
+            return Ok(true);
+        }
+
+        self.stats.msgs_rate_limited += 1;
+        debug!(
+            "{:?}: Drop message {:?}: exceeded {} per second (total dropped: {})",
+            &self, msg_id, max_per_sec, self.stats.msgs_rate_limited
+        );
+
+        let max_rate_limited_msgs = self.connection.options.max_rate_limited_msgs;
+        if max_rate_limited_msgs > 0 && self.stats.msgs_rate_limited > max_rate_limited_msgs {
+            info!(
+                "{:?}: Peer exceeded message rate limits {} times; disconnecting",
+                &self, self.stats.msgs_rate_limited
+            );
+            return Err(net_error::ConnectionBroken);
+        }
+        Ok(false)
+    }
+
     /// Update chat statistics, depending on whether or not the message was solicited
     fn update_stats(&mut self, msg: &StacksMessage, solicited: bool) {
         if solicited {
@@ -2871,6 +3033,10 @@ impl ConversationP2P {
                 continue;
             }
 
+            if !self.check_message_rate(msg.payload.get_message_id())? {
+                continue;
+            }
+
             let (mut reply_opt, consumed) = if self.connection.has_public_key() {
                 // we already have this remote peer's public key, so the message signature will
                 // have been verified by the underlying ConnectionP2P.
@@ -4071,6 +4237,145 @@ mod test {
         assert!(convo_2.connection.get_public_key().is_none());
     }
 
+    #[test]
+    fn convo_handshake_incompatible_peer_version() {
+        let conn_opts = ConnectionOptions::default();
+        let socketaddr_1 = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080);
+        let socketaddr_2 = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(1, 2, 3, 4)), 8081);
+
+        let burnchain = testing_burnchain_config();
+
+        let mut chain_view = BurnchainView {
+            burn_block_height: 12348,
+            burn_block_hash: BurnchainHeaderHash([0x11; 32]),
+            burn_stable_block_height: 12341,
+            burn_stable_block_hash: BurnchainHeaderHash([0x22; 32]),
+            last_burn_block_hashes: HashMap::new(),
+            rc_consensus_hash: ConsensusHash([0x33; 20]),
+        };
+        chain_view.make_test_data();
+
+        let first_burn_hash = BurnchainHeaderHash::from_hex(
+            "0000000000000000000000000000000000000000000000000000000000000000",
+        )
+        .unwrap();
+
+        let test_name_1 = "convo_handshake_incompatible_peer_version_1";
+        let test_name_2 = "convo_handshake_incompatible_peer_version_2";
+
+        let (mut peerdb_1, mut sortdb_1, stackerdbs_1, pox_id_1, chainstate_1) =
+            make_test_chain_dbs(
+                test_name_1,
+                &burnchain,
+                0x9abcdef0,
+                12350,
+                "http://peer1.com".into(),
+                &vec![],
+                &vec![],
+                DEFAULT_SERVICES,
+            );
+        let (mut peerdb_2, mut sortdb_2, stackerdbs_2, pox_id_2, mut chainstate_2) =
+            make_test_chain_dbs(
+                test_name_2,
+                &burnchain,
+                0x9abcdef0,
+                12351,
+                "http://peer2.com".into(),
+                &vec![],
+                &vec![],
+                DEFAULT_SERVICES,
+            );
+
+        let net_1 = db_setup(
+            &test_name_1,
+            &burnchain,
+            0x9abcdef0,
+            &mut peerdb_1,
+            &mut sortdb_1,
+            &socketaddr_1,
+            &chain_view,
+        );
+        let mut net_2 = db_setup(
+            &test_name_2,
+            &burnchain,
+            0x9abcdef0,
+            &mut peerdb_2,
+            &mut sortdb_2,
+            &socketaddr_2,
+            &chain_view,
+        );
+
+        let local_peer_1 = PeerDB::get_local_peer(&peerdb_1.conn()).unwrap();
+        let local_peer_2 = PeerDB::get_local_peer(&peerdb_2.conn()).unwrap();
+
+        let mut convo_1 = ConversationP2P::new(
+            123,
+            456,
+            &burnchain,
+            &socketaddr_2,
+            &conn_opts,
+            true,
+            0,
+            StacksEpoch::unit_test_pre_2_05(0),
+        );
+        let mut convo_2 = ConversationP2P::new(
+            123,
+            456,
+            &burnchain,
+            &socketaddr_1,
+            &conn_opts,
+            true,
+            0,
+            StacksEpoch::unit_test_pre_2_05(0),
+        );
+
+        // no peer public keys known yet
+        assert!(convo_1.connection.get_public_key().is_none());
+        assert!(convo_2.connection.get_public_key().is_none());
+
+        // convo_1 speaks a different major protocol version, but the same minor version and epoch
+        convo_1.version = convo_2.version ^ 0x01000000;
+
+        // convo_1 sends a handshake to convo_2
+        let handshake_data_1 = HandshakeData::from_local_peer(&local_peer_1);
+        let handshake_1 = convo_1
+            .sign_message(
+                &chain_view,
+                &local_peer_1.private_key,
+                StacksMessageType::Handshake(handshake_data_1.clone()),
+            )
+            .unwrap();
+
+        let mut rh_1 = convo_1.send_signed_request(handshake_1, 1000000).unwrap();
+
+        // convo_2 receives it, and rejects the conversation
+        convo_send_recv(&mut convo_1, vec![&mut rh_1], &mut convo_2);
+        let unhandled_2_err =
+            convo_2.chat(&mut net_2, &sortdb_2, &mut chainstate_2, &mut None, false);
+
+        assert_eq!(
+            unhandled_2_err.unwrap_err(),
+            net_error::IncompatiblePeerVersion(convo_1.version)
+        );
+
+        // convo_2 did not learn convo_1's public key
+        assert!(convo_2.connection.get_public_key().is_none());
+
+        // a different minor version is acceptable
+        convo_1.version = convo_2.version ^ 0x00010000;
+        let handshake_1 = convo_1
+            .sign_message(
+                &chain_view,
+                &local_peer_1.private_key,
+                StacksMessageType::Handshake(handshake_data_1),
+            )
+            .unwrap();
+        assert_eq!(
+            convo_2.is_preamble_valid(&handshake_1, &chain_view),
+            Ok(true)
+        );
+    }
+
     #[test]
     fn convo_handshake_badpeeraddress() {
         let conn_opts = ConnectionOptions::default();
@@ -4743,6 +5048,200 @@ mod test {
         }
     }
 
+    #[test]
+    fn convo_ping_rate_limited() {
+        let conn_opts = ConnectionOptions::default();
+        let mut conn_opts_2 = ConnectionOptions::default();
+        conn_opts_2.max_pings_per_sec = 2;
+        conn_opts_2.max_rate_limited_msgs = 3;
+
+        let socketaddr_1 = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080);
+        let socketaddr_2 = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(1, 2, 3, 4)), 8081);
+
+        let burnchain = testing_burnchain_config();
+
+        let mut chain_view = BurnchainView {
+            burn_block_height: 12348,
+            burn_block_hash: BurnchainHeaderHash([0x11; 32]),
+            burn_stable_block_height: 12341,
+            burn_stable_block_hash: BurnchainHeaderHash([0x22; 32]),
+            last_burn_block_hashes: HashMap::new(),
+            rc_consensus_hash: ConsensusHash([0x33; 20]),
+        };
+        chain_view.make_test_data();
+
+        let test_name_1 = "convo_ping_rate_limited_1";
+        let test_name_2 = "convo_ping_rate_limited_2";
+
+        let (mut peerdb_1, mut sortdb_1, stackerdbs_1, pox_id_1, _) = make_test_chain_dbs(
+            test_name_1,
+            &burnchain,
+            0x9abcdef0,
+            12350,
+            "http://peer1.com".into(),
+            &vec![],
+            &vec![],
+            DEFAULT_SERVICES,
+        );
+        let (mut peerdb_2, mut sortdb_2, stackerdbs_2, pox_id_2, mut chainstate_2) =
+            make_test_chain_dbs(
+                test_name_2,
+                &burnchain,
+                0x9abcdef0,
+                12351,
+                "http://peer2.com".into(),
+                &vec![],
+                &vec![],
+                DEFAULT_SERVICES,
+            );
+
+        db_setup(
+            &test_name_1,
+            &burnchain,
+            0x9abcdef0,
+            &mut peerdb_1,
+            &mut sortdb_1,
+            &socketaddr_1,
+            &chain_view,
+        );
+        let mut net_2 = db_setup(
+            &test_name_2,
+            &burnchain,
+            0x9abcdef0,
+            &mut peerdb_2,
+            &mut sortdb_2,
+            &socketaddr_2,
+            &chain_view,
+        );
+
+        let local_peer_1 = PeerDB::get_local_peer(&peerdb_1.conn()).unwrap();
+
+        let mut convo_1 = ConversationP2P::new(
+            123,
+            456,
+            &burnchain,
+            &socketaddr_2,
+            &conn_opts,
+            true,
+            0,
+            StacksEpoch::unit_test_pre_2_05(0),
+        );
+        let mut convo_2 = ConversationP2P::new(
+            123,
+            456,
+            &burnchain,
+            &socketaddr_1,
+            &conn_opts_2,
+            true,
+            0,
+            StacksEpoch::unit_test_pre_2_05(0),
+        );
+
+        // pin convo_2's rate-limit clock, so the outcome doesn't depend on how fast this runs
+        convo_2.rate_limit_clock_ms = Some(1_000_000);
+
+        // convo_1 sends a handshake, and then floods convo_2 with pings
+        let handshake_data_1 = HandshakeData::from_local_peer(&local_peer_1);
+        let handshake_1 = convo_1
+            .sign_message(
+                &chain_view,
+                &local_peer_1.private_key,
+                StacksMessageType::Handshake(handshake_data_1.clone()),
+            )
+            .unwrap();
+        let mut rh_handshake_1 = convo_1
+            .send_signed_request(handshake_1.clone(), 1000000)
+            .unwrap();
+
+        let mut rh_pings_1 = vec![];
+        for _ in 0..5 {
+            let ping_1 = convo_1
+                .sign_message(
+                    &chain_view,
+                    &local_peer_1.private_key,
+                    StacksMessageType::Ping(PingData::new()),
+                )
+                .unwrap();
+            rh_pings_1.push(convo_1.send_signed_request(ping_1, 1000000).unwrap());
+        }
+
+        let mut rhs: Vec<_> = vec![&mut rh_handshake_1];
+        rhs.extend(rh_pings_1.iter_mut());
+        convo_send_recv(&mut convo_1, rhs, &mut convo_2);
+
+        // only the first two pings get through
+        convo_2
+            .chat(&mut net_2, &sortdb_2, &mut chainstate_2, &mut None, false)
+            .unwrap();
+        assert_eq!(convo_2.stats.msgs_rate_limited, 3);
+        assert_eq!(
+            convo_2.stats.get_message_recv_count(StacksMessageID::Ping),
+            2
+        );
+
+        // deliver the handshake-accept and pongs
+        convo_send_recv(&mut convo_2, vec![], &mut convo_1);
+
+        // half a second later, the bucket has refilled by one ping
+        convo_2.rate_limit_clock_ms = Some(1_000_500);
+        let ping_1 = convo_1
+            .sign_message(
+                &chain_view,
+                &local_peer_1.private_key,
+                StacksMessageType::Ping(PingData::new()),
+            )
+            .unwrap();
+        let mut rh_ping_1 = convo_1.send_signed_request(ping_1, 1000000).unwrap();
+        convo_send_recv(&mut convo_1, vec![&mut rh_ping_1], &mut convo_2);
+        convo_2
+            .chat(&mut net_2, &sortdb_2, &mut chainstate_2, &mut None, false)
+            .unwrap();
+        assert_eq!(convo_2.stats.msgs_rate_limited, 3);
+        assert_eq!(
+            convo_2.stats.get_message_recv_count(StacksMessageID::Ping),
+            3
+        );
+        convo_send_recv(&mut convo_2, vec![], &mut convo_1);
+
+        // ...but no further, so one more dropped ping exceeds the hard limit, and the
+        // conversation breaks
+        let ping_1 = convo_1
+            .sign_message(
+                &chain_view,
+                &local_peer_1.private_key,
+                StacksMessageType::Ping(PingData::new()),
+            )
+            .unwrap();
+        let mut rh_ping_1 = convo_1.send_signed_request(ping_1, 1000000).unwrap();
+        convo_send_recv(&mut convo_1, vec![&mut rh_ping_1], &mut convo_2);
+
+        match convo_2.chat(&mut net_2, &sortdb_2, &mut chainstate_2, &mut None, false) {
+            Err(net_error::ConnectionBroken) => {}
+            x => panic!("Expected ConnectionBroken, got {:?}", &x),
+        }
+        assert_eq!(convo_2.stats.msgs_rate_limited, 4);
+    }
+
+    #[test]
+    fn message_rate_limiter_refills() {
+        let mut limiter = MessageRateLimiter::new(2, 1_000);
+        assert!(limiter.try_acquire(1_000));
+        assert!(limiter.try_acquire(1_000));
+        assert!(!limiter.try_acquire(1_000));
+
+        // half a second refills one token
+        assert!(limiter.try_acquire(1_500));
+        assert!(!limiter.try_acquire(1_500));
+
+        // the bucket never holds more than a second's worth of tokens
+        assert!(limiter.try_acquire(10_000));
+        assert!(limiter.try_acquire(10_000));
+        assert!(!limiter.try_acquire(10_000));
+
+        // a clock that goes backwards doesn't add tokens
+        assert!(!limiter.try_acquire(5_000));
+    }
+
     #[test]
     fn convo_handshake_ping_loop() {
         let conn_opts = ConnectionOptions::default();
@@ -6411,6 +6910,53 @@ mod test {
         assert_eq!(stats.get_health_score(), 0.0);
     }
 
+    #[test]
+    fn test_neighbor_stats_health_score() {
+        let now = 1_000_000;
+
+        // never contacted
+        let stats = NeighborStats::new(true);
+        assert_eq!(stats.health_score_at(now), 0.0);
+
+        // contacted just now, handshaked, no data
+        let mut fresh = NeighborStats::new(true);
+        fresh.first_contact_time = now;
+        fresh.last_contact_time = now;
+        fresh.last_handshake_time = now;
+        assert_eq!(
+            fresh.health_score_at(now),
+            HEALTH_SCORE_RECENCY_WEIGHT + HEALTH_SCORE_HANDSHAKE_WEIGHT
+        );
+
+        // same, but last heard from a minute ago
+        let mut stale = fresh.clone();
+        stale.last_contact_time = now - 60;
+        assert_eq!(
+            stale.health_score_at(now),
+            HEALTH_SCORE_RECENCY_WEIGHT * 0.5 + HEALTH_SCORE_HANDSHAKE_WEIGHT
+        );
+
+        // same as fresh, but sent us 64KiB/sec for 100 seconds
+        let mut busy = fresh.clone();
+        busy.first_contact_time = now - 100;
+        busy.bytes_rx = 100 * 65536;
+        assert_eq!(
+            busy.health_score_at(now),
+            HEALTH_SCORE_RECENCY_WEIGHT
+                + HEALTH_SCORE_HANDSHAKE_WEIGHT
+                + HEALTH_SCORE_THROUGHPUT_WEIGHT * 0.5
+        );
+
+        // same as fresh, but never handshaked
+        let mut no_handshake = fresh.clone();
+        no_handshake.last_handshake_time = 0;
+
+        assert!(busy.health_score_at(now) > fresh.health_score_at(now));
+        assert!(fresh.health_score_at(now) > stale.health_score_at(now));
+        assert!(stale.health_score_at(now) > no_handshake.health_score_at(now));
+        assert!(no_handshake.health_score_at(now) > stats.health_score_at(now));
+    }
+
     #[test]
     fn test_neighbor_stats_block_push_bandwidth() {
         let mut stats = NeighborStats::new(false);
@@ -48,6 +48,21 @@ use crate::core::PEER_VERSION_TESTNET;
 use crate::net::db::LocalPeer;
 use crate::net::{Error as net_error, *};
 
+/// Decode exactly one value from `fd`, and fail with `TrailingBytes` if `fd` has any data left
+/// over afterwards.
+pub fn consensus_deserialize_exact<T: StacksMessageCodec, R: Read>(
+    fd: &mut R,
+) -> Result<T, net_error> {
+    let item: T = read_next(fd)?;
+    let mut trailing = vec![];
+    fd.read_to_end(&mut trailing)
+        .map_err(net_error::ReadError)?;
+    if !trailing.is_empty() {
+        return Err(net_error::TrailingBytes(trailing.len()));
+    }
+    Ok(item)
+}
+
 pub fn bitvec_len(bitlen: u16) -> u16 {
     (bitlen / 8) + (if bitlen % 8 != 0 { 1 } else { 0 })
 }
@@ -1369,6 +1384,24 @@ impl StacksMessage {
         }
     }
 
+    /// Is the peer with the given public key hash in this message's relayers list?
+    /// Relayers are matched by key rather than by address, since several peers can share an IP
+    /// address (e.g. behind a NAT, or on the same host).
+    pub fn has_relayer(&self, public_key_hash: &Hash160) -> bool {
+        self.relayers
+            .iter()
+            .any(|relayer| relayer.peer.public_key_hash == *public_key_hash)
+    }
+
+    /// Verify that this message can be relayed to the peer with the given public key hash without
+    /// looping back -- i.e. the peer has not already relayed it.
+    pub fn verify_relayers(&self, public_key_hash: &Hash160) -> Result<(), net_error> {
+        if self.has_relayer(public_key_hash) {
+            return Err(net_error::RelayLoop);
+        }
+        Ok(())
+    }
+
     /// Sign the stacks message
     fn do_sign(&mut self, private_key: &Secp256k1PrivateKey) -> Result<(), net_error> {
         let mut message_bits = vec![];
@@ -1737,6 +1770,45 @@ pub mod test {
         );
     }
 
+    #[test]
+    fn codec_serialize_to_hex() {
+        assert_eq!(
+            0x01020304u32.consensus_serialize_to_hex().unwrap(),
+            "01020304"
+        );
+        assert_eq!(
+            vec![0xabu8, 0xcd].consensus_serialize_to_hex().unwrap(),
+            "00000002abcd"
+        );
+    }
+
+    #[test]
+    fn codec_vector_length_prefix_bounds() {
+        let bytes = vec![0x00, 0x00, 0x00, 0x03, 0x01, 0x02, 0x03];
+
+        // at or under the bound
+        let v: Vec<u8> = read_next_at_most(&mut &bytes[..], 3).unwrap();
+        assert_eq!(v, vec![0x01, 0x02, 0x03]);
+
+        // over the bound
+        match read_next_at_most::<_, u8>(&mut &bytes[..], 2) {
+            Err(codec_error::DeserializeError(msg)) => {
+                assert!(msg.starts_with("Array has too many items"))
+            }
+            x => panic!("Expected DeserializeError, got {:?}", &x),
+        }
+
+        // a corrupt length prefix is rejected before anything is allocated, even when
+        // unbounded
+        let corrupt_bytes = vec![0xff, 0xff, 0xff, 0xff, 0x01, 0x02, 0x03];
+        match Vec::<u64>::consensus_deserialize(&mut &corrupt_bytes[..]) {
+            Err(codec_error::DeserializeError(msg)) => {
+                assert!(msg.starts_with("Message occupies too many bytes"))
+            }
+            x => panic!("Expected DeserializeError, got {:?}", &x),
+        }
+    }
+
     #[test]
     fn codec_Preamble() {
         let preamble = Preamble {
@@ -2749,6 +2821,55 @@ pub mod test {
         ping.verify_secp256k1(&pubkey_buf).unwrap();
     }
 
+    #[test]
+    fn codec_sign_relay_and_verify_relayers() {
+        let privkey = Secp256k1PrivateKey::new();
+        let relayer_privkey = Secp256k1PrivateKey::new();
+        let relayer_addr = NeighborAddress {
+            addrbytes: PeerAddress::from_ipv4(1, 2, 3, 4),
+            port: 20444,
+            public_key_hash: Hash160::from_node_public_key(&Secp256k1PublicKey::from_private(
+                &relayer_privkey,
+            )),
+        };
+
+        // a different peer, which may well be behind the same IP address and port
+        let other_hash = Hash160::from_node_public_key(&Secp256k1PublicKey::from_private(
+            &Secp256k1PrivateKey::new(),
+        ));
+
+        let mut ping = StacksMessage::new(
+            PEER_VERSION_TESTNET,
+            0x9abcdef0,
+            12345,
+            &BurnchainHeaderHash([0x11; 32]),
+            12339,
+            &BurnchainHeaderHash([0x22; 32]),
+            StacksMessageType::Ping(PingData { nonce: 0x01020304 }),
+        );
+
+        ping.sign(444, &privkey).unwrap();
+        assert!(!ping.has_relayer(&relayer_addr.public_key_hash));
+        ping.verify_relayers(&relayer_addr.public_key_hash).unwrap();
+
+        ping.sign_relay(&relayer_privkey, 555, &relayer_addr)
+            .unwrap();
+        assert!(ping.has_relayer(&relayer_addr.public_key_hash));
+        assert!(!ping.has_relayer(&other_hash));
+        match ping.verify_relayers(&relayer_addr.public_key_hash) {
+            Err(net_error::RelayLoop) => {}
+            x => panic!("Expected RelayLoop, got {:?}", &x),
+        }
+        ping.verify_relayers(&other_hash).unwrap();
+
+        // relayers survive a round-trip
+        let mut bytes = vec![];
+        ping.consensus_serialize(&mut bytes).unwrap();
+        let ping_2 = StacksMessage::consensus_deserialize(&mut &bytes[..]).unwrap();
+        assert_eq!(ping_2.relayers, ping.relayers);
+        assert!(ping_2.has_relayer(&relayer_addr.public_key_hash));
+    }
+
     #[test]
     fn codec_stacks_public_key_roundtrip() {
         for i in 0..100 {
@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
 use std::io::{Read, Write};
 use std::ops::{Deref, DerefMut};
 use std::sync::mpsc::{
@@ -46,7 +46,8 @@ use crate::net::neighbors::{
     WALK_STATE_TIMEOUT,
 };
 use crate::net::{
-    Error as net_error, MessageSequence, Preamble, ProtocolFamily, RelayData, StacksHttp, StacksP2P,
+    Error as net_error, MessageSequence, Preamble, ProtocolFamily, RelayData, StacksHttp,
+    StacksMessageID, StacksP2P,
 };
 
 /// Receiver notification handle.
@@ -359,6 +360,7 @@ pub struct ConnectionOptions {
     pub download_interval: u64,
     pub pingback_timeout: u64,
     pub dns_timeout: u128,
+    /// maximum number of block or microblock requests in flight at once, across all peers
     pub max_inflight_blocks: u64,
     pub max_inflight_attachments: u64,
     pub max_attachment_retry_count: u64,
@@ -417,6 +419,44 @@ pub struct ConnectionOptions {
     pub force_nakamoto_epoch_transition: bool,
     /// The authorization token to enable the block proposal RPC endpoint
     pub block_proposal_token: Option<String>,
+    /// If true, HTTP 404, 405, and 500 responses are sent as a uniform JSON object with
+    /// `error`, `code`, and `path` fields, instead of each endpoint's own error body
+    pub json_error_responses: bool,
+    /// HTTP requests that take at least this many milliseconds to handle are logged with a
+    /// warning.  0 disables this.
+    pub slow_request_threshold_ms: u64,
+    /// maximum number of Pings per second we'll accept from a single peer.  0 means no limit.
+    pub max_pings_per_sec: u64,
+    /// maximum number of inventory requests (GetBlocksInv, GetPoxInv, GetNakamotoInv) per second
+    /// we'll accept from a single peer.  0 means no limit.
+    pub max_inv_requests_per_sec: u64,
+    /// once a peer has had this many messages dropped for exceeding a rate limit, its
+    /// conversation is considered broken.  0 means no limit.
+    pub max_rate_limited_msgs: u64,
+    /// if true, don't listen for or accept inbound p2p connections.  Only outbound connections
+    /// will be made.  Does not affect the HTTP server.
+    pub outbound_only: bool,
+    /// message types we're willing to relay or broadcast to our neighbors.  Messages of any
+    /// other type are dropped with `MessageTypeFiltered`.
+    pub relay_message_filter: HashSet<StacksMessageID>,
+    /// if set, seed the neighbor walk's RNG with this value so the walk is reproducible.  Only
+    /// useful for testing and debugging.
+    pub walk_seed: Option<u64>,
+    /// how many (neighbor, message) pairs to remember when suppressing duplicate relays.  The
+    /// least-recently-seen pair is forgotten once this many are stored.  0 disables the cache.
+    pub seen_cache_size: usize,
+    /// how many seconds a (neighbor, message) pair is remembered for when suppressing duplicate
+    /// relays.  Only forwarded messages (i.e. those with relayers) are suppressed.
+    pub seen_cache_ttl: u64,
+    /// how many requests to take from each `NetworkHandle` per pass of the network loop.
+    /// Handles are serviced round-robin, one request at a time, until each is empty or has
+    /// submitted this many requests.  Must be at least 1.
+    pub max_requests_per_handle_per_pass: usize,
+    /// if true, serve `POST /v2/contracts/analyze`.  Analysis runs on the p2p thread, so this
+    /// is off by default.
+    pub contract_analysis_endpoint: bool,
+    /// maximum size in bytes of a `POST /v2/contracts/analyze` request body
+    pub maximum_contract_analysis_size: u32,
 }
 
 impl std::default::Default for ConnectionOptions {
@@ -511,6 +551,19 @@ impl std::default::Default for ConnectionOptions {
             force_disconnect_interval: None,
             force_nakamoto_epoch_transition: false,
             block_proposal_token: None,
+            json_error_responses: false,
+            slow_request_threshold_ms: 0,
+            max_pings_per_sec: 0,        // no limit
+            max_inv_requests_per_sec: 0, // no limit
+            max_rate_limited_msgs: 0,    // never disconnect a rate-limited peer
+            outbound_only: false,
+            relay_message_filter: StacksMessageID::ALL.into_iter().collect(), // relay everything
+            walk_seed: None,
+            seen_cache_size: 1024,
+            seen_cache_ttl: 300,
+            max_requests_per_handle_per_pass: 16,
+            contract_analysis_endpoint: false,
+            maximum_contract_analysis_size: 128 * 1024,
         }
     }
 }
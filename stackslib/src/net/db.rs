@@ -15,6 +15,8 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 use std::collections::HashSet;
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
 use std::{fmt, fs};
 
 use clarity::vm::types::{
@@ -24,6 +26,7 @@ use rand::seq::SliceRandom;
 use rand::{thread_rng, Rng, RngCore};
 use rusqlite::types::ToSql;
 use rusqlite::{Connection, OpenFlags, OptionalExtension, Row, Transaction, NO_PARAMS};
+use serde::{Deserialize, Serialize};
 use stacks_common::types::net::{PeerAddress, PeerHost};
 use stacks_common::util;
 use stacks_common::util::hash::{
@@ -400,6 +403,68 @@ const PEERDB_SCHEMA_2: &'static [&'static str] = &[
     "#,
 ];
 
+/// Portable representation of a neighbor, for sharing peer lists as JSON
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NeighborJson {
+    pub peer_version: u32,
+    /// IP address, as a string
+    pub addr: String,
+    pub port: u16,
+    /// compressed public key, as hex
+    pub public_key: String,
+    pub expire_block: u64,
+    pub asn: u32,
+    pub org: u32,
+    pub in_degree: u32,
+    pub out_degree: u32,
+}
+
+impl NeighborJson {
+    pub fn from_neighbor(neighbor: &Neighbor) -> NeighborJson {
+        NeighborJson {
+            peer_version: neighbor.addr.peer_version,
+            addr: neighbor.addr.to_socketaddr().ip().to_string(),
+            port: neighbor.addr.port,
+            public_key: to_hex(&neighbor.public_key.to_bytes_compressed()),
+            expire_block: neighbor.expire_block,
+            asn: neighbor.asn,
+            org: neighbor.org,
+            in_degree: neighbor.in_degree,
+            out_degree: neighbor.out_degree,
+        }
+    }
+
+    /// Convert to a neighbor in the given network.
+    /// Fails if the address or public key cannot be parsed.
+    pub fn to_neighbor(&self, network_id: u32) -> Result<Neighbor, db_error> {
+        let ip = IpAddr::from_str(&self.addr).map_err(|_e| {
+            warn!("Invalid neighbor address '{}'", &self.addr);
+            db_error::ParseError
+        })?;
+        let public_key = Secp256k1PublicKey::from_hex(&self.public_key).map_err(|_e| {
+            warn!("Invalid neighbor public key '{}'", &self.public_key);
+            db_error::ParseError
+        })?;
+        Ok(Neighbor {
+            addr: NeighborKey {
+                peer_version: self.peer_version,
+                network_id,
+                addrbytes: PeerAddress::from_socketaddr(&SocketAddr::new(ip, self.port)),
+                port: self.port,
+            },
+            public_key,
+            expire_block: self.expire_block,
+            last_contact_time: 0,
+            allowed: 0,
+            denied: 0,
+            asn: self.asn,
+            org: self.org,
+            in_degree: self.in_degree,
+            out_degree: self.out_degree,
+        })
+    }
+}
+
 #[derive(Debug)]
 pub struct PeerDB {
     pub conn: Connection,
@@ -1627,6 +1692,31 @@ impl PeerDB {
         count: u32,
         block_height: u64,
         always_include_allowed: bool,
+    ) -> Result<Vec<Neighbor>, db_error> {
+        Self::inner_get_fresh_random_neighbors(
+            conn,
+            network_id,
+            network_epoch,
+            min_age,
+            count,
+            block_height,
+            always_include_allowed,
+            None,
+        )
+    }
+
+    /// Get random neighbors, optionally always including allowed neighbors.
+    /// If `rng_opt` is given, then sample with it instead of with SQLite's `RANDOM()`, so the
+    /// sample is reproducible for a seeded RNG.  Doing so loads every candidate neighbor.
+    fn inner_get_fresh_random_neighbors(
+        conn: &DBConn,
+        network_id: u32,
+        network_epoch: u8,
+        min_age: u64,
+        count: u32,
+        block_height: u64,
+        always_include_allowed: bool,
+        rng_opt: Option<&mut dyn RngCore>,
     ) -> Result<Vec<Neighbor>, db_error> {
         let mut ret = vec![];
 
@@ -1635,7 +1725,7 @@ impl PeerDB {
 
         if always_include_allowed {
             // always include allowed neighbors, freshness be damned
-            let allow_qry = "SELECT * FROM frontier WHERE network_id = ?1 AND denied < ?2 AND (allowed < 0 OR ?3 < allowed) AND (peer_version & 0x000000ff) >= ?4";
+            let allow_qry = "SELECT * FROM frontier WHERE network_id = ?1 AND denied < ?2 AND (allowed < 0 OR ?3 < allowed) AND (peer_version & 0x000000ff) >= ?4 ORDER BY addrbytes, port";
             let allow_args: &[&dyn ToSql] = &[
                 &network_id,
                 &u64_to_sql(now_secs)?,
@@ -1647,7 +1737,10 @@ impl PeerDB {
             if allow_rows.len() >= (count as usize) {
                 // return a random subset
                 let allow_slice = allow_rows.as_mut_slice();
-                allow_slice.shuffle(&mut thread_rng());
+                match rng_opt {
+                    Some(rng) => allow_slice.shuffle(rng),
+                    None => allow_slice.shuffle(&mut thread_rng()),
+                }
                 return Ok(allow_slice[0..(count as usize)].to_vec());
             }
 
@@ -1658,14 +1751,15 @@ impl PeerDB {
         }
 
         // fill in with non-allowed, randomly-chosen, fresh peers
-        let random_peers_qry = if always_include_allowed {
+        let random_peers_filter = if always_include_allowed {
             "SELECT * FROM frontier WHERE network_id = ?1 AND last_contact_time >= ?2 AND ?3 < expire_block_height AND denied < ?4 AND \
-                 (allowed >= 0 AND allowed <= ?5) AND (peer_version & 0x000000ff) >= ?6 ORDER BY RANDOM() LIMIT ?7"
+                 (allowed >= 0 AND allowed <= ?5) AND (peer_version & 0x000000ff) >= ?6"
         } else {
             "SELECT * FROM frontier WHERE network_id = ?1 AND last_contact_time >= ?2 AND ?3 < expire_block_height AND denied < ?4 AND \
-                 (allowed < 0 OR (allowed >= 0 AND allowed <= ?5)) AND (peer_version & 0x000000ff) >= ?6 ORDER BY RANDOM() LIMIT ?7"
+                 (allowed < 0 OR (allowed >= 0 AND allowed <= ?5)) AND (peer_version & 0x000000ff) >= ?6"
         };
 
+        let num_random_peers = count - (ret.len() as u32);
         let random_peers_args: &[&dyn ToSql] = &[
             &network_id,
             &u64_to_sql(min_age)?,
@@ -1673,10 +1767,20 @@ impl PeerDB {
             &u64_to_sql(now_secs)?,
             &u64_to_sql(now_secs)?,
             &network_epoch,
-            &(count - (ret.len() as u32)),
+            &num_random_peers,
         ];
-        let mut random_peers =
-            query_rows::<Neighbor, _>(conn, &random_peers_qry, random_peers_args)?;
+        let mut random_peers = if let Some(rng) = rng_opt {
+            // sample all candidates in a fixed order
+            let random_peers_qry = format!("{} ORDER BY addrbytes, port", random_peers_filter);
+            let mut random_peers =
+                query_rows::<Neighbor, _>(conn, &random_peers_qry, &random_peers_args[0..6])?;
+            random_peers.shuffle(rng);
+            random_peers.truncate(num_random_peers as usize);
+            random_peers
+        } else {
+            let random_peers_qry = format!("{} ORDER BY RANDOM() LIMIT ?7", random_peers_filter);
+            query_rows::<Neighbor, _>(conn, &random_peers_qry, random_peers_args)?
+        };
 
         ret.append(&mut random_peers);
         Ok(ret)
@@ -1718,6 +1822,29 @@ impl PeerDB {
         )
     }
 
+    /// Same as `get_random_walk_neighbors()`, but sample with the given RNG so the result is
+    /// reproducible for a seeded RNG.
+    pub fn get_random_walk_neighbors_with_rng(
+        conn: &DBConn,
+        network_id: u32,
+        network_epoch: u8,
+        min_age: u64,
+        count: u32,
+        block_height: u64,
+        rng: &mut dyn RngCore,
+    ) -> Result<Vec<Neighbor>, db_error> {
+        PeerDB::inner_get_fresh_random_neighbors(
+            conn,
+            network_id,
+            network_epoch,
+            min_age,
+            count,
+            block_height,
+            false,
+            Some(rng),
+        )
+    }
+
     /// Add an IPv4 <--> ASN mapping
     /// Used during db instantiation
     fn asn4_insert(tx: &Transaction, asn4: &ASEntry4) -> Result<(), db_error> {
@@ -1782,6 +1909,103 @@ impl PeerDB {
         Ok(count as u64)
     }
 
+    /// Count the number of neighbors we know about in the given network
+    pub fn count_neighbors(&self, network_id: u32) -> Result<u64, db_error> {
+        let qry = "SELECT COUNT(*) FROM frontier WHERE network_id = ?1";
+        let args = [&network_id as &dyn ToSql];
+        let count = query_count(self.conn(), &qry, &args)?;
+        Ok(count as u64)
+    }
+
+    /// Delete all neighbors in the given network whose keys expired before the given burnchain
+    /// block height, as well as their stacker DB contracts.  Initial (bootstrap) neighbors,
+    /// allowed neighbors, and neighbors that are still denied are never deleted, so the
+    /// operator's allow/deny configuration survives pruning.
+    /// Returns the number of neighbors deleted.
+    pub fn prune_expired(&mut self, network_id: u32, before_block: u64) -> Result<u64, db_error> {
+        let now_secs = util::get_epoch_time_secs();
+        let tx = self.tx_begin()?;
+        let args: &[&dyn ToSql] = &[
+            &network_id,
+            &u64_to_sql(before_block)?,
+            &u64_to_sql(now_secs)?,
+        ];
+        let slots: Vec<u32> = query_rows(
+            &tx,
+            "SELECT slot FROM frontier WHERE network_id = ?1 AND expire_block_height < ?2 AND initial = 0 AND \
+                 (allowed >= 0 AND allowed <= ?3) AND (denied >= 0 AND denied < ?3)",
+            args,
+        )?;
+        for slot in slots.iter() {
+            Self::drop_stacker_dbs(&tx, *slot)?;
+        }
+        let num_deleted = tx
+            .execute(
+                "DELETE FROM frontier WHERE network_id = ?1 AND expire_block_height < ?2 AND initial = 0 AND \
+                     (allowed >= 0 AND allowed <= ?3) AND (denied >= 0 AND denied < ?3)",
+                args,
+            )
+            .map_err(db_error::SqliteError)?;
+        tx.commit().map_err(db_error::SqliteError)?;
+
+        debug!(
+            "Pruned {} neighbors in network {} that expired before block {}",
+            num_deleted, network_id, before_block
+        );
+        Ok(num_deleted as u64)
+    }
+
+    /// Export all neighbors in the given network, so they can be serialized to JSON
+    pub fn export_neighbors(&self, network_id: u32) -> Result<Vec<NeighborJson>, db_error> {
+        let qry = "SELECT * FROM frontier WHERE network_id = ?1 ORDER BY addrbytes ASC, port ASC";
+        let args = [&network_id as &dyn ToSql];
+        let neighbors = query_rows::<Neighbor, _>(self.conn(), &qry, &args)?;
+        Ok(neighbors.iter().map(NeighborJson::from_neighbor).collect())
+    }
+
+    /// Import neighbors into the given network.  Neighbors that are already present are skipped,
+    /// as are neighbors for which there are no free slots.  All neighbors are validated before
+    /// any are inserted.
+    /// Returns the number of neighbors inserted.
+    pub fn import_neighbors(
+        &mut self,
+        network_id: u32,
+        neighbors: &[NeighborJson],
+    ) -> Result<u64, db_error> {
+        let neighbors = neighbors
+            .iter()
+            .map(|neighbor_json| neighbor_json.to_neighbor(network_id))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let tx = self.tx_begin()?;
+        let mut num_inserted = 0;
+        for neighbor in neighbors.iter() {
+            if PeerDB::has_peer(
+                &tx,
+                network_id,
+                &neighbor.addr.addrbytes,
+                neighbor.addr.port,
+            )? {
+                continue;
+            }
+            let slots = PeerDB::peer_slots(
+                &tx,
+                network_id,
+                &neighbor.addr.addrbytes,
+                neighbor.addr.port,
+            )?;
+            for slot in slots.iter() {
+                if !PeerDB::has_peer_at(&tx, network_id, *slot)? {
+                    PeerDB::insert_or_replace_peer(&tx, neighbor, *slot)?;
+                    num_inserted += 1;
+                    break;
+                }
+            }
+        }
+        tx.commit().map_err(db_error::SqliteError)?;
+        Ok(num_inserted)
+    }
+
     pub fn get_all_peers(conn: &DBConn) -> Result<Vec<Neighbor>, db_error> {
         let qry = "SELECT * FROM frontier ORDER BY addrbytes ASC, port ASC";
         let rows = query_rows::<Neighbor, _>(conn, &qry, NO_PARAMS)?;
@@ -1964,6 +2188,251 @@ mod test {
         }
     }
 
+    /// Verify that PeerDB::prune_expired() deletes only non-initial, non-allowed, non-denied peers
+    /// in the given network whose keys have expired, and that PeerDB::count_neighbors() tracks
+    /// the number of stored peers.
+    #[test]
+    fn test_count_and_prune_expired_neighbors() {
+        let make_neighbor = |network_id: u32, port: u16, expire_block: u64| Neighbor {
+            addr: NeighborKey {
+                peer_version: 0x12345678,
+                network_id,
+                addrbytes: PeerAddress::from_ipv4(1, 2, 3, 4),
+                port,
+            },
+            public_key: Secp256k1PublicKey::from_hex(
+                "02fa66b66f8971a8cd4d20ffded09674e030f0f33883f337f34b95ad4935bac0e3",
+            )
+            .unwrap(),
+            expire_block,
+            last_contact_time: 1552509642,
+            allowed: 0,
+            denied: 0,
+            asn: 34567,
+            org: 45678,
+            in_degree: 1,
+            out_degree: 1,
+        };
+
+        let now_secs = get_epoch_time_secs() as i64;
+
+        let expired = make_neighbor(0x9abcdef0, 12345, 100);
+        let expired_initial = make_neighbor(0x9abcdef0, 12346, 100);
+        let live = make_neighbor(0x9abcdef0, 12347, 1000);
+        let expired_always_allowed = Neighbor {
+            allowed: -1,
+            ..make_neighbor(0x9abcdef0, 12348, 100)
+        };
+        let expired_still_allowed = Neighbor {
+            allowed: now_secs + 3600,
+            ..make_neighbor(0x9abcdef0, 12349, 100)
+        };
+        let expired_still_denied = Neighbor {
+            denied: now_secs + 3600,
+            ..make_neighbor(0x9abcdef0, 12350, 100)
+        };
+        let expired_always_denied = Neighbor {
+            denied: -1,
+            ..make_neighbor(0x9abcdef0, 12351, 100)
+        };
+        let expired_other_network = make_neighbor(0x9abcdef1, 12352, 100);
+
+        let mut db = PeerDB::connect_memory(
+            0x9abcdef0,
+            12345,
+            0,
+            "http://foo.com".into(),
+            &vec![],
+            &vec![],
+        )
+        .unwrap();
+
+        assert_eq!(db.count_neighbors(0x9abcdef0).unwrap(), 0);
+
+        {
+            let tx = db.tx_begin().unwrap();
+            for (slot, neighbor) in [
+                &expired,
+                &expired_initial,
+                &live,
+                &expired_always_allowed,
+                &expired_still_allowed,
+                &expired_still_denied,
+                &expired_always_denied,
+                &expired_other_network,
+            ]
+            .iter()
+            .enumerate()
+            {
+                PeerDB::insert_or_replace_peer(&tx, neighbor, slot as u32).unwrap();
+            }
+            PeerDB::set_initial_peer(
+                &tx,
+                0x9abcdef0,
+                &expired_initial.addr.addrbytes,
+                expired_initial.addr.port,
+            )
+            .unwrap();
+            tx.commit().unwrap();
+        }
+
+        assert_eq!(db.count_neighbors(0x9abcdef0).unwrap(), 7);
+        assert_eq!(db.count_neighbors(0x9abcdef1).unwrap(), 1);
+        assert_eq!(db.count_neighbors(0x9abcdef2).unwrap(), 0);
+
+        // nothing expired before block 100
+        assert_eq!(db.prune_expired(0x9abcdef0, 100).unwrap(), 0);
+        assert_eq!(db.count_neighbors(0x9abcdef0).unwrap(), 7);
+
+        // only the non-initial, non-allowed, non-denied expired peer gets removed
+        assert_eq!(db.prune_expired(0x9abcdef0, 500).unwrap(), 1);
+        assert_eq!(db.count_neighbors(0x9abcdef0).unwrap(), 6);
+
+        for (neighbor, present) in [
+            (&expired, false),
+            (&expired_initial, true),
+            (&live, true),
+            (&expired_always_allowed, true),
+            (&expired_still_allowed, true),
+            (&expired_still_denied, true),
+            (&expired_always_denied, true),
+            (&expired_other_network, true),
+        ] {
+            let neighbor_opt = PeerDB::get_peer(
+                db.conn(),
+                neighbor.addr.network_id,
+                &neighbor.addr.addrbytes,
+                neighbor.addr.port,
+            )
+            .unwrap();
+            assert_eq!(neighbor_opt.is_some(), present);
+        }
+
+        // idempotent
+        assert_eq!(db.prune_expired(0x9abcdef0, 500).unwrap(), 0);
+
+        // live peer expires too
+        assert_eq!(db.prune_expired(0x9abcdef0, 1001).unwrap(), 1);
+        assert_eq!(db.count_neighbors(0x9abcdef0).unwrap(), 5);
+
+        // other networks are only pruned when asked for
+        assert_eq!(db.count_neighbors(0x9abcdef1).unwrap(), 1);
+        assert_eq!(db.prune_expired(0x9abcdef1, 500).unwrap(), 1);
+        assert_eq!(db.count_neighbors(0x9abcdef1).unwrap(), 0);
+    }
+
+    /// Verify that neighbors exported from one PeerDB as JSON can be imported into another, that
+    /// importing is idempotent, and that malformed neighbors are rejected.
+    #[test]
+    fn test_export_import_neighbors() {
+        let make_neighbor = |addrbytes: PeerAddress, port: u16| Neighbor {
+            addr: NeighborKey {
+                peer_version: 0x12345678,
+                network_id: 0x9abcdef0,
+                addrbytes,
+                port,
+            },
+            public_key: Secp256k1PublicKey::from_hex(
+                "02fa66b66f8971a8cd4d20ffded09674e030f0f33883f337f34b95ad4935bac0e3",
+            )
+            .unwrap(),
+            expire_block: 23456,
+            last_contact_time: 0,
+            allowed: 0,
+            denied: 0,
+            asn: 34567,
+            org: 45678,
+            in_degree: 1,
+            out_degree: 2,
+        };
+
+        let neighbors = vec![
+            make_neighbor(PeerAddress::from_ipv4(1, 2, 3, 4), 12345),
+            make_neighbor(
+                PeerAddress([
+                    0x20, 0x01, 0x0d, 0xb8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x01,
+                ]),
+                12346,
+            ),
+        ];
+
+        let mut db_1 = PeerDB::connect_memory(
+            0x9abcdef0,
+            12345,
+            0,
+            "http://foo.com".into(),
+            &vec![],
+            &vec![],
+        )
+        .unwrap();
+        let mut db_2 = PeerDB::connect_memory(
+            0x9abcdef0,
+            12345,
+            0,
+            "http://foo.com".into(),
+            &vec![],
+            &vec![],
+        )
+        .unwrap();
+
+        {
+            let tx = db_1.tx_begin().unwrap();
+            for neighbor in neighbors.iter() {
+                assert!(PeerDB::try_insert_peer(&tx, neighbor, &[]).unwrap());
+            }
+            tx.commit().unwrap();
+        }
+
+        let exported = db_1.export_neighbors(0x9abcdef0).unwrap();
+        assert_eq!(exported.len(), 2);
+        assert_eq!(exported[0].addr, "1.2.3.4");
+        assert_eq!(exported[1].addr, "2001:db8::1");
+        assert_eq!(db_1.export_neighbors(0x9abcdef1).unwrap(), vec![]);
+
+        let exported_json = serde_json::to_string(&exported).unwrap();
+        let imported: Vec<NeighborJson> = serde_json::from_str(&exported_json).unwrap();
+        assert_eq!(imported, exported);
+
+        assert_eq!(db_2.import_neighbors(0x9abcdef0, &imported).unwrap(), 2);
+        assert_eq!(db_2.export_neighbors(0x9abcdef0).unwrap(), exported);
+        for neighbor in neighbors.iter() {
+            let neighbor_opt = PeerDB::get_peer(
+                db_2.conn(),
+                0x9abcdef0,
+                &neighbor.addr.addrbytes,
+                neighbor.addr.port,
+            )
+            .unwrap();
+            assert_eq!(neighbor_opt.as_ref(), Some(neighbor));
+        }
+
+        // idempotent
+        assert_eq!(db_2.import_neighbors(0x9abcdef0, &imported).unwrap(), 0);
+        assert_eq!(db_2.count_neighbors(0x9abcdef0).unwrap(), 2);
+
+        // bad public key or address means nothing gets imported
+        let mut db_3 = PeerDB::connect_memory(
+            0x9abcdef0,
+            12345,
+            0,
+            "http://foo.com".into(),
+            &vec![],
+            &vec![],
+        )
+        .unwrap();
+
+        let mut bad_key = imported.clone();
+        bad_key[1].public_key = "02fa66b66f".into();
+        assert!(db_3.import_neighbors(0x9abcdef0, &bad_key).is_err());
+
+        let mut bad_addr = imported.clone();
+        bad_addr[1].addr = "1.2.3".into();
+        assert!(db_3.import_neighbors(0x9abcdef0, &bad_addr).is_err());
+
+        assert_eq!(db_3.count_neighbors(0x9abcdef0).unwrap(), 0);
+    }
+
     /// Verify that PeerDB::insert_or_replace_peer() will maintain each peer's stacker DB contract
     /// IDs. New peers' contract IDs get added, and dropped peers' contract IDs get removed.
     #[test]
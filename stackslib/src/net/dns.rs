@@ -15,8 +15,9 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
 use std::hash::{Hash, Hasher};
-use std::net::{SocketAddr, ToSocketAddrs};
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
 use std::sync::mpsc::{
     sync_channel, Receiver, RecvError, RecvTimeoutError, SyncSender, TryRecvError, TrySendError,
 };
@@ -90,6 +91,51 @@ impl DNSResponse {
     }
 }
 
+/// A source of host name resolutions.  The DNSResolver thread consults one of these to answer
+/// queries from the DNSClient, so tests and air-gapped nodes can swap in a static hosts map
+/// instead of the system resolver.  Implementations may block.
+pub trait DnsResolver: Send + fmt::Debug {
+    /// Resolve `host` to its IP addresses.
+    fn resolve(&mut self, host: &str) -> Result<Vec<IpAddr>, net_error>;
+}
+
+/// Resolves names with the operating system's resolver (i.e. getaddrinfo).
+#[derive(Debug, Clone, Default)]
+pub struct SystemDnsResolver;
+
+impl DnsResolver for SystemDnsResolver {
+    fn resolve(&mut self, host: &str) -> Result<Vec<IpAddr>, net_error> {
+        let addrs = (host, 0)
+            .to_socket_addrs()
+            .map_err(|ioe| net_error::LookupError(format!("DNS resolve error: {:?}", &ioe)))?;
+        Ok(addrs.map(|addr| addr.ip()).collect())
+    }
+}
+
+/// Resolves names from a fixed hosts map, and fails on any name not in it.
+#[derive(Debug, Clone, Default)]
+pub struct StaticDnsResolver {
+    hosts: HashMap<String, Vec<IpAddr>>,
+}
+
+impl StaticDnsResolver {
+    pub fn new(hosts: HashMap<String, Vec<IpAddr>>) -> StaticDnsResolver {
+        StaticDnsResolver { hosts }
+    }
+
+    pub fn add_host(&mut self, host: &str, addrs: Vec<IpAddr>) -> () {
+        self.hosts.insert(host.to_string(), addrs);
+    }
+}
+
+impl DnsResolver for StaticDnsResolver {
+    fn resolve(&mut self, host: &str) -> Result<Vec<IpAddr>, net_error> {
+        self.hosts.get(host).cloned().ok_or_else(|| {
+            net_error::LookupError(format!("DNS resolve error: no static entry for {}", host))
+        })
+    }
+}
+
 /// The DNSResolver runs as a background thread in the node. In a loop, it collects inbound requests,
 /// then tries to resolve the valid requests.
 #[derive(Debug)]
@@ -101,6 +147,9 @@ pub struct DNSResolver {
 
     // used mainly for testing
     hardcoded: HashMap<(String, u16), Vec<SocketAddr>>,
+
+    /// where names not in `hardcoded` get resolved
+    backend: Box<dyn DnsResolver>,
 }
 
 /// The DNSClient provides an API to send DNS requests and poll DNS results. The client forwards
@@ -114,6 +163,14 @@ pub struct DNSClient {
 
 impl DNSResolver {
     pub fn new(max_inflight: u64) -> (DNSResolver, DNSClient) {
+        DNSResolver::new_with_backend(max_inflight, Box::new(SystemDnsResolver))
+    }
+
+    /// Create a resolver/client pair whose resolver answers queries with `backend`
+    pub fn new_with_backend(
+        max_inflight: u64,
+        backend: Box<dyn DnsResolver>,
+    ) -> (DNSResolver, DNSClient) {
         let (dns_chan_tx, dns_chan_rx) = sync_channel(1024);
         let (socket_chan_tx, socket_chan_rx) = sync_channel(1024);
 
@@ -124,6 +181,7 @@ impl DNSResolver {
             outbound: dns_chan_tx,
             max_inflight: max_inflight,
             hardcoded: HashMap::new(),
+            backend,
         };
         (resolver, client)
     }
@@ -132,7 +190,7 @@ impl DNSResolver {
         self.hardcoded.insert((host.to_string(), port), addrs);
     }
 
-    pub fn resolve(&self, req: DNSRequest) -> DNSResponse {
+    pub fn resolve(&mut self, req: DNSRequest) -> DNSResponse {
         if let Some(ref addrs) = self.hardcoded.get(&(req.host.clone(), req.port)) {
             return DNSResponse::new(req, Ok(addrs.to_vec()));
         }
@@ -140,16 +198,13 @@ impl DNSResolver {
         // TODO: this is a blocking operation, but there's not really a good solution here other
         // than to just do this in a separate thread :shrug:
         test_debug!("Resolve {}:{}", &req.host, req.port);
-        let addrs: Vec<SocketAddr> = match (req.host.as_str(), req.port).to_socket_addrs() {
-            Ok(iter) => {
-                let mut list = vec![];
-                for addr in iter {
-                    list.push(addr);
-                }
-                list
-            }
-            Err(ioe) => {
-                return DNSResponse::error(req, format!("DNS resolve error: {:?}", &ioe));
+        let addrs: Vec<SocketAddr> = match self.backend.resolve(&req.host) {
+            Ok(ips) => ips
+                .into_iter()
+                .map(|ip| SocketAddr::new(ip, req.port))
+                .collect(),
+            Err(e) => {
+                return DNSResponse::error(req, e.to_string());
             }
         };
 
@@ -359,9 +414,12 @@ impl DNSClient {
 mod test {
     use std::collections::HashMap;
     use std::error::Error;
+    use std::net::{IpAddr, SocketAddr};
+    use std::thread;
 
     use stacks_common::util::*;
 
+    use super::*;
     use crate::net::test::*;
 
     #[test]
@@ -371,6 +429,55 @@ mod test {
         thread_handle.join().unwrap();
     }
 
+    #[test]
+    fn dns_resolve_static_hosts() {
+        let mut backend = StaticDnsResolver::default();
+        backend.add_host(
+            "node.example",
+            vec![
+                "127.0.0.1".parse().unwrap(),
+                "::1".parse::<IpAddr>().unwrap(),
+            ],
+        );
+        let (mut resolver, mut client) = DNSResolver::new_with_backend(100, Box::new(backend));
+        let thread_handle = thread::spawn(move || resolver.thread_main());
+
+        for host in ["node.example", "missing.example"] {
+            client
+                .queue_lookup(host, 20443, get_epoch_time_ms() + 120_000)
+                .unwrap();
+        }
+
+        let mut results = HashMap::new();
+        while results.len() < 2 {
+            client.try_recv().unwrap();
+            for host in ["node.example", "missing.example"] {
+                if results.contains_key(host) {
+                    continue;
+                }
+                if let Some(resp) = client.poll_lookup(host, 20443).unwrap() {
+                    results.insert(host, resp.result);
+                }
+            }
+            sleep_ms(100);
+        }
+
+        assert_eq!(
+            results.remove("node.example").unwrap().unwrap(),
+            vec![
+                "127.0.0.1:20443".parse::<SocketAddr>().unwrap(),
+                "[::1]:20443".parse::<SocketAddr>().unwrap(),
+            ]
+        );
+        assert!(results
+            .remove("missing.example")
+            .unwrap()
+            .unwrap_err()
+            .contains("DNS resolve error"));
+
+        dns_thread_shutdown(client, thread_handle);
+    }
+
     #[test]
     fn dns_resolve_one_name() {
         let (mut client, thread_handle) = dns_thread_start(100);
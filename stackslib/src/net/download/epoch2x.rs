@@ -209,7 +209,11 @@ pub struct BlockDownloader {
     pub finished_scan_at: u64,
     last_inv_update_at: u64,
 
-    /// Maximum number of concurrent requests
+    /// When was the last time we downloaded a block or microblock stream?
+    pub last_download_at: u64,
+
+    /// Maximum number of concurrent requests, across all peers.  Missing blocks beyond this
+    /// many sortition heights stay queued until the current batch of requests finishes.
     max_inflight_requests: u64,
 
     /// Block requests to try, grouped by block, keyed by sortition height
@@ -266,6 +270,7 @@ impl BlockDownloader {
             empty_microblock_download_passes: 0,
             finished_scan_at: 0,
             last_inv_update_at: 0,
+            last_download_at: 0,
 
             max_inflight_requests: max_inflight_requests,
             blocks_to_try: HashMap::new(),
@@ -979,6 +984,13 @@ impl BlockDownloader {
         self.microblocks_to_try.len() + self.blocks_to_try.len()
     }
 
+    /// How many block and microblock HTTP requests are outstanding right now?
+    /// This never exceeds `max_inflight_requests`, since each scheduled sortition height has at
+    /// most one outstanding request and at most `max_inflight_requests` heights are scheduled.
+    pub fn num_http_requests_inflight(&self) -> usize {
+        self.getblock_requests.len() + self.getmicroblocks_requests.len()
+    }
+
     // is the downloader idle? i.e. did we already do a scan?
     pub fn is_download_idle(&self) -> bool {
         self.empty_block_download_passes > 0 && self.empty_microblock_download_passes > 0
@@ -2094,6 +2106,7 @@ impl PeerNetwork {
                     now.saturating_sub(request_key.download_start),
                 ));
                 downloader.num_blocks_downloaded += 1;
+                downloader.last_download_at = get_epoch_time_secs();
 
                 // don't try this again
                 downloader
@@ -2153,6 +2166,7 @@ impl PeerNetwork {
                         now.saturating_sub(request_key.download_start),
                     ));
                     downloader.num_microblocks_downloaded += 1;
+                    downloader.last_download_at = get_epoch_time_secs();
                 } else {
                     // stream is not well-formed
                     debug!(
@@ -0,0 +1,178 @@
+// Copyright (C) 2024 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::cmp;
+use std::collections::HashSet;
+
+use stacks_common::util::get_epoch_time_secs;
+
+use crate::net::db::PeerDB;
+use crate::net::p2p::PeerNetwork;
+
+/// If the last successful neighbor walk was longer ago than this, then it's reported.
+pub const NEIGHBOR_WALK_STALE_SECS: u64 = 3600;
+/// If the block downloader has had work to do but made no progress for this long, then it's
+/// reported.
+pub const BLOCK_DOWNLOAD_STALL_SECS: u64 = 1800;
+
+/// Kinds of problems that `PeerNetwork::health_check()` can find
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HealthWarningCode {
+    /// We have no outbound peers
+    NoOutboundPeers,
+    /// All of our peers are in the same autonomous system
+    SingleASN,
+    /// We haven't completed a neighbor walk in a while
+    NoRecentNeighborWalk,
+    /// The block downloader has work to do, but isn't making progress
+    BlockDownloadStalled,
+    /// The HTTP server can't take any more clients
+    HttpAtCapacity,
+    /// Our p2p key has expired, but has not been replaced
+    RekeyOverdue,
+}
+
+impl HealthWarningCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::NoOutboundPeers => "no_outbound_peers",
+            Self::SingleASN => "single_asn",
+            Self::NoRecentNeighborWalk => "no_recent_neighbor_walk",
+            Self::BlockDownloadStalled => "block_download_stalled",
+            Self::HttpAtCapacity => "http_at_capacity",
+            Self::RekeyOverdue => "rekey_overdue",
+        }
+    }
+}
+
+/// An actionable problem with the peer network
+#[derive(Debug, Clone, PartialEq)]
+pub struct HealthWarning {
+    pub code: HealthWarningCode,
+    pub message: String,
+}
+
+impl HealthWarning {
+    pub fn new(code: HealthWarningCode, message: String) -> HealthWarning {
+        HealthWarning { code, message }
+    }
+}
+
+impl PeerNetwork {
+    /// Check the peer network for actionable problems.
+    /// Returns the list of warnings, ordered from most to least severe.  An empty list means
+    /// nothing is wrong.
+    pub fn health_check(&self) -> Vec<HealthWarning> {
+        let now = get_epoch_time_secs();
+        let mut warnings = vec![];
+
+        let mut num_outbound = 0;
+        let mut asns = HashSet::new();
+        let mut num_with_asn = 0;
+        for convo in self.peers.values() {
+            if convo.is_outbound() {
+                num_outbound += 1;
+            }
+            let nk = convo.to_neighbor_key();
+            let Ok(Some(peer)) =
+                PeerDB::get_peer(self.peerdb.conn(), nk.network_id, &nk.addrbytes, nk.port)
+            else {
+                continue;
+            };
+            if peer.asn != 0 {
+                asns.insert(peer.asn);
+                num_with_asn += 1;
+            }
+        }
+
+        if num_outbound == 0 {
+            warnings.push(HealthWarning::new(
+                HealthWarningCode::NoOutboundPeers,
+                "Not connected to any outbound peers; check that this node can reach the network"
+                    .into(),
+            ));
+        }
+
+        if num_with_asn > 1 && asns.len() == 1 {
+            warnings.push(HealthWarning::new(
+                HealthWarningCode::SingleASN,
+                format!(
+                    "All {} peers with a known ASN are in ASN {}; this node is vulnerable to eclipse attacks",
+                    num_with_asn,
+                    asns.iter().next().expect("BUG: no ASN")
+                ),
+            ));
+        }
+
+        if self.walk_last_success_ts > 0 {
+            let since = now.saturating_sub(self.walk_last_success_ts);
+            if since > NEIGHBOR_WALK_STALE_SECS {
+                warnings.push(HealthWarning::new(
+                    HealthWarningCode::NoRecentNeighborWalk,
+                    format!("No neighbor walk has completed in {} seconds", since),
+                ));
+            }
+        } else if self.walk_attempts > 0 {
+            warnings.push(HealthWarning::new(
+                HealthWarningCode::NoRecentNeighborWalk,
+                format!(
+                    "No neighbor walk has completed after {} attempts",
+                    self.walk_attempts
+                ),
+            ));
+        }
+
+        if let Some(downloader) = self.block_downloader.as_ref() {
+            let last_progress = cmp::max(downloader.last_download_at, downloader.finished_scan_at);
+            if !downloader.is_download_idle() && last_progress > 0 {
+                let since = now.saturating_sub(last_progress);
+                if since > BLOCK_DOWNLOAD_STALL_SECS {
+                    warnings.push(HealthWarning::new(
+                        HealthWarningCode::BlockDownloadStalled,
+                        format!(
+                            "Block downloader has not made progress in {} seconds",
+                            since
+                        ),
+                    ));
+                }
+            }
+        }
+
+        if let Some(http) = self.http.as_ref() {
+            let num_http = http.peers.len() as u64;
+            if num_http >= self.connection_opts.max_http_clients {
+                warnings.push(HealthWarning::new(
+                    HealthWarningCode::HttpAtCapacity,
+                    format!(
+                        "HTTP server has {} clients, at its limit of {}; consider raising max_http_clients",
+                        num_http, self.connection_opts.max_http_clients
+                    ),
+                ));
+            }
+        }
+
+        if self.local_peer.private_key_expire < self.chain_view.burn_block_height {
+            warnings.push(HealthWarning::new(
+                HealthWarningCode::RekeyOverdue,
+                format!(
+                    "Node key expired at burn height {}, but the chain is at {}",
+                    self.local_peer.private_key_expire, self.chain_view.burn_block_height
+                ),
+            ));
+        }
+
+        warnings
+    }
+}
@@ -373,3 +373,55 @@ impl HttpErrorResponse for HttpError {
         try_parse_error_response(preamble.status_code, preamble.content_type, body)
     }
 }
+
+/// An HTTP error whose body is a uniform JSON envelope of the form
+/// `{"error": <message>, "code": <status code>, "path": <request path>}`.
+/// Used in place of an endpoint's own error body when the node is configured to send JSON
+/// error responses.
+pub struct HttpErrorEnvelope {
+    error: u16,
+    error_text: String,
+    path: String,
+}
+
+impl HttpErrorEnvelope {
+    pub fn new(error: u16, error_text: String, path: String) -> Self {
+        Self {
+            error,
+            error_text,
+            path,
+        }
+    }
+
+    /// Wrap an error response body, keeping its status code and message
+    pub fn from_payload(error: u16, payload: &HttpResponsePayload, path: String) -> Self {
+        let error_text = match payload {
+            HttpResponsePayload::Empty => http_reason(error).to_string(),
+            HttpResponsePayload::Text(text) => text.clone(),
+            HttpResponsePayload::JSON(serde_json::Value::String(text)) => text.clone(),
+            HttpResponsePayload::JSON(value) => value.to_string(),
+            HttpResponsePayload::Bytes(bytes) => String::from_utf8_lossy(bytes).to_string(),
+        };
+        Self::new(error, error_text, path)
+    }
+}
+
+impl HttpErrorResponse for HttpErrorEnvelope {
+    fn code(&self) -> u16 {
+        self.error
+    }
+    fn payload(&self) -> HttpResponsePayload {
+        HttpResponsePayload::JSON(serde_json::json!({
+            "error": self.error_text,
+            "code": self.error,
+            "path": self.path,
+        }))
+    }
+    fn try_parse_response(
+        &self,
+        preamble: &HttpResponsePreamble,
+        body: &[u8],
+    ) -> Result<HttpResponsePayload, Error> {
+        try_parse_error_response(preamble.status_code, preamble.content_type, body)
+    }
+}
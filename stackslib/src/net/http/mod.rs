@@ -37,9 +37,9 @@ pub use crate::net::http::common::{
     HTTP_PREAMBLE_MAX_NUM_HEADERS,
 };
 pub use crate::net::http::error::{
-    http_error_from_code_and_text, http_reason, HttpBadRequest, HttpError, HttpErrorResponse,
-    HttpForbidden, HttpNotFound, HttpPaymentRequired, HttpServerError, HttpServiceUnavailable,
-    HttpUnauthorized,
+    http_error_from_code_and_text, http_reason, try_parse_error_response, HttpBadRequest,
+    HttpError, HttpErrorEnvelope, HttpErrorResponse, HttpForbidden, HttpNotFound,
+    HttpPaymentRequired, HttpServerError, HttpServiceUnavailable, HttpUnauthorized,
 };
 pub use crate::net::http::request::{
     HttpRequest, HttpRequestContents, HttpRequestPayload, HttpRequestPreamble,
@@ -17,13 +17,15 @@
 /// This module binds the http library to Stacks as a `ProtocolFamily` implementation
 use std::collections::{BTreeMap, HashMap};
 use std::io::{Read, Write};
-use std::net::SocketAddr;
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
 use std::{fmt, io, mem};
 
 use clarity::vm::costs::ExecutionCost;
 use clarity::vm::types::{QualifiedContractIdentifier, BOUND_VALUE_SERIALIZATION_HEX};
 use clarity::vm::{ClarityName, ContractName};
 use percent_encoding::percent_decode_str;
+use rand::{thread_rng, Rng};
 use regex::{Captures, Regex};
 use stacks_common::codec::{read_next, Error as CodecError, StacksMessageCodec, MAX_MESSAGE_LEN};
 use stacks_common::types::chainstate::{
@@ -33,6 +35,7 @@ use stacks_common::types::net::PeerHost;
 use stacks_common::types::Address;
 use stacks_common::util::chunked_encoding::*;
 use stacks_common::util::get_epoch_time_ms;
+use stacks_common::util::hash::Hash160;
 use stacks_common::util::retry::{BoundReader, RetryReader};
 use url::Url;
 
@@ -46,13 +49,17 @@ use crate::core::{MemPoolDB, StacksEpoch};
 use crate::net::connection::ConnectionOptions;
 use crate::net::http::common::HTTP_PREAMBLE_MAX_ENCODED_SIZE;
 use crate::net::http::{
-    http_reason, Error as HttpError, HttpBadRequest, HttpContentType, HttpErrorResponse,
-    HttpNotFound, HttpRequest, HttpRequestContents, HttpRequestPreamble, HttpResponse,
-    HttpResponseContents, HttpResponsePayload, HttpResponsePreamble, HttpServerError, HttpVersion,
+    http_reason, try_parse_error_response, Error as HttpError, HttpBadRequest, HttpContentType,
+    HttpErrorEnvelope, HttpErrorResponse, HttpNotFound, HttpRequest, HttpRequestContents,
+    HttpRequestPreamble, HttpResponse, HttpResponseContents, HttpResponsePayload,
+    HttpResponsePreamble, HttpServerError, HttpVersion,
 };
 use crate::net::p2p::PeerNetwork;
 use crate::net::server::HttpPeer;
-use crate::net::{Error as NetError, MessageSequence, ProtocolFamily, StacksNodeState, UrlString};
+use crate::net::{
+    Error as NetError, MessageSequence, PeerHostExtensions, ProtocolFamily, StacksNodeState,
+    UrlString,
+};
 
 const CHUNK_BUF_LEN: usize = 32768;
 
@@ -403,7 +410,7 @@ pub trait RPCRequestHandler: HttpRequest + HttpResponse + RPCRequestHandlerClone
                     "Failed to get fee rate estimate because could not load Stacks epoch for canonical burn height = {}",
                     block_height
                 );
-                warn!("{}", &msg);
+                warn!("{}", &msg; "request_id" => preamble.get_request_id().unwrap_or(HTTP_REQUEST_ID_RESERVED));
                 StacksHttpResponse::new_error(&preamble, &HttpServerError::new(msg))
             })
     }
@@ -418,14 +425,14 @@ pub trait RPCRequestHandler: HttpRequest + HttpResponse + RPCRequestHandlerClone
         NakamotoChainState::get_canonical_block_header(chainstate.db(), sortdb)
             .map_err(|e| {
                 let msg = format!("Failed to load stacks chain tip header: {:?}", &e);
-                warn!("{}", &msg);
+                warn!("{}", &msg; "request_id" => preamble.get_request_id().unwrap_or(HTTP_REQUEST_ID_RESERVED));
                 StacksHttpResponse::new_error(&preamble, &HttpServerError::new(msg))
             })?
             .ok_or_else(|| {
                 let msg =
                     "No stacks tip exists yet. Perhaps no blocks have been processed by this node"
                         .to_string();
-                warn!("{}", &msg);
+                warn!("{}", &msg; "request_id" => preamble.get_request_id().unwrap_or(HTTP_REQUEST_ID_RESERVED));
                 StacksHttpResponse::new_error(&preamble, &HttpNotFound::new(msg))
             })
     }
@@ -540,12 +547,17 @@ impl StacksHttpRequest {
         self.preamble.headers.clear();
     }
 
+    /// Get this request's ID, or generate one and set it on this request if it does not have
+    /// one.  Either way, the request carries the returned ID afterwards.
+    pub fn get_or_set_request_id(&mut self) -> u32 {
+        get_or_set_request_id(&mut self.preamble)
+    }
+
     /// Destruct into parts
     pub fn destruct(self) -> (HttpRequestPreamble, HttpRequestContents) {
         (self.preamble, self.contents)
     }
 
-    #[cfg(test)]
     pub fn try_serialize(&self) -> Result<Vec<u8>, NetError> {
         let mut ret = vec![];
         self.send(&mut ret)?;
@@ -883,6 +895,18 @@ pub struct StacksHttp {
     pub read_only_call_limit: ExecutionCost,
     /// The authorization token to enable the block proposal RPC endpoint
     pub block_proposal_token: Option<String>,
+    /// Whether or not to send 404, 405, and 500 errors as a uniform JSON envelope
+    pub json_error_responses: bool,
+    /// Requests that take at least this many milliseconds to handle are logged as slow (0 to
+    /// disable)
+    pub slow_request_threshold_ms: u64,
+    /// Number of requests that have been logged as slow
+    #[cfg(test)]
+    pub num_slow_requests: u64,
+    /// Whether or not to serve the contract analysis endpoint
+    pub contract_analysis_endpoint: bool,
+    /// Maximum size of a contract analysis request body
+    pub maximum_contract_analysis_size: u32,
 }
 
 impl StacksHttp {
@@ -899,6 +923,12 @@ impl StacksHttp {
             maximum_call_argument_size: conn_opts.maximum_call_argument_size,
             read_only_call_limit: conn_opts.read_only_call_limit.clone(),
             block_proposal_token: conn_opts.block_proposal_token.clone(),
+            json_error_responses: conn_opts.json_error_responses,
+            slow_request_threshold_ms: conn_opts.slow_request_threshold_ms,
+            #[cfg(test)]
+            num_slow_requests: 0,
+            contract_analysis_endpoint: conn_opts.contract_analysis_endpoint,
+            maximum_contract_analysis_size: conn_opts.maximum_contract_analysis_size,
         };
         http.register_rpc_methods();
         http
@@ -932,6 +962,56 @@ impl StacksHttp {
         None
     }
 
+    /// Log a warning if a request took at least `slow_request_threshold_ms` to handle.
+    /// Returns true if the request was slow.
+    pub fn check_slow_request(
+        &self,
+        verb: &str,
+        path: &str,
+        request_id: u32,
+        elapsed: Duration,
+    ) -> bool {
+        if self.slow_request_threshold_ms == 0
+            || elapsed < Duration::from_millis(self.slow_request_threshold_ms)
+        {
+            return false;
+        }
+        warn!("Slow HTTP request";
+              "verb" => %verb,
+              "path" => %path,
+              "request_id" => request_id,
+              "duration_ms" => elapsed.as_millis(),
+              "threshold_ms" => self.slow_request_threshold_ms);
+        true
+    }
+
+    /// Should an error response with this status code be sent as a JSON error envelope?
+    fn use_error_envelope(&self, status_code: u16) -> bool {
+        self.json_error_responses && matches!(status_code, 404 | 405 | 500)
+    }
+
+    /// Make an HTTP error response to the given request.
+    /// If `json_error_responses` is set, then 404, 405, and 500 errors are sent as a JSON
+    /// envelope with the error message, status code, and request path.  Otherwise, the error's
+    /// own body is sent.
+    pub fn make_error_response(
+        &self,
+        preamble: &HttpRequestPreamble,
+        error: &dyn HttpErrorResponse,
+    ) -> StacksHttpResponse {
+        if !self.use_error_envelope(error.code()) {
+            return StacksHttpResponse::new_error(preamble, error);
+        }
+        let path = preamble
+            .path_and_query_str
+            .split('?')
+            .next()
+            .unwrap_or("")
+            .to_string();
+        let envelope = HttpErrorEnvelope::from_payload(error.code(), &error.payload(), path);
+        StacksHttpResponse::new_error(preamble, &envelope)
+    }
+
     /// Force the state machine to expect a response
     #[cfg(test)]
     pub fn set_response_handler(&mut self, request_verb: &str, request_path: &str) {
@@ -1011,12 +1091,25 @@ impl StacksHttp {
                 }
             };
 
-            debug!("Handle StacksHttpRequest"; "verb" => %verb, "peer_addr" => %self.peer_addr, "path" => %decoded_path, "query" => %query);
-            let request = StacksHttpRequest::new(preamble.clone(), payload);
+            let mut request = StacksHttpRequest::new(preamble.clone(), payload);
+            let request_id = request.get_or_set_request_id();
+            debug!("Handle StacksHttpRequest"; "verb" => %verb, "peer_addr" => %self.peer_addr, "path" => %decoded_path, "query" => %query, "request_id" => request_id);
             return Ok(request);
         }
 
         test_debug!("Failed to parse '{}'", &preamble.path_and_query_str);
+        if self.json_error_responses
+            && self
+                .request_handlers
+                .iter()
+                .any(|(_, regex, _)| regex.is_match(&decoded_path))
+        {
+            // the path exists, but not for this verb
+            return Err(NetError::Http(HttpError::Http(
+                405,
+                format!("Method not allowed: {}", &preamble.verb),
+            )));
+        }
         Err(NetError::Http(HttpError::Http(
             404,
             "No such file or directory".into(),
@@ -1086,9 +1179,34 @@ impl StacksHttp {
     /// Returns Ok((preamble, contents)) on success.  Note that this could be an HTTP error
     /// message.
     /// Returns Err(..) on failure to decode or generate the response.
+    /// The response echoes the request's `X-Request-Id` header, which is generated if the client
+    /// did not supply one.  Slow requests are logged (see `check_slow_request()`).
     pub fn try_handle_request(
+        &mut self,
+        mut request: StacksHttpRequest,
+        node: &mut StacksNodeState,
+    ) -> Result<(HttpResponsePreamble, HttpResponseContents), NetError> {
+        let request_id = request.get_or_set_request_id();
+        let verb = request.verb().to_string();
+        let (path, _) = decode_request_path(request.request_path())?;
+        let start_time = Instant::now();
+        let result = self.inner_try_handle_request(request, request_id, node);
+        if self.check_slow_request(&verb, &path, request_id, start_time.elapsed()) {
+            #[cfg(test)]
+            {
+                self.num_slow_requests += 1;
+            }
+        }
+        let (mut response_preamble, response_contents) = result?;
+        response_preamble.set_request_id(request_id);
+        Ok((response_preamble, response_contents))
+    }
+
+    /// Handle a HTTP request with the given request ID.
+    fn inner_try_handle_request(
         &mut self,
         request: StacksHttpRequest,
+        request_id: u32,
         node: &mut StacksNodeState,
     ) -> Result<(HttpResponsePreamble, HttpResponseContents), NetError> {
         let (decoded_path, _) = decode_request_path(&request.preamble().path_and_query_str)?;
@@ -1097,15 +1215,16 @@ impl StacksHttp {
             .or_else(|| self.find_response_handler(&request.preamble().verb, &decoded_path))
         else {
             // method not found
-            return StacksHttpResponse::new_error(
-                &request.preamble,
-                &HttpNotFound::new(format!(
-                    "No such API endpoint '{} {}'",
-                    &request.preamble().verb,
-                    &decoded_path
-                )),
-            )
-            .try_into_contents();
+            return self
+                .make_error_response(
+                    &request.preamble,
+                    &HttpNotFound::new(format!(
+                        "No such API endpoint '{} {}'",
+                        &request.preamble().verb,
+                        &decoded_path
+                    )),
+                )
+                .try_into_contents();
         };
 
         let (_, _, request_handler) = self
@@ -1120,14 +1239,37 @@ impl StacksHttp {
         let (response_preamble, response_contents) = match request_result {
             Ok((rp, rc)) => (rp, rc),
             Err(NetError::Http(e)) => {
-                return StacksHttpResponse::new_error(&request_preamble, &*e.into_http_error())
+                return self
+                    .make_error_response(&request_preamble, &*e.into_http_error())
                     .try_into_contents()
             }
             Err(e) => {
-                warn!("Irrecoverable error when handling request"; "path" => %request_preamble.path_and_query_str, "error" => %e);
+                warn!("Irrecoverable error when handling request"; "path" => %request_preamble.path_and_query_str, "request_id" => request_id, "error" => %e);
                 return Err(e);
             }
         };
+
+        // endpoints build their own error bodies, so re-wrap them if needed
+        if self.use_error_envelope(response_preamble.status_code) {
+            if let HttpResponseContents::RAM(ref body) = response_contents {
+                let payload = try_parse_error_response(
+                    response_preamble.status_code,
+                    response_preamble.content_type,
+                    body,
+                )
+                .unwrap_or_else(|_| HttpResponsePayload::Bytes(body.clone()));
+                let path = request_preamble
+                    .path_and_query_str
+                    .split('?')
+                    .next()
+                    .unwrap_or("")
+                    .to_string();
+                let envelope =
+                    HttpErrorEnvelope::from_payload(response_preamble.status_code, &payload, path);
+                return StacksHttpResponse::new_error(&request_preamble, &envelope)
+                    .try_into_contents();
+            }
+        }
         Ok((response_preamble, response_contents))
     }
 
@@ -1275,7 +1417,6 @@ impl StacksHttp {
     }
 
     /// Given a fully-formed single HTTP response, parse it (used by clients).
-    #[cfg(test)]
     pub fn parse_response(
         verb: &str,
         request_path: &str,
@@ -1368,13 +1509,18 @@ impl ProtocolFamily for StacksHttp {
 
         let mut cursor = io::Cursor::new(buf);
 
-        let preamble = {
+        let mut preamble = {
             let mut rd =
                 BoundReader::from_reader(&mut cursor, HTTP_PREAMBLE_MAX_ENCODED_SIZE as u64);
             let preamble: StacksHttpPreamble = read_next(&mut rd)?;
             preamble
         };
 
+        // give each inbound request its ID once, so all handling of it logs the same ID
+        if let StacksHttpPreamble::Request(ref mut http_request_preamble) = preamble {
+            get_or_set_request_id(http_request_preamble);
+        }
+
         let preamble_len = cursor.position() as usize;
         self.set_preamble(&preamble)?;
 
@@ -1499,14 +1645,21 @@ impl ProtocolFamily for StacksHttp {
 
                 trace!("read http request payload of {} bytes", len);
 
+                let request_id = http_request_preamble
+                    .get_request_id()
+                    .unwrap_or(HTTP_REQUEST_ID_RESERVED);
                 match self.try_parse_request(http_request_preamble, &buf[0..len]) {
                     Ok(data_request) => Ok((StacksHttpMessage::Request(data_request), len)),
                     Err(NetError::Http(http_error)) => {
-                        // convert into a response
-                        let resp = StacksHttpResponse::new_error(
-                            http_request_preamble,
-                            &*http_error.into_http_error(),
-                        );
+                        // convert into a response, which echoes the request ID
+                        let (mut resp_preamble, resp_body) = self
+                            .make_error_response(
+                                http_request_preamble,
+                                &*http_error.into_http_error(),
+                            )
+                            .destruct();
+                        resp_preamble.set_request_id(request_id);
+                        let resp = StacksHttpResponse::new(resp_preamble, resp_body);
                         self.reset();
                         return Ok((
                             StacksHttpMessage::Error(
@@ -1517,7 +1670,7 @@ impl ProtocolFamily for StacksHttp {
                         ));
                     }
                     Err(e) => {
-                        info!("Failed to parse HTTP request: {:?}", &e);
+                        info!("Failed to parse HTTP request: {:?}", &e; "request_id" => request_id);
                         self.reset();
                         Err(e)
                     }
@@ -1637,6 +1790,68 @@ impl PeerNetwork {
             })
         })
     }
+
+    /// Ask the node at `data_url` for its `/v2/info`, and check that it reports the public key
+    /// hash `node_public_key_hash`.  Returns Ok(true) if it does, and Ok(false) if some other node
+    /// answered.
+    ///
+    /// This blocks for up to `timeout` per network operation, and since the HTTP server is driven
+    /// by the same thread that owns the PeerNetwork, it must never be called from that thread.
+    pub fn check_data_url_identity(
+        data_url: &UrlString,
+        node_public_key_hash: &Hash160,
+        timeout: Duration,
+    ) -> Result<bool, NetError> {
+        if data_url.len() == 0 {
+            return Err(NetError::NoDataUrl);
+        }
+        let url = data_url
+            .parse_to_block_url()
+            .map_err(|e| NetError::LookupError(format!("Invalid data URL: {:?}", &e)))?;
+        let peerhost = PeerHost::try_from_url(data_url)
+            .ok_or(NetError::LookupError("Invalid data URL".to_string()))?;
+        let host = url
+            .host_str()
+            .ok_or(NetError::LookupError("Data URL has no host".to_string()))?;
+        let port = url
+            .port_or_known_default()
+            .ok_or(NetError::LookupError("Data URL has no port".to_string()))?;
+        let addr = (host, port)
+            .to_socket_addrs()
+            .map_err(|e| NetError::LookupError(format!("Failed to resolve {}: {:?}", host, &e)))?
+            .next()
+            .ok_or(NetError::LookupError(format!("No addresses for {}", host)))?;
+
+        let mut request = StacksHttpRequest::new_getinfo(peerhost, None);
+        request.preamble_mut().keep_alive = false;
+        let request_bytes = request.try_serialize()?;
+
+        let mut sock =
+            TcpStream::connect_timeout(&addr, timeout).map_err(|_e| NetError::ConnectionError)?;
+        sock.set_read_timeout(Some(timeout))
+            .map_err(|_e| NetError::ConnectionError)?;
+        sock.set_write_timeout(Some(timeout))
+            .map_err(|_e| NetError::ConnectionError)?;
+        sock.write_all(&request_bytes)
+            .map_err(NetError::WriteError)?;
+
+        let mut response_bytes = vec![];
+        sock.read_to_end(&mut response_bytes)
+            .map_err(NetError::ReadError)?;
+
+        let response = match StacksHttp::parse_response(
+            &request.preamble().verb,
+            &request.preamble().path_and_query_str,
+            &response_bytes,
+        )? {
+            StacksHttpMessage::Response(response) => response,
+            _ => {
+                return Err(NetError::InvalidMessage);
+            }
+        };
+        let peer_info = response.decode_peer_info()?;
+        Ok(peer_info.node_public_key_hash.as_ref() == Some(node_public_key_hash))
+    }
 }
 
 /// Given a raw path, decode it (i.e. if it's url-encoded)
@@ -1657,3 +1872,15 @@ pub fn decode_request_path(path: &str) -> Result<(String, String), NetError> {
         query_str.unwrap_or("").to_string(),
     ))
 }
+
+/// Get the request ID from a request preamble.  If it has none, or if it is not a number, then
+/// generate a new one and set it on the preamble, so that every log line and the response for this
+/// request carry the same ID.
+fn get_or_set_request_id(preamble: &mut HttpRequestPreamble) -> u32 {
+    if let Some(request_id) = preamble.get_request_id() {
+        return request_id;
+    }
+    let request_id = thread_rng().gen_range(1..=u32::MAX);
+    preamble.set_request_id(request_id);
+    request_id
+}
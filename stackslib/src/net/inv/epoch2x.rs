@@ -1079,6 +1079,25 @@ impl InvState {
         }
     }
 
+    /// Which blocks does this neighbor advertise that `local_inv` lacks?
+    /// Returns the absolute burn block heights of these blocks, in ascending order.  Returns an
+    /// empty list if we have no inventory for this neighbor.
+    pub fn missing_relative_to(&self, nk: &NeighborKey, local_inv: &PeerBlocksInv) -> Vec<u64> {
+        let Some(stats) = self.block_stats.get(nk) else {
+            return vec![];
+        };
+        (0..stats.inv.num_sortitions)
+            .map(|i| i + stats.inv.first_block_height)
+            .filter(|height| stats.inv.has_ith_block(*height) && !local_inv.has_ith_block(*height))
+            .collect()
+    }
+
+    /// Has an inventory sync with all of our neighbors finished, and did the most recent one
+    /// learn nothing new?  If so, then we know everything our neighbors have.
+    pub fn is_synced(&self) -> bool {
+        self.num_inv_syncs > 0 && !self.hint_do_rescan
+    }
+
     /// Cull broken peers and purge their stats
     pub fn cull_bad_peers(&mut self) -> HashSet<NeighborKey> {
         let mut bad_peers = HashSet::new();
@@ -121,6 +121,9 @@ pub mod db;
 /// which serves as an API for `DNSResolver`.  
 pub mod dns;
 pub mod download;
+/// Implements `PeerNetwork::health_check()`, which summarizes actionable problems with the peer
+/// network.
+pub mod health;
 pub mod http;
 /// Links http crate to Stacks
 pub mod httpcore;
@@ -284,6 +287,20 @@ pub enum Error {
     InvalidState,
     /// Waiting for DNS resolution
     WaitingForDNS,
+    /// Decoded a value, but this many bytes were left over
+    TrailingBytes(usize),
+    /// Remote peer speaks an incompatible major protocol version (contains the remote peer version)
+    IncompatiblePeerVersion(u32),
+    /// Remote peer authenticated with a public key other than the one we expected
+    BadPublicKey,
+    /// Message type is not enabled for relaying
+    MessageTypeFiltered,
+    /// Message would be relayed back to a peer that already relayed it
+    RelayLoop,
+    /// Message was recently relayed to this peer already
+    DuplicateRelay,
+    /// Request did not complete within the caller's deadline
+    Timeout,
 }
 
 impl From<libstackerdb_error> for Error {
@@ -432,6 +449,17 @@ impl fmt::Display for Error {
             Error::Http(e) => fmt::Display::fmt(&e, f),
             Error::InvalidState => write!(f, "Invalid state-machine state reached"),
             Error::WaitingForDNS => write!(f, "Waiting for DNS resolution"),
+            Error::TrailingBytes(ref num_bytes) => {
+                write!(f, "Trailing bytes after decoded value ({})", num_bytes)
+            }
+            Error::IncompatiblePeerVersion(ref version) => {
+                write!(f, "Incompatible peer version {:08x}", version)
+            }
+            Error::BadPublicKey => write!(f, "Remote peer presented an unexpected public key"),
+            Error::MessageTypeFiltered => write!(f, "Message type is not enabled for relaying"),
+            Error::RelayLoop => write!(f, "Message was already relayed by this peer"),
+            Error::DuplicateRelay => write!(f, "Message was recently relayed to this peer"),
+            Error::Timeout => write!(f, "Request timed out"),
         }
     }
 }
@@ -505,6 +533,13 @@ impl error::Error for Error {
             Error::Http(ref e) => Some(e),
             Error::InvalidState => None,
             Error::WaitingForDNS => None,
+            Error::TrailingBytes(..) => None,
+            Error::IncompatiblePeerVersion(..) => None,
+            Error::BadPublicKey => None,
+            Error::MessageTypeFiltered => None,
+            Error::RelayLoop => None,
+            Error::DuplicateRelay => None,
+            Error::Timeout => None,
         }
     }
 }
@@ -1176,6 +1211,39 @@ pub enum StacksMessageID {
     Reserved = 255,
 }
 
+impl StacksMessageID {
+    /// All message IDs that correspond to a `StacksMessageType`
+    pub const ALL: [StacksMessageID; 27] = [
+        StacksMessageID::Handshake,
+        StacksMessageID::HandshakeAccept,
+        StacksMessageID::HandshakeReject,
+        StacksMessageID::GetNeighbors,
+        StacksMessageID::Neighbors,
+        StacksMessageID::GetBlocksInv,
+        StacksMessageID::BlocksInv,
+        StacksMessageID::GetPoxInv,
+        StacksMessageID::PoxInv,
+        StacksMessageID::BlocksAvailable,
+        StacksMessageID::MicroblocksAvailable,
+        StacksMessageID::Blocks,
+        StacksMessageID::Microblocks,
+        StacksMessageID::Transaction,
+        StacksMessageID::Nack,
+        StacksMessageID::Ping,
+        StacksMessageID::Pong,
+        StacksMessageID::NatPunchRequest,
+        StacksMessageID::NatPunchReply,
+        StacksMessageID::StackerDBHandshakeAccept,
+        StacksMessageID::StackerDBGetChunkInv,
+        StacksMessageID::StackerDBChunkInv,
+        StacksMessageID::StackerDBGetChunk,
+        StacksMessageID::StackerDBChunk,
+        StacksMessageID::StackerDBPushChunk,
+        StacksMessageID::GetNakamotoInv,
+        StacksMessageID::NakamotoInv,
+    ];
+}
+
 /// Message type for all P2P Stacks network messages
 #[derive(Debug, Clone, PartialEq)]
 pub struct StacksMessage {
@@ -1336,6 +1404,44 @@ impl NeighborKey {
         }
     }
 
+    pub fn new(
+        peer_version: u32,
+        network_id: u32,
+        addrbytes: PeerAddress,
+        port: u16,
+    ) -> NeighborKey {
+        NeighborKey {
+            peer_version,
+            network_id,
+            addrbytes,
+            port,
+        }
+    }
+
+    /// Parse an `ip:port` string into a neighbor key for the given peer version and network ID.
+    /// Fails if the string is not a socket address, or if the port is 0.
+    pub fn try_from_str(
+        addr_str: &str,
+        peer_version: u32,
+        network_id: u32,
+    ) -> Result<NeighborKey, Error> {
+        let addr = SocketAddr::from_str(addr_str).map_err(|e| {
+            Error::DeserializeError(format!("Invalid socket address '{}': {:?}", addr_str, &e))
+        })?;
+        if addr.port() == 0 {
+            return Err(Error::DeserializeError(format!(
+                "Invalid port in '{}'",
+                addr_str
+            )));
+        }
+        Ok(NeighborKey::new(
+            peer_version,
+            network_id,
+            PeerAddress::from_socketaddr(&addr),
+            addr.port(),
+        ))
+    }
+
     pub fn from_neighbor_address(
         peer_version: u32,
         network_id: u32,
@@ -1466,6 +1572,17 @@ pub struct NetworkResult {
     pub rc_consensus_hash: ConsensusHash,
     /// The current StackerDB configs
     pub stacker_db_configs: HashMap<QualifiedContractIdentifier, StackerDBConfig>,
+    /// Whether or not the node has caught up with its peers.  This is true once (1) the Stacks
+    /// 2.x inventory sync has completed a full pass over all neighbors without learning anything
+    /// new, and (2) the Stacks 2.x block downloader has since made a full pass over the
+    /// sortition history in which no connected peer offered a block or confirmed microblock
+    /// stream that the node was missing.  It is always false while the 2.x downloader is not
+    /// running (e.g. if block download is disabled).
+    /// This is not limited to stable burnchain heights: the downloader scans every sortition up
+    /// to our burnchain tip and does not track which heights its missing blocks are at, so a
+    /// missing block above the stable tip also keeps this false.  If this is true, then no
+    /// connected peer has a stable-height block that we are missing.
+    pub is_caught_up: bool,
 }
 
 impl NetworkResult {
@@ -1499,6 +1616,7 @@ impl NetworkResult {
             burn_height,
             rc_consensus_hash,
             stacker_db_configs,
+            is_caught_up: false,
         }
     }
 
@@ -18,13 +18,14 @@ use std::collections::{HashMap, HashSet};
 use std::{cmp, mem};
 
 use rand::prelude::*;
-use rand::thread_rng;
 use stacks_common::util::hash::Hash160;
 use stacks_common::util::{get_epoch_time_secs, log};
 
 use crate::burnchains::{Address, Burnchain, BurnchainView};
 use crate::net::db::PeerDB;
-use crate::net::neighbors::{NeighborWalkResult, NEIGHBOR_MINIMUM_CONTACT_INTERVAL, NUM_NEIGHBORS};
+use crate::net::neighbors::{
+    NeighborWalkResult, WalkRng, NEIGHBOR_MINIMUM_CONTACT_INTERVAL, NUM_NEIGHBORS,
+};
 use crate::net::p2p::PeerNetwork;
 use crate::net::{
     Error as net_error, HandshakeAcceptData, HandshakeData, Neighbor, NeighborAddress, NeighborKey,
@@ -184,14 +185,25 @@ pub trait NeighborWalkDB {
     ) -> Result<Vec<Neighbor>, net_error> {
         let block_height = network.get_chain_view().burn_block_height;
         let cur_epoch = network.get_current_epoch();
-        let neighbors = PeerDB::get_random_walk_neighbors(
-            &network.peerdb_conn(),
-            network.get_local_peer().network_id,
-            cur_epoch.network_epoch,
-            min_age,
-            num_neighbors as u32,
-            block_height,
-        )
+        let neighbors = match network.walk_rng() {
+            WalkRng::Seeded(mut rng) => PeerDB::get_random_walk_neighbors_with_rng(
+                &network.peerdb_conn(),
+                network.get_local_peer().network_id,
+                cur_epoch.network_epoch,
+                min_age,
+                num_neighbors as u32,
+                block_height,
+                &mut *rng,
+            ),
+            WalkRng::Thread(_) => PeerDB::get_random_walk_neighbors(
+                &network.peerdb_conn(),
+                network.get_local_peer().network_id,
+                cur_epoch.network_epoch,
+                min_age,
+                num_neighbors as u32,
+                block_height,
+            ),
+        }
         .map_err(net_error::DBError)?;
 
         if neighbors.len() == 0 {
@@ -259,7 +271,7 @@ pub trait NeighborWalkDB {
         next_neighbors.sort_by(|n1, n2| n1.last_contact_time.cmp(&n2.last_contact_time));
         let median_neighbor_idx = next_neighbors.len() / 2;
         let random_neighbor_idx = if median_neighbor_idx > 0 {
-            thread_rng().gen::<usize>() % median_neighbor_idx
+            network.walk_rng().gen::<usize>() % median_neighbor_idx
         } else {
             0
         };
@@ -279,9 +291,11 @@ impl PeerDBNeighborWalk {
 
     /// Given a neighbor we tried to insert into the peer database, find one of the existing
     /// neighbors it collided with.  Return its slot in the peer db.
+    /// `choice` selects which of the colliding slots to use.
     fn find_replaced_neighbor_slot(
         conn: &DBConn,
         nk: &NeighborKey,
+        choice: usize,
     ) -> Result<Option<u32>, net_error> {
         let mut slots = PeerDB::peer_slots(conn, nk.network_id, &nk.addrbytes, nk.port)
             .map_err(net_error::DBError)?;
@@ -291,9 +305,8 @@ impl PeerDBNeighborWalk {
             return Ok(None);
         }
 
-        let mut rng = thread_rng();
-        slots.shuffle(&mut rng);
-        Ok(Some(slots[0]))
+        slots.sort();
+        Ok(Some(slots[choice % slots.len()]))
     }
 }
 
@@ -369,6 +382,7 @@ impl NeighborWalkDB for PeerDBNeighborWalk {
         replacements: &mut NeighborReplacements,
     ) -> Result<(bool, Neighbor), net_error> {
         let local_peer_str = format!("{:?}", network.get_local_peer());
+        let replaced_slot_choice = network.walk_rng().gen::<usize>();
         let tx = network.peerdb_tx_begin()?;
         let (mut neighbor_from_handshake, was_present) =
             Neighbor::load_and_update(&tx, preamble.peer_version, preamble.network_id, handshake)?;
@@ -404,8 +418,11 @@ impl NeighborWalkDB for PeerDBNeighborWalk {
 
         // neighbor was new, but we don't have space to insert it.
         // find and record a neighbor it would replace.
-        let replaced_neighbor_slot_opt =
-            Self::find_replaced_neighbor_slot(&tx, &neighbor_from_handshake.addr)?;
+        let replaced_neighbor_slot_opt = Self::find_replaced_neighbor_slot(
+            &tx,
+            &neighbor_from_handshake.addr,
+            replaced_slot_choice,
+        )?;
         if let Some(slot) = replaced_neighbor_slot_opt {
             replacements.add_neighbor(
                 NeighborAddress::from_neighbor(&neighbor_from_handshake),
@@ -425,7 +442,7 @@ impl NeighborWalkDB for PeerDBNeighborWalk {
         network: &PeerNetwork,
         ibd: bool,
     ) -> Result<Vec<Neighbor>, net_error> {
-        let allowed_peers = if ibd {
+        let mut allowed_peers = if ibd {
             // only get bootstrap peers (will be randomized)
             PeerDB::get_bootstrap_peers(
                 &network.peerdb_conn(),
@@ -438,6 +455,10 @@ impl NeighborWalkDB for PeerDBNeighborWalk {
                 network.get_local_peer().network_id,
             )?
         };
+
+        // SQLite's RANDOM() can't be seeded, so re-randomize with the walk's RNG
+        allowed_peers.sort_by(|n1, n2| n1.addr.cmp(&n2.addr));
+        allowed_peers.shuffle(&mut network.walk_rng());
         Ok(allowed_peers)
     }
 
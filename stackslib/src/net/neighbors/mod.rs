@@ -14,12 +14,13 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+use std::cell::RefMut;
 use std::collections::{HashMap, HashSet};
 use std::net::SocketAddr;
 use std::{cmp, mem};
 
 use rand::prelude::*;
-use rand::thread_rng;
+use rand::rngs::ThreadRng;
 use stacks_common::util::secp256k1::Secp256k1PublicKey;
 use stacks_common::util::{get_epoch_time_secs, log};
 
@@ -106,6 +107,43 @@ pub const NEIGHBOR_WALK_INTERVAL: u64 = 0;
 #[cfg(not(test))]
 pub const NEIGHBOR_WALK_INTERVAL: u64 = 120; // seconds
 
+/// RNG used by the neighbor walk.  This is either the peer network's seeded RNG, if it has one,
+/// or the thread-local RNG.
+pub enum WalkRng<'a> {
+    Seeded(RefMut<'a, StdRng>),
+    Thread(ThreadRng),
+}
+
+impl RngCore for WalkRng<'_> {
+    fn next_u32(&mut self) -> u32 {
+        match self {
+            WalkRng::Seeded(rng) => rng.next_u32(),
+            WalkRng::Thread(rng) => rng.next_u32(),
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        match self {
+            WalkRng::Seeded(rng) => rng.next_u64(),
+            WalkRng::Thread(rng) => rng.next_u64(),
+        }
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        match self {
+            WalkRng::Seeded(rng) => rng.fill_bytes(dest),
+            WalkRng::Thread(rng) => rng.fill_bytes(dest),
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        match self {
+            WalkRng::Seeded(rng) => rng.try_fill_bytes(dest),
+            WalkRng::Thread(rng) => rng.try_fill_bytes(dest),
+        }
+    }
+}
+
 impl PeerNetwork {
     /// Begin an outbound walk or a pingback walk, depending on whether or not we have pingback
     /// state.
@@ -125,7 +163,7 @@ impl PeerNetwork {
 
         // Either do an outbound walk, or do a pingback walk.
         // If one fails, then try the other
-        let do_outbound = thread_rng().gen::<bool>();
+        let do_outbound = self.walk_rng().gen::<bool>();
         if do_outbound {
             match NeighborWalk::instantiate_walk(
                 self.get_neighbor_walk_db(),
@@ -387,6 +425,7 @@ impl PeerNetwork {
             Ok(Some(ws)) => {
                 // walk ran to completion
                 self.walk_count += 1;
+                self.walk_last_success_ts = get_epoch_time_secs();
                 self.walk_deadline = self.connection_opts.walk_interval + get_epoch_time_secs();
 
                 debug!(
@@ -435,8 +474,7 @@ impl PeerNetwork {
             || walk_timed_out
         {
             // consider re-setting the walk state, now that we completed a walk.
-            let mut rng = thread_rng();
-            let sample: f64 = rng.gen();
+            let sample: f64 = self.walk_rng().gen();
             if walk_timed_out || sample < walk.walk_reset_prob {
                 debug!(
                     "{:?}: Resetting walk due to either a walk timeout ({}) or random restart",
@@ -131,7 +131,11 @@ impl Neighbor {
     /// So, we can estimate the undirected degree as being a random value between the lower and
     /// upper bound.
     pub fn degree(&self) -> u64 {
-        let mut rng = thread_rng();
+        self.degree_with_rng(&mut thread_rng())
+    }
+
+    /// Same as `degree()`, but using the given RNG
+    pub fn degree_with_rng<R: Rng + ?Sized>(&self, rng: &mut R) -> u64 {
         let min = cmp::min(self.in_degree, self.out_degree);
         let max = cmp::max(self.in_degree, self.out_degree);
         let res = rng.gen_range(min..(max + 1)) as u64;
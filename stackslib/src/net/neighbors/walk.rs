@@ -18,7 +18,6 @@ use std::collections::{HashMap, HashSet};
 use std::{cmp, mem};
 
 use rand::prelude::*;
-use rand::thread_rng;
 use stacks_common::types::chainstate::StacksPublicKey;
 use stacks_common::util::hash::Hash160;
 use stacks_common::util::secp256k1::Secp256k1PublicKey;
@@ -66,6 +65,9 @@ pub struct NeighborWalkResult {
     /// Neighbors who got replaced in the PeerDB because they were offline, but mapped to a new
     /// peer that was online and had the same slot locations
     pub replaced_neighbors: HashSet<NeighborKey>,
+    /// Outbound neighbors grouped by ASN, as of the end of the walk.  Neighbors with an unknown
+    /// ASN are omitted, so this is empty if no ASN data is loaded.
+    pub asn_distribution: HashMap<u32, Vec<NeighborKey>>,
 }
 
 impl NeighborWalkResult {
@@ -75,6 +77,7 @@ impl NeighborWalkResult {
             dead_connections: HashSet::new(),
             broken_connections: HashSet::new(),
             replaced_neighbors: HashSet::new(),
+            asn_distribution: HashMap::new(),
         }
     }
 
@@ -94,11 +97,41 @@ impl NeighborWalkResult {
         self.replaced_neighbors.insert(nk);
     }
 
+    pub fn add_asn_member(&mut self, asn: u32, nk: NeighborKey) -> () {
+        if let Some(members) = self.asn_distribution.get_mut(&asn) {
+            members.push(nk);
+        } else {
+            self.asn_distribution.insert(asn, vec![nk]);
+        }
+    }
+
+    /// Number of distinct ASNs our outbound neighbors belong to
+    pub fn asn_diversity(&self) -> usize {
+        self.asn_distribution.len()
+    }
+
+    /// Find the ASN with strictly more outbound neighbors than any other ASN, if there is one and
+    /// it has more than one neighbor.
+    pub fn overrepresented_asn(&self) -> Option<u32> {
+        let mut counts: Vec<(u32, usize)> = self
+            .asn_distribution
+            .iter()
+            .map(|(asn, members)| (*asn, members.len()))
+            .collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1));
+        match counts.as_slice() {
+            [(asn, count)] if *count > 1 => Some(*asn),
+            [(asn, count), (_, next_count), ..] if count > next_count && *count > 1 => Some(*asn),
+            _ => None,
+        }
+    }
+
     pub fn clear(&mut self) -> () {
         self.new_connections.clear();
         self.dead_connections.clear();
         self.broken_connections.clear();
         self.replaced_neighbors.clear();
+        self.asn_distribution.clear();
     }
 }
 
@@ -333,7 +366,8 @@ impl<DB: NeighborWalkDB, NC: NeighborComms> NeighborWalk<DB, NC> {
         comms: NC,
         network: &PeerNetwork,
     ) -> Result<NeighborWalk<DB, NC>, net_error> {
-        let event_ids: Vec<_> = network.iter_peer_event_ids().collect();
+        let mut event_ids: Vec<_> = network.iter_peer_event_ids().collect();
+        event_ids.sort();
         if event_ids.len() == 0 {
             debug!(
                 "{:?}: failed to begin inbound neighbor walk: no one's connected to us",
@@ -343,7 +377,7 @@ impl<DB: NeighborWalkDB, NC: NeighborComms> NeighborWalk<DB, NC> {
         }
 
         // pick a random search index
-        let mut idx = thread_rng().gen::<usize>() % event_ids.len();
+        let mut idx = network.walk_rng().gen::<usize>() % event_ids.len();
 
         test_debug!(
             "{:?}: try inbound neighbors -- sample out of {}. idx = {}",
@@ -416,7 +450,7 @@ impl<DB: NeighborWalkDB, NC: NeighborComms> NeighborWalk<DB, NC> {
         }
 
         // random search
-        let idx = thread_rng().gen::<usize>() % network.get_walk_pingbacks().len();
+        let idx = network.walk_rng().gen::<usize>() % network.get_walk_pingbacks().len();
 
         test_debug!(
             "{:?}: try pingback candidates -- sample out of {}. idx = {}",
@@ -425,8 +459,10 @@ impl<DB: NeighborWalkDB, NC: NeighborComms> NeighborWalk<DB, NC> {
             idx
         );
 
-        let (addr, pingback_peer) = match network.get_walk_pingbacks().iter().skip(idx).next() {
-            Some((addr, pingback_peer)) => (addr, pingback_peer),
+        let mut pingbacks: Vec<_> = network.get_walk_pingbacks().iter().collect();
+        pingbacks.sort_by(|(addr_1, _), (addr_2, _)| addr_1.cmp(addr_2));
+        let (addr, pingback_peer) = match pingbacks.get(idx) {
+            Some((addr, pingback_peer)) => (*addr, *pingback_peer),
             None => {
                 return Err(net_error::NoSuchNeighbor);
             }
@@ -893,7 +929,7 @@ impl<DB: NeighborWalkDB, NC: NeighborComms> NeighborWalk<DB, NC> {
                 neighbor_addrs_to_resolve.len(),
                 &self.cur_neighbor.addr
             );
-            neighbor_addrs_to_resolve.shuffle(&mut thread_rng());
+            neighbor_addrs_to_resolve.shuffle(&mut network.walk_rng());
             neighbor_addrs_to_resolve
                 .truncate(network.get_connection_opts().max_neighbors_of_neighbor as usize);
         }
@@ -1365,20 +1401,22 @@ impl<DB: NeighborWalkDB, NC: NeighborComms> NeighborWalk<DB, NC> {
     }
 
     /// Pick a random neighbor from a given list of neighbors, excluding an optional given neighbor
-    fn pick_random_neighbor(
+    fn pick_random_neighbor<R: Rng + ?Sized>(
+        rnd: &mut R,
         frontier: &HashMap<NeighborKey, Neighbor>,
         exclude: Option<&Neighbor>,
     ) -> Option<Neighbor> {
-        let mut rnd = thread_rng();
         if frontier.len() == 0 || (exclude.is_some() && frontier.len() == 1) {
             return None;
         }
         // select a random neighbor index, if exclude is set, and matches this
         //  neighbor, then use the next index (modulo the frontier length).
         let mut neighbor_index = rnd.gen_range(0..frontier.len());
+        let mut frontier: Vec<_> = frontier.iter().collect();
+        frontier.sort_by(|(nk_1, _), (nk_2, _)| nk_1.cmp(nk_2));
         for _ in 0..2 {
             // two attempts, in case our first attempt lands on `exclude`
-            for (cnt, (nk, n)) in frontier.iter().enumerate() {
+            for (cnt, &(nk, n)) in frontier.iter().enumerate() {
                 if cnt < neighbor_index {
                     continue;
                 }
@@ -1404,9 +1442,15 @@ impl<DB: NeighborWalkDB, NC: NeighborComms> NeighborWalk<DB, NC> {
     /// measure how represented each neighbor's AS is in the peer graph.  We *bias* the sample so
     /// that peers in under-represented ASs are more likely to be walked to than they otherwise
     /// would be if considering only neighbor degrees.
-    fn degree_ratio(&self, network: &PeerNetwork, n1: &Neighbor, n2: &Neighbor) -> f64 {
-        let d1 = n1.degree() as f64;
-        let d2 = n2.degree() as f64;
+    fn degree_ratio<R: Rng + ?Sized>(
+        &self,
+        rnd: &mut R,
+        network: &PeerNetwork,
+        n1: &Neighbor,
+        n2: &Neighbor,
+    ) -> f64 {
+        let d1 = n1.degree_with_rng(rnd) as f64;
+        let d2 = n2.degree_with_rng(rnd) as f64;
         let as_d1 = self.neighbor_db.get_asn_count(network, n1.asn) as f64;
         let as_d2 = self.neighbor_db.get_asn_count(network, n2.asn) as f64;
         (d1 * as_d2) / (d2 * as_d1)
@@ -1433,7 +1477,7 @@ impl<DB: NeighborWalkDB, NC: NeighborComms> NeighborWalk<DB, NC> {
             &self.cur_neighbor.addr
         );
 
-        let mut rnd = thread_rng();
+        let mut rnd = network.walk_rng();
 
         // step to a node in cur_neighbor's frontier, per MHRWDA
         let next_neighbor_opt = if self.frontier.len() == 0 {
@@ -1446,12 +1490,12 @@ impl<DB: NeighborWalkDB, NC: NeighborComms> NeighborWalk<DB, NC> {
             }
         } else {
             // continuing the walk
-            let next_neighbor =
-                Self::pick_random_neighbor(&self.frontier, None).expect("BUG: empty frontier size"); // won't panic since self.frontier.len() > 0
+            let next_neighbor = Self::pick_random_neighbor(&mut rnd, &self.frontier, None)
+                .expect("BUG: empty frontier size"); // won't panic since self.frontier.len() > 0
             let walk_prob: f64 = rnd.gen();
             if walk_prob
                 < self
-                    .degree_ratio(network, &self.cur_neighbor, &next_neighbor)
+                    .degree_ratio(&mut rnd, network, &self.cur_neighbor, &next_neighbor)
                     .min(1.0)
             {
                 // won the coin toss; will take a step.
@@ -1467,15 +1511,26 @@ impl<DB: NeighborWalkDB, NC: NeighborComms> NeighborWalk<DB, NC> {
                             // have alternative choices, so instead of backtracking, we'll delay
                             // acceptance by probabilistically deciding to step to an alternative
                             // instead of backtracking.
-                            let alt_next_neighbor =
-                                Self::pick_random_neighbor(&self.frontier, Some(&prev_neighbor))
-                                    .expect("BUG: empty frontier size");
+                            let alt_next_neighbor = Self::pick_random_neighbor(
+                                &mut rnd,
+                                &self.frontier,
+                                Some(&prev_neighbor),
+                            )
+                            .expect("BUG: empty frontier size");
                             let alt_prob: f64 = rnd.gen();
 
-                            let cur_to_alt =
-                                self.degree_ratio(network, &self.cur_neighbor, &alt_next_neighbor);
-                            let prev_to_cur =
-                                self.degree_ratio(network, &prev_neighbor, &self.cur_neighbor);
+                            let cur_to_alt = self.degree_ratio(
+                                &mut rnd,
+                                network,
+                                &self.cur_neighbor,
+                                &alt_next_neighbor,
+                            );
+                            let prev_to_cur = self.degree_ratio(
+                                &mut rnd,
+                                network,
+                                &prev_neighbor,
+                                &self.cur_neighbor,
+                            );
                             let trans_prob = fmin!(
                                 fmin!(1.0, cur_to_alt * cur_to_alt),
                                 fmax!(1.0, prev_to_cur * prev_to_cur)
@@ -14,30 +14,33 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+use std::cell::RefCell;
 use std::cmp::Ordering;
 use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
-use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, Shutdown, SocketAddr};
 use std::sync::mpsc::{
-    sync_channel, Receiver, RecvError, SendError, SyncSender, TryRecvError, TrySendError,
+    sync_channel, Receiver, RecvError, RecvTimeoutError, SendError, SyncSender, TryRecvError,
+    TrySendError,
 };
 use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
 use std::{cmp, mem};
 
 use clarity::vm::ast::ASTRules;
 use clarity::vm::database::BurnStateDB;
 use clarity::vm::types::QualifiedContractIdentifier;
-use mio::net as mio_net;
+use mio::{self, net as mio_net};
 use rand::prelude::*;
 use rand::thread_rng;
 use stacks_common::consts::{FIRST_BURNCHAIN_CONSENSUS_HASH, FIRST_STACKS_BLOCK_HASH};
 use stacks_common::types::chainstate::{PoxId, SortitionId};
 use stacks_common::types::net::{PeerAddress, PeerHost};
 use stacks_common::types::StacksEpochId;
-use stacks_common::util::hash::to_hex;
+use stacks_common::util::hash::{to_hex, Sha512Trunc256Sum};
 use stacks_common::util::secp256k1::Secp256k1PublicKey;
 use stacks_common::util::{get_epoch_time_ms, get_epoch_time_secs, log};
+use url;
 use wsts::curve::point::Point;
-use {mio, url};
 
 use crate::burnchains::db::{BurnchainDB, BurnchainHeaderReader};
 use crate::burnchains::{Address, Burnchain, BurnchainView, PublicKey};
@@ -48,6 +51,7 @@ use crate::chainstate::coordinator::{
     static_get_stacks_tip_affirmation_map,
 };
 use crate::chainstate::stacks::boot::MINERS_NAME;
+use crate::chainstate::stacks::db::blocks::MemPoolRejection;
 use crate::chainstate::stacks::db::StacksChainState;
 use crate::chainstate::stacks::{StacksBlockHeader, MAX_BLOCK_LEN, MAX_TRANSACTION_LEN};
 use crate::core::StacksEpoch;
@@ -66,7 +70,7 @@ use crate::net::inv::nakamoto::{InvGenerator, NakamotoInvStateMachine};
 use crate::net::neighbors::*;
 use crate::net::poll::{NetworkPollState, NetworkState};
 use crate::net::prune::*;
-use crate::net::relay::{RelayerStats, *, *};
+use crate::net::relay::{RelayerStats, *};
 use crate::net::server::*;
 use crate::net::stackerdb::{StackerDBConfig, StackerDBSync, StackerDBTx, StackerDBs};
 use crate::net::{Error as net_error, Neighbor, NeighborKey, *};
@@ -84,12 +88,24 @@ pub enum NetworkRequest {
     ), // announce to all wanting neighbors that we have these confirmed microblock streams
     Relay(NeighborKey, StacksMessage),
     Broadcast(Vec<RelayData>, StacksMessageType),
+    /// Send a request to a peer with the given TTL, and send back the reply handle
+    SignedRequest(
+        NeighborKey,
+        StacksMessage,
+        u64,
+        SyncSender<Result<ReplyHandleP2P, net_error>>,
+    ),
+    /// Ping a peer, and send back the reply handle for its pong
+    Ping(NeighborKey, SyncSender<Result<ReplyHandleP2P, net_error>>),
 }
 
 /// Handle for other threads to use to issue p2p network requests.
 /// The "main loop" for sending/receiving data is a select/poll loop, and runs outside of other
 /// threads that need a synchronous RPC or a multi-RPC interface.  This object gives those threads
 /// a way to issue commands and hear back replies from them.
+///
+/// A handle becomes invalid once the peer network shuts down (see `PeerNetwork::shutdown()`),
+/// after which every request sent through it fails with `net_error::InvalidHandle`.
 pub struct NetworkHandle {
     chan_in: SyncSender<NetworkRequest>,
 }
@@ -169,6 +185,85 @@ impl NetworkHandle {
         let req = NetworkRequest::Broadcast(relay_hints, msg);
         self.send_request(req)
     }
+
+    /// Send a signed request to a peer via the p2p network thread, and wait for its reply.
+    /// `ttl` is the request's timeout in seconds within the p2p thread; `wait` bounds how long
+    /// this call blocks overall.  Returns `net_error::Timeout` if no reply arrives in time.
+    /// Called from outside the p2p thread by other threads.
+    pub fn send_signed_request_with_timeout(
+        &mut self,
+        neighbor_key: NeighborKey,
+        msg: StacksMessage,
+        ttl: u64,
+        wait: Duration,
+    ) -> Result<StacksMessage, net_error> {
+        let deadline = Instant::now() + wait;
+        let (reply_tx, reply_rx) = sync_channel(1);
+        let req = NetworkRequest::SignedRequest(neighbor_key, msg, ttl, reply_tx);
+        self.send_request(req)?;
+        NetworkHandle::wait_for_reply(reply_rx, Some(deadline))
+    }
+
+    /// Ping a peer via the p2p network thread, and wait for its pong.
+    /// Returns the round-trip time in milliseconds.  The p2p thread signs the ping with its
+    /// current chain view, and the request times out after `ConnectionOptions::timeout` seconds.
+    /// Called from outside the p2p thread by other threads.
+    pub fn ping_peer(&mut self, neighbor_key: &NeighborKey) -> Result<u64, net_error> {
+        let start = Instant::now();
+        let (reply_tx, reply_rx) = sync_channel(1);
+        let req = NetworkRequest::Ping(neighbor_key.clone(), reply_tx);
+        self.send_request(req)?;
+
+        let reply = NetworkHandle::wait_for_reply(reply_rx, None)?;
+        match reply.payload {
+            StacksMessageType::Pong(_) => Ok(start.elapsed().as_millis() as u64),
+            _ => {
+                debug!(
+                    "Expected Pong from {:?}, got {}",
+                    neighbor_key,
+                    reply.get_message_name()
+                );
+                Err(net_error::InvalidMessage)
+            }
+        }
+    }
+
+    /// Wait for the p2p thread to hand back a reply handle, and then poll it for the reply.
+    /// If `deadline` is given, fail with `net_error::Timeout` once it passes.  Otherwise, wait
+    /// until the reply handle's own TTL expires.
+    fn wait_for_reply(
+        reply_rx: Receiver<Result<ReplyHandleP2P, net_error>>,
+        deadline: Option<Instant>,
+    ) -> Result<StacksMessage, net_error> {
+        let handle_res = match deadline {
+            Some(deadline) => reply_rx
+                .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                .map_err(|e| match e {
+                    RecvTimeoutError::Timeout => net_error::Timeout,
+                    RecvTimeoutError::Disconnected => net_error::InvalidHandle,
+                }),
+            None => reply_rx.recv().map_err(|_| net_error::InvalidHandle),
+        }?;
+        let mut handle = handle_res?;
+
+        loop {
+            handle = match handle.try_send_recv() {
+                Ok(reply) => {
+                    return Ok(reply);
+                }
+                Err(Ok(handle)) => handle,
+                Err(Err(e)) => {
+                    return Err(e);
+                }
+            };
+            if let Some(deadline) = deadline {
+                if Instant::now() >= deadline {
+                    return Err(net_error::Timeout);
+                }
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
 }
 
 impl NetworkHandleServer {
@@ -266,6 +361,8 @@ pub struct PeerNetwork {
     // ongoing messages the network is sending via the p2p interface
     pub relay_handles: HashMap<usize, VecDeque<ReplyHandleP2P>>,
     pub relayer_stats: RelayerStats,
+    /// (neighbor, payload) pairs we recently relayed, so we don't relay them again
+    pub seen_messages: SeenMessageCache,
 
     // handles for other threads to send/receive data to peers
     handles: VecDeque<NetworkHandleServer>,
@@ -296,6 +393,10 @@ pub struct PeerNetwork {
     pub walk_total_step_count: u64,
     pub walk_pingbacks: HashMap<NeighborAddress, NeighborPingback>, // inbound peers for us to try to ping back and add to our frontier, mapped to (peer_version, network_id, timeout, pubkey)
     pub walk_result: NeighborWalkResult, // last successful neighbor walk result
+    pub walk_last_success_ts: u64,       // when the last neighbor walk completed
+    /// Seeded RNG for the neighbor walk, if `walk_seed` is set.  Otherwise, the walk uses
+    /// `thread_rng()`.
+    walk_rng: Option<RefCell<StdRng>>,
 
     /// Epoch 2.x inventory state
     pub inv_state: Option<InvState>,
@@ -340,6 +441,12 @@ pub struct PeerNetwork {
     pub prune_outbound_counts: HashMap<NeighborKey, u64>,
     pub prune_inbound_counts: HashMap<NeighborKey, u64>,
 
+    // neighbors that prune_connections() must never disconnect (not persisted to the peer DB)
+    pinned_outbound: HashSet<NeighborKey>,
+
+    // public keys that neighbors must authenticate with, as given to connect_peer_with_pubkey()
+    expected_public_keys: HashMap<NeighborKey, Secp256k1PublicKey>,
+
     // http endpoint, used for driving HTTP conversations (some of which we initiate)
     pub http: Option<HttpPeer>,
 
@@ -419,6 +526,15 @@ impl PeerNetwork {
             debug!("{:?}: disable inbound neighbor walks", &local_peer);
         }
 
+        let seen_messages = SeenMessageCache::new(
+            connection_opts.seen_cache_size,
+            connection_opts.seen_cache_ttl,
+        );
+        let walk_rng = connection_opts.walk_seed.map(|seed| {
+            debug!("{:?}: seed neighbor walk RNG with {}", &local_peer, seed);
+            RefCell::new(StdRng::seed_from_u64(seed))
+        });
+
         let first_block_height = burnchain.first_block_height;
         let first_burn_header_hash = burnchain.first_block_hash.clone();
         let first_burn_header_ts = burnchain.first_block_timestamp;
@@ -466,6 +582,7 @@ impl PeerNetwork {
 
             relay_handles: HashMap::new(),
             relayer_stats: RelayerStats::new(),
+            seen_messages,
 
             handles: VecDeque::new(),
             network: None,
@@ -485,9 +602,11 @@ impl PeerNetwork {
             walk_retries: 0,
             walk_resets: 0,
             walk_count: 0,
+            walk_last_success_ts: 0,
             walk_total_step_count: 0,
             walk_pingbacks: HashMap::new(),
             walk_result: NeighborWalkResult::new(),
+            walk_rng,
 
             inv_state: None,
             inv_state_nakamoto: None,
@@ -511,6 +630,8 @@ impl PeerNetwork {
 
             prune_outbound_counts: HashMap::new(),
             prune_inbound_counts: HashMap::new(),
+            pinned_outbound: HashSet::new(),
+            expected_public_keys: HashMap::new(),
 
             http: Some(http),
             bind_nk: NeighborKey {
@@ -618,7 +739,13 @@ impl PeerNetwork {
     pub fn bind(&mut self, my_addr: &SocketAddr, http_addr: &SocketAddr) -> Result<(), net_error> {
         let mut net = NetworkState::new(self.connection_opts.max_sockets)?;
 
-        let (p2p_handle, bound_p2p_addr) = net.bind(my_addr)?;
+        let (p2p_handle, bound_p2p_addr) = if self.connection_opts.outbound_only {
+            // don't listen for inbound p2p connections, but still allocate a handle under which
+            // our outbound sockets get registered
+            (net.bind_outbound_only(my_addr)?, my_addr.clone())
+        } else {
+            net.bind(my_addr)?
+        };
         let (http_handle, bound_http_addr) = net.bind(http_addr)?;
 
         test_debug!(
@@ -666,6 +793,15 @@ impl PeerNetwork {
         &self.connection_opts
     }
 
+    /// Get the RNG the neighbor walk should use.
+    /// Don't hold onto it across calls that might also need it.
+    pub fn walk_rng(&self) -> WalkRng {
+        match self.walk_rng.as_ref() {
+            Some(rng) => WalkRng::Seeded(rng.borrow_mut()),
+            None => WalkRng::Thread(thread_rng()),
+        }
+    }
+
     /// Get a peer conversation ref by its event ID
     pub fn get_p2p_convo(&self, event_id: usize) -> Option<&ConversationP2P> {
         self.peers.get(&event_id)
@@ -829,6 +965,19 @@ impl PeerNetwork {
         client
     }
 
+    /// Shut down this peer network's handles.  This drops the server end of every
+    /// `NetworkHandle`, so callers in other threads get `net_error::InvalidHandle` instead of
+    /// queuing up requests that will never be dispatched.
+    /// Called when the p2p thread exits.
+    pub fn shutdown(&mut self) {
+        debug!(
+            "{:?}: Shut down {} network handle(s)",
+            &self.local_peer,
+            self.handles.len()
+        );
+        self.handles.clear();
+    }
+
     /// Saturate a socket with bufferred data in the p2p conversation.
     /// The caller fills the reply handle with the serialized message, and this
     /// function (1) pushes it into the conversation's inner connection's outbox,
@@ -931,6 +1080,54 @@ impl PeerNetwork {
         neighbor_key: &NeighborKey,
         message: StacksMessage,
     ) -> Result<(), net_error> {
+        // only forwarded messages are de-duplicated.  Messages we originate (such as antientropy
+        // pushes) may be re-sent on purpose.
+        let forwarded_digest = if message.relayers.is_empty() {
+            None
+        } else {
+            Some(SeenMessageCache::payload_digest(&message.payload))
+        };
+        self.relay_signed_message_with_digest(neighbor_key, message, forwarded_digest)
+    }
+
+    /// Relay a signed message to a peer, given the payload digest (see
+    /// `SeenMessageCache::payload_digest()`) if the message is being forwarded and should be
+    /// de-duplicated.  Use this to forward the same payload to several peers, so it only needs
+    /// to be hashed once.
+    /// Called from _within_ the p2p thread.
+    pub fn relay_signed_message_with_digest(
+        &mut self,
+        neighbor_key: &NeighborKey,
+        message: StacksMessage,
+        forwarded_digest: Option<Sha512Trunc256Sum>,
+    ) -> Result<(), net_error> {
+        if !self
+            .connection_opts
+            .relay_message_filter
+            .contains(&message.payload.get_message_id())
+        {
+            debug!(
+                "{:?}: Will not relay '{}' to {:?}: message type is filtered",
+                &self.local_peer,
+                message.payload.get_message_description(),
+                neighbor_key
+            );
+            return Err(net_error::MessageTypeFiltered);
+        }
+
+        let now = get_epoch_time_secs();
+        if let Some(digest) = forwarded_digest.as_ref() {
+            if self.seen_messages.contains(neighbor_key, digest, now) {
+                debug!(
+                    "{:?}: Will not relay '{}' to {:?}: recently relayed",
+                    &self.local_peer,
+                    message.payload.get_message_description(),
+                    neighbor_key
+                );
+                return Err(net_error::DuplicateRelay);
+            }
+        }
+
         let event_id = if let Some(event_id) = self.events.get(&neighbor_key) {
             *event_id
         } else {
@@ -938,6 +1135,23 @@ impl PeerNetwork {
             return Err(net_error::NoSuchNeighbor);
         };
 
+        // relayers are identified by key, so only an authenticated peer can be matched
+        if let Some(public_key_hash) = self
+            .peers
+            .get(&event_id)
+            .and_then(|convo| convo.get_public_key_hash())
+        {
+            if let Err(e) = message.verify_relayers(&public_key_hash) {
+                debug!(
+                    "{:?}: Will not relay '{}' to {:?}: it has already relayed it",
+                    &self.local_peer,
+                    message.payload.get_message_description(),
+                    neighbor_key
+                );
+                return Err(e);
+            }
+        }
+
         self.with_p2p_convo(event_id, |network, convo, client_sock| {
             let _msg = message.get_message_name();
             let _seq = message.preamble.seq;
@@ -956,10 +1170,58 @@ impl PeerNetwork {
                 // keep trying to send
                 network.add_relay_handle(event_id, reply_handle);
             }
+            if let Some(digest) = forwarded_digest {
+                network
+                    .seen_messages
+                    .insert(neighbor_key.clone(), digest, now);
+            }
             Ok(())
         })?
     }
 
+    /// Send a signed request to a peer, and get back a handle for its reply.
+    /// The caller needs to flush the handle and poll it for the reply.
+    /// Called from _within_ the p2p thread.
+    pub fn send_signed_request(
+        &mut self,
+        neighbor_key: &NeighborKey,
+        message: StacksMessage,
+        ttl: u64,
+    ) -> Result<ReplyHandleP2P, net_error> {
+        let event_id = if let Some(event_id) = self.events.get(&neighbor_key) {
+            *event_id
+        } else {
+            info!("Not connected to {:?}", &neighbor_key);
+            return Err(net_error::NoSuchNeighbor);
+        };
+
+        self.with_p2p_convo(event_id, |_network, convo, _client_sock| {
+            convo.send_signed_request(message, ttl)
+        })?
+    }
+
+    /// Ping a peer, and get back a handle for its pong.
+    /// The caller needs to flush the handle and poll it for the reply.
+    /// Called from _within_ the p2p thread.
+    pub fn ping_peer(&mut self, neighbor_key: &NeighborKey) -> Result<ReplyHandleP2P, net_error> {
+        let event_id = if let Some(event_id) = self.events.get(&neighbor_key) {
+            *event_id
+        } else {
+            info!("Not connected to {:?}", &neighbor_key);
+            return Err(net_error::NoSuchNeighbor);
+        };
+
+        self.with_p2p_convo(event_id, |network, convo, _client_sock| {
+            let payload = StacksMessageType::Ping(PingData::new());
+            let ping = convo.sign_message(
+                &network.chain_view,
+                &network.local_peer.private_key,
+                payload,
+            )?;
+            convo.send_signed_request(ping, network.connection_opts.timeout)
+        })?
+    }
+
     /// Broadcast a message to a list of neighbors
     pub fn broadcast_message(
         &mut self,
@@ -974,7 +1236,37 @@ impl PeerNetwork {
             neighbor_keys.len(),
             &relay_hints
         );
+        if !self
+            .connection_opts
+            .relay_message_filter
+            .contains(&message_payload.get_message_id())
+        {
+            debug!(
+                "{:?}: Will not broadcast '{}': message type is filtered",
+                &self.local_peer,
+                message_payload.get_message_description()
+            );
+            return;
+        }
+        // only forwarded messages are de-duplicated; see relay_signed_message()
+        let now = get_epoch_time_secs();
+        let forwarded_digest = if relay_hints.is_empty() {
+            None
+        } else {
+            Some(SeenMessageCache::payload_digest(&message_payload))
+        };
         for nk in neighbor_keys.drain(..) {
+            if let Some(digest) = forwarded_digest.as_ref() {
+                if self.seen_messages.contains(&nk, digest, now) {
+                    debug!(
+                        "{:?}: Do not broadcast '{}' to {:?}: recently relayed",
+                        &self.local_peer,
+                        message_payload.get_message_description(),
+                        &nk
+                    );
+                    continue;
+                }
+            }
             if let Some(event_id) = self.events.get(&nk) {
                 let event_id = *event_id;
                 if let Some(convo) = self.peers.get_mut(&event_id) {
@@ -1013,6 +1305,9 @@ impl PeerNetwork {
                                 &nk
                             );
                             self.add_relay_handle(event_id, rh);
+                            if let Some(digest) = forwarded_digest.as_ref() {
+                                self.seen_messages.insert(nk.clone(), digest.clone(), now);
+                            }
                         }
                         Err(e) => {
                             warn!(
@@ -1097,6 +1392,55 @@ impl PeerNetwork {
         self.connect_peer_deny_checks(neighbor, true)
     }
 
+    /// Connect to a peer whose public key we already know out-of-band (e.g. a bootstrap node).
+    /// Once the handshake completes, the remote peer must have authenticated with `expected`;
+    /// otherwise the connection is dropped with net_error::BadPublicKey.  The expectation is
+    /// remembered for subsequent connections to this neighbor as well, until it is replaced by
+    /// another call to this method or removed with clear_expected_public_key().
+    pub fn connect_peer_with_pubkey(
+        &mut self,
+        neighbor: &NeighborKey,
+        expected: &Secp256k1PublicKey,
+    ) -> Result<usize, net_error> {
+        self.expected_public_keys
+            .insert(neighbor.clone(), expected.clone());
+        self.connect_peer(neighbor)
+    }
+
+    /// Stop expecting the neighbor to authenticate with the public key given to
+    /// connect_peer_with_pubkey().  Existing connections are unaffected.
+    /// Returns true if a public key was expected.
+    pub fn clear_expected_public_key(&mut self, neighbor: &NeighborKey) -> bool {
+        self.expected_public_keys.remove(neighbor).is_some()
+    }
+
+    /// Verify that an authenticated conversation is using the public key we were told to
+    /// expect via connect_peer_with_pubkey(), if any.
+    fn check_expected_public_key(&self, event_id: usize) -> Result<(), net_error> {
+        let convo = match self.peers.get(&event_id) {
+            Some(convo) => convo,
+            None => {
+                return Ok(());
+            }
+        };
+        if !convo.is_authenticated() {
+            return Ok(());
+        }
+        let expected = match self
+            .get_event_neighbor_key(event_id)
+            .and_then(|nk| self.expected_public_keys.get(&nk))
+        {
+            Some(expected) => expected,
+            None => {
+                return Ok(());
+            }
+        };
+        if convo.get_public_key().as_ref() != Some(expected) {
+            return Err(net_error::BadPublicKey);
+        }
+        Ok(())
+    }
+
     /// Connect to a peer, optionally checking our deny information.
     /// Idempotent -- will not re-connect if already connected.
     /// It will, however, permit multiple connection attempts if none have yet connected.
@@ -1355,6 +1699,26 @@ impl PeerNetwork {
                 self.broadcast_message(neighbor_keys, relay_hints, msg);
                 Ok(())
             }
+            NetworkRequest::SignedRequest(neighbor_key, msg, ttl, reply_tx) => {
+                let handle_res = self.send_signed_request(&neighbor_key, msg, ttl);
+                if let Err(e) = reply_tx.send(handle_res) {
+                    debug!(
+                        "{:?}: requester for {:?} went away: {:?}",
+                        &self.local_peer, &neighbor_key, &e
+                    );
+                }
+                Ok(())
+            }
+            NetworkRequest::Ping(neighbor_key, reply_tx) => {
+                let handle_res = self.ping_peer(&neighbor_key);
+                if let Err(e) = reply_tx.send(handle_res) {
+                    debug!(
+                        "{:?}: pinger of {:?} went away: {:?}",
+                        &self.local_peer, &neighbor_key, &e
+                    );
+                }
+                Ok(())
+            }
         }
     }
 
@@ -1366,31 +1730,35 @@ impl PeerNetwork {
         let mut messages = vec![];
         let mut responses = vec![];
 
-        // receive all in-bound requests
-        for i in 0..self.handles.len() {
-            match self.handles.get(i) {
-                Some(ref handle) => {
-                    loop {
-                        // drain all inbound requests
-                        let inbound_request_res = handle.chan_in.try_recv();
-                        match inbound_request_res {
-                            Ok(inbound_request) => {
-                                messages.push((i, inbound_request));
-                            }
-                            Err(TryRecvError::Empty) => {
-                                // nothing to do
-                                break;
-                            }
-                            Err(TryRecvError::Disconnected) => {
-                                // dead; remove
-                                to_remove.push(i);
-                                break;
-                            }
-                        }
+        // receive in-bound requests round-robin, taking at most
+        // `max_requests_per_handle_per_pass` from each handle.  Always poll each handle at least
+        // once, so that dead handles get noticed and cleaned up.
+        let mut live: Vec<usize> = (0..self.handles.len()).collect();
+        let max_passes = cmp::max(1, self.connection_opts.max_requests_per_handle_per_pass);
+        for _ in 0..max_passes {
+            if live.is_empty() {
+                break;
+            }
+            live.retain(|i| {
+                let Some(handle) = self.handles.get(*i) else {
+                    return false;
+                };
+                match handle.chan_in.try_recv() {
+                    Ok(inbound_request) => {
+                        messages.push((*i, inbound_request));
+                        true
+                    }
+                    Err(TryRecvError::Empty) => {
+                        // nothing more to do
+                        false
+                    }
+                    Err(TryRecvError::Disconnected) => {
+                        // dead; remove
+                        to_remove.push(*i);
+                        false
                     }
                 }
-                None => {}
-            }
+            });
         }
 
         // dispatch all in-bound requests from waiting threads
@@ -1410,6 +1778,7 @@ impl PeerNetwork {
         }
 
         // clear out dead handles
+        to_remove.sort_unstable();
         to_remove.reverse();
         for i in to_remove {
             self.handles.remove(i);
@@ -1707,6 +2076,13 @@ impl PeerNetwork {
             ),
         };
 
+        // if we were given this peer's public key out-of-band, then only accept messages signed
+        // with it (check_expected_public_key() catches a re-key to a different key)
+        let pubkey_opt = match self.expected_public_keys.get(&neighbor_key) {
+            Some(expected) => Some(expected.clone()),
+            None => pubkey_opt,
+        };
+
         match self.can_register_peer(&neighbor_key, outbound) {
             Ok(_) => {}
             Err(e) => {
@@ -1932,6 +2308,16 @@ impl PeerNetwork {
         let mut registered = vec![];
 
         for (hint_event_id, client_sock) in poll_state.new.drain() {
+            if self.connection_opts.outbound_only {
+                // we don't take inbound connections
+                debug!(
+                    "{:?}: outbound-only; dropping inbound socket {:?}",
+                    &self.local_peer, &client_sock
+                );
+                let _ = client_sock.shutdown(Shutdown::Both);
+                continue;
+            }
+
             let event_id = match self.network {
                 Some(ref mut network) => {
                     // add to poller
@@ -2149,6 +2535,15 @@ impl PeerNetwork {
                     self.get_p2p_convo(*event_id),
                 );
                 to_remove.push(*event_id);
+            } else if let Err(e) = self.check_expected_public_key(*event_id) {
+                info!(
+                    "{:?}: Disconnecting {:?}: {:?}",
+                    &self.local_peer,
+                    self.get_p2p_convo(*event_id),
+                    &e
+                );
+                to_remove.push(*event_id);
+                continue;
             }
 
             // forward along unhandled messages from this peer
@@ -2173,11 +2568,34 @@ impl PeerNetwork {
         }
     }
 
+    /// Get up to `n` connected neighbors with the highest NeighborStats::health_score(), best
+    /// first.  Ties are broken by neighbor key, so the order is deterministic.
+    pub fn healthiest_neighbors(&self, n: usize) -> Vec<NeighborKey> {
+        let now = get_epoch_time_secs();
+        let mut scored: Vec<(f64, NeighborKey)> = self
+            .events
+            .iter()
+            .filter_map(|(nk, event_id)| {
+                self.peers
+                    .get(event_id)
+                    .map(|convo| (convo.stats.health_score_at(now), nk.clone()))
+            })
+            .collect();
+
+        scored.sort_by(|(score_1, nk_1), (score_2, nk_2)| {
+            score_2
+                .partial_cmp(score_1)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| nk_1.cmp(nk_2))
+        });
+        scored.into_iter().take(n).map(|(_, nk)| nk).collect()
+    }
+
     /// Update peer connections as a result of a peer graph walk.
     /// -- Drop broken connections.
     /// -- Update our frontier.
     /// -- Prune our frontier if it gets too big.
-    fn process_neighbor_walk(&mut self, walk_result: NeighborWalkResult) -> () {
+    fn process_neighbor_walk(&mut self, mut walk_result: NeighborWalkResult) -> () {
         for broken in walk_result.broken_connections.iter() {
             self.deregister_and_ban_neighbor(broken);
         }
@@ -2190,10 +2608,72 @@ impl PeerNetwork {
             self.deregister_neighbor(replaced);
         }
 
+        self.rebalance_outbound_asns(&mut walk_result);
+
         // store for later
         self.walk_result = walk_result;
     }
 
+    /// Compute the ASN distribution of our outbound neighbors, and store it in the walk result.
+    /// If we have more outbound neighbors than we want, then disconnect from neighbors in whichever
+    /// ASN over-represents itself, so that pruning doesn't leave us clustered in one AS.
+    /// Neighbors that `prune_connections()` would keep are never disconnected.
+    /// Does nothing if we have no ASN data.
+    fn rebalance_outbound_asns(&mut self, walk_result: &mut NeighborWalkResult) {
+        walk_result.asn_distribution.clear();
+        for (nk, event_id) in self.events.iter() {
+            let Some(convo) = self.peers.get(event_id) else {
+                continue;
+            };
+            if !convo.is_outbound() {
+                continue;
+            }
+            let Ok(Some(peer)) =
+                PeerDB::get_peer(self.peerdb.conn(), nk.network_id, &nk.addrbytes, nk.port)
+            else {
+                continue;
+            };
+            if peer.asn != 0 {
+                walk_result.add_asn_member(peer.asn, nk.clone());
+            }
+        }
+
+        if cfg!(test) && self.connection_opts.disable_network_prune {
+            return;
+        }
+
+        // never disconnect a neighbor that pruning would keep
+        let Some(safe) = self.get_prune_safe_set() else {
+            return;
+        };
+
+        let mut num_outbound = PeerNetwork::count_outbound_conversations(&self.peers);
+        while num_outbound > self.connection_opts.soft_num_neighbors {
+            let Some(asn) = walk_result.overrepresented_asn() else {
+                break;
+            };
+            let Some(members) = walk_result.asn_distribution.get_mut(&asn) else {
+                break;
+            };
+            members.sort();
+            let Some(idx) = members.iter().position(|nk| {
+                self.events
+                    .get(nk)
+                    .map(|event_id| !safe.contains(event_id))
+                    .unwrap_or(false)
+            }) else {
+                break;
+            };
+            let nk = members.remove(idx);
+            debug!(
+                "{:?}: disconnect {:?} since its ASN {} is over-represented among our outbound neighbors",
+                &self.local_peer, &nk, asn
+            );
+            self.deregister_neighbor(&nk);
+            num_outbound = num_outbound.saturating_sub(1);
+        }
+    }
+
     /// Queue up pings to everyone we haven't spoken to in a while to let them know that we're still
     /// alive.
     pub fn queue_ping_heartbeats(&mut self) -> () {
@@ -2291,18 +2771,44 @@ impl PeerNetwork {
         ret
     }
 
-    /// Prune inbound and outbound connections if we can
-    fn prune_connections(&mut self) -> () {
-        if cfg!(test) && self.connection_opts.disable_network_prune {
-            return;
-        }
+    /// Pin a neighbor, so that its connection is never pruned.  Unlike allowing the peer in the
+    /// peer DB, this only lasts for as long as this PeerNetwork does.
+    /// Returns true if the neighbor was not already pinned.
+    pub fn pin_neighbor(&mut self, neighbor_key: &NeighborKey) -> bool {
+        self.pinned_outbound.insert(neighbor_key.clone())
+    }
 
-        debug!("Prune from {} connections", self.events.len());
+    /// Unpin a neighbor, so its connection can be pruned again.
+    /// Returns true if the neighbor was pinned.
+    pub fn unpin_neighbor(&mut self, neighbor_key: &NeighborKey) -> bool {
+        self.pinned_outbound.remove(neighbor_key)
+    }
+
+    /// Is this neighbor pinned?
+    pub fn is_neighbor_pinned(&self, neighbor_key: &NeighborKey) -> bool {
+        self.pinned_outbound.contains(neighbor_key)
+    }
+
+    /// Get the event IDs of the connections that pruning must leave alone: pinned peers, allowed
+    /// peers, and connections in use by the peer walk or the stacker DB syncs.
+    /// Returns None if we could not query the peer DB.
+    fn get_prune_safe_set(&self) -> Option<HashSet<usize>> {
         let mut safe: HashSet<usize> = HashSet::new();
         let now = get_epoch_time_secs();
 
         // don't prune allowed peers
         for (nk, event_id) in self.events.iter() {
+            // don't prune pinned peers
+            if self.pinned_outbound.contains(nk) {
+                test_debug!(
+                    "{:?}: event {} is pinned: {:?}",
+                    &self.local_peer,
+                    event_id,
+                    &nk
+                );
+                safe.insert(*event_id);
+            }
+
             let neighbor = match PeerDB::get_peer(
                 self.peerdb.conn(),
                 self.local_peer.network_id,
@@ -2317,7 +2823,7 @@ impl PeerNetwork {
                 },
                 Err(e) => {
                     debug!("Failed to query {:?}: {:?}", &nk, &e);
-                    return;
+                    return None;
                 }
             };
             if neighbor.allowed < 0 || (neighbor.allowed as u64) > now {
@@ -2349,16 +2855,32 @@ impl PeerNetwork {
             }
         }
 
+        Some(safe)
+    }
+
+    /// Prune inbound and outbound connections if we can
+    fn prune_connections(&mut self) -> () {
+        if cfg!(test) && self.connection_opts.disable_network_prune {
+            return;
+        }
+
+        debug!("Prune from {} connections", self.events.len());
+        let Some(safe) = self.get_prune_safe_set() else {
+            return;
+        };
+
         self.prune_frontier(&safe);
     }
 
     /// Regenerate our session private key and re-handshake with everyone.
-    fn rekey(&mut self, old_local_peer_opt: Option<&LocalPeer>) {
+    /// Returns the neighbors to whom we could not send the re-key Handshake.
+    fn rekey(&mut self, old_local_peer_opt: Option<&LocalPeer>) -> Vec<NeighborKey> {
         assert!(old_local_peer_opt.is_some());
         let _old_local_peer = old_local_peer_opt.unwrap();
 
         // begin re-key
         let mut msgs = HashMap::new();
+        let mut failed = vec![];
         for (event_id, convo) in self.peers.iter_mut() {
             let nk = convo.to_neighbor_key();
             let handshake_data = HandshakeData::from_local_peer(&self.local_peer);
@@ -2378,10 +2900,14 @@ impl PeerNetwork {
                 &nk
             );
 
-            if let Ok(msg) =
-                convo.sign_message(&self.chain_view, &_old_local_peer.private_key, handshake)
-            {
-                msgs.insert(nk, (*event_id, msg));
+            match convo.sign_message(&self.chain_view, &_old_local_peer.private_key, handshake) {
+                Ok(msg) => {
+                    msgs.insert(nk, (*event_id, msg));
+                }
+                Err(e) => {
+                    info!("Failed to sign re-key Handshake to {:?}: {:?}", &nk, &e);
+                    failed.push(nk);
+                }
             }
         }
 
@@ -2396,9 +2922,11 @@ impl PeerNetwork {
                 }
                 Err(e) => {
                     info!("Failed to rekey to {:?}: {:?}", &nk, &e);
+                    failed.push(nk);
                 }
             }
         }
+        failed
     }
 
     /// Flush relayed message handles, but don't block.
@@ -2570,16 +3098,12 @@ impl PeerNetwork {
         debug!("{:?}: begin obtaining public IP address", &self.local_peer);
 
         // pick a random outbound conversation to one of the initial neighbors
-        let mut idx = thread_rng().gen::<usize>() % self.peers.len();
-        for _ in 0..self.peers.len() + 1 {
-            let event_id = match self.peers.keys().skip(idx).next() {
-                Some(eid) => *eid,
-                None => {
-                    idx = 0;
-                    continue;
-                }
-            };
-            idx = (idx + 1) % self.peers.len();
+        let mut event_ids: Vec<usize> = self.peers.keys().copied().collect();
+        event_ids.sort();
+        let mut idx = self.walk_rng().gen::<usize>() % event_ids.len();
+        for _ in 0..event_ids.len() {
+            let event_id = event_ids[idx];
+            idx = (idx + 1) % event_ids.len();
 
             if let Some(convo) = self.peers.get_mut(&event_id) {
                 if !convo.is_authenticated() || !convo.is_outbound() {
@@ -5132,7 +5656,7 @@ impl PeerNetwork {
 
     /// Find inbound conversations that have authenticated, given a list of event ids to search
     /// for.  Add them to our network pingbacks
-    fn schedule_network_pingbacks(&mut self, event_ids: Vec<usize>) {
+    fn schedule_network_pingbacks(&mut self, mut event_ids: Vec<usize>) {
         if cfg!(test) && self.connection_opts.disable_pingbacks {
             test_debug!("{:?}: pingbacks are disabled for testing", &self.local_peer);
             return;
@@ -5155,6 +5679,7 @@ impl PeerNetwork {
         ));
 
         // add new pingbacks
+        event_ids.sort();
         for event_id in event_ids.into_iter() {
             if let Some(ref convo) = self.peers.get(&event_id) {
                 if !convo.is_outbound() && convo.is_authenticated() {
@@ -5201,13 +5726,11 @@ impl PeerNetwork {
 
                     if self.walk_pingbacks.len() > MAX_NEIGHBORS_DATA_LEN as usize {
                         // drop one at random
-                        let idx = thread_rng().gen::<usize>() % self.walk_pingbacks.len();
-                        let drop_addr = match self.walk_pingbacks.keys().skip(idx).next() {
-                            Some(ref addr) => (*addr).clone(),
-                            None => {
-                                continue;
-                            }
-                        };
+                        let mut pingback_addrs: Vec<_> =
+                            self.walk_pingbacks.keys().cloned().collect();
+                        pingback_addrs.sort();
+                        let idx = self.walk_rng().gen::<usize>() % pingback_addrs.len();
+                        let drop_addr = pingback_addrs.swap_remove(idx);
 
                         debug!("{:?}: drop pingback {:?}", &self.local_peer, drop_addr);
                         self.walk_pingbacks.remove(&drop_addr);
@@ -5256,6 +5779,18 @@ impl PeerNetwork {
         }
     }
 
+    /// Do we know all of our neighbors' block inventories, and has the block downloader since
+    /// made a full pass over the chain, up to the burnchain tip (not just the stable tip), without
+    /// finding anything to fetch?  See `NetworkResult::is_caught_up`.
+    pub fn is_caught_up(&self) -> bool {
+        let (Some(downloader), Some(inv_state)) = (&self.block_downloader, &self.inv_state) else {
+            return false;
+        };
+        // N.B. each full inventory sync pass wakes up an idle downloader, so if the downloader is
+        // idle now, then its last scan began after the inventories were synced.
+        inv_state.is_synced() && !downloader.is_initial_download() && downloader.is_download_idle()
+    }
+
     /// Get the local peer from the peer DB, but also preserve the public IP address
     pub fn load_local_peer(&self) -> Result<LocalPeer, net_error> {
         let mut lp = PeerDB::get_local_peer(&self.peerdb.conn())?;
@@ -5777,7 +6312,15 @@ impl PeerNetwork {
                 .expect("FATAL: failed to load local peer from peer DB");
             let old_local_peer = self.local_peer.clone();
             self.local_peer = new_local_peer;
-            self.rekey(Some(&old_local_peer));
+            let failed_rekeys = self.rekey(Some(&old_local_peer));
+            if !failed_rekeys.is_empty() {
+                info!(
+                    "{:?}: Failed to re-key with {} neighbor(s): {:?}",
+                    &self.local_peer,
+                    failed_rekeys.len(),
+                    &failed_rekeys
+                );
+            }
         }
 
         // update our relay statistics, so we know who to forward messages to
@@ -5850,6 +6393,10 @@ impl PeerNetwork {
             &stacks_epoch.block_limit,
             &stacks_epoch.epoch_id,
         ) {
+            if let MemPoolRejection::AlreadyKnown(_) = e {
+                debug!("Already have tx {}", txid);
+                return false;
+            }
             warn!("Transaction rejected from mempool, {}", &e.into_json(&txid));
             return false;
         }
@@ -6098,6 +6645,7 @@ impl PeerNetwork {
             ibd,
             p2p_poll_state,
         );
+        network_result.is_caught_up = self.is_caught_up();
 
         debug!("<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<< End Network Dispatch <<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<");
         Ok(network_result)
@@ -6111,8 +6659,7 @@ mod test {
     use clarity::vm::ast::stack_depth_checker::AST_CALL_STACK_DEPTH_BUFFER;
     use clarity::vm::types::StacksAddressExtensions;
     use clarity::vm::MAX_CALL_STACK_DEPTH;
-    use rand;
-    use rand::RngCore;
+    use rand::{self, RngCore};
     use stacks_common::types::chainstate::BurnchainHeaderHash;
     use stacks_common::util::secp256k1::Secp256k1PrivateKey;
     use stacks_common::util::{log, sleep_ms};
@@ -6126,6 +6673,7 @@ mod test {
     use crate::net::atlas::*;
     use crate::net::codec::*;
     use crate::net::db::*;
+    use crate::net::health::*;
     use crate::net::relay::test::make_contract_tx;
     use crate::net::test::*;
     use crate::net::*;
@@ -6140,15 +6688,8 @@ mod test {
 
     fn make_test_neighbor(port: u16) -> Neighbor {
         let neighbor = Neighbor {
-            addr: NeighborKey {
-                peer_version: 0x12345678,
-                network_id: 0x9abcdef0,
-                addrbytes: PeerAddress([
-                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0x7f,
-                    0x00, 0x00, 0x01,
-                ]),
-                port: port,
-            },
+            addr: NeighborKey::try_from_str(&format!("127.0.0.1:{}", port), 0x12345678, 0x9abcdef0)
+                .unwrap(),
             public_key: Secp256k1PublicKey::from_hex(
                 "02fa66b66f8971a8cd4d20ffded09674e030f0f33883f337f34b95ad4935bac0e3",
             )
@@ -6388,6 +6929,917 @@ mod test {
         })
     }
 
+    #[test]
+    fn test_shutdown_invalidates_handles() {
+        let mut p2p = make_test_p2p_network(&vec![]);
+        let neighbor = make_test_neighbor(2200);
+
+        // fill up the handle's buffer, so a caller has to wait for the p2p thread
+        let mut h = p2p.new_handle(1);
+        h.ban_peers(vec![neighbor.addr.clone()]).unwrap();
+        assert_eq!(
+            h.ban_peers(vec![neighbor.addr.clone()]),
+            Err(net_error::FullHandle)
+        );
+
+        let (sx, rx) = sync_channel(1);
+        let caller_thread = thread::spawn(move || {
+            sx.send(()).unwrap();
+            loop {
+                match h.ban_peers(vec![neighbor.addr.clone()]) {
+                    Err(net_error::FullHandle) => {
+                        sleep_ms(10);
+                    }
+                    res => {
+                        return res;
+                    }
+                }
+            }
+        });
+
+        // caller is waiting on the full handle
+        rx.recv().unwrap();
+        sleep_ms(100);
+
+        p2p.shutdown();
+        assert_eq!(p2p.handles.len(), 0);
+
+        let res = caller_thread.join().unwrap();
+        assert_eq!(res, Err(net_error::InvalidHandle));
+    }
+
+    fn add_test_convos(p2p: &mut PeerNetwork, neighbors: &[Neighbor], outbound: bool) {
+        for neighbor in neighbors.iter() {
+            let event_id = p2p.peers.len();
+            let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), neighbor.addr.port);
+            let mut convo = ConversationP2P::new(
+                p2p.local_peer.network_id,
+                p2p.peer_version,
+                &p2p.burnchain,
+                &addr,
+                &p2p.connection_opts,
+                outbound,
+                event_id,
+                p2p.epochs.clone(),
+            );
+            convo.peer_network_id = neighbor.addr.network_id;
+            p2p.peers.insert(event_id, convo);
+        }
+    }
+
+    fn health_check_codes(p2p: &PeerNetwork) -> Vec<HealthWarningCode> {
+        p2p.health_check().into_iter().map(|w| w.code).collect()
+    }
+
+    /// Step `peer_0` and each of `peers` until `peer_0` has an authenticated outbound
+    /// conversation with every one of `peers`.
+    fn connect_and_authenticate(peer_0: &mut TestPeer, peers: &mut [&mut TestPeer]) {
+        let nks: Vec<_> = peers.iter().map(|peer| peer.to_neighbor().addr).collect();
+        for _ in 0..100 {
+            peer_0.step().unwrap();
+            for peer in peers.iter_mut() {
+                peer.step().unwrap();
+            }
+
+            let authenticated = nks.iter().all(|nk| {
+                peer_0
+                    .network
+                    .get_neighbor_convo(nk)
+                    .map(|convo| convo.is_authenticated())
+                    .unwrap_or(false)
+            });
+            if authenticated
+                && PeerNetwork::count_outbound_conversations(&peer_0.network.peers)
+                    >= nks.len() as u64
+            {
+                return;
+            }
+            sleep_ms(100);
+        }
+        panic!("Peer 0 did not connect to {:?}", &nks);
+    }
+
+    #[test]
+    fn test_health_check() {
+        let now = get_epoch_time_secs();
+        let neighbor_1 = make_test_neighbor(2300);
+        let mut neighbor_2 = make_test_neighbor(2301);
+        neighbor_2.asn = neighbor_1.asn + 1;
+        let neighbor_3 = make_test_neighbor(2302);
+
+        // healthy network
+        let mut p2p = make_test_p2p_network(&vec![neighbor_1.clone(), neighbor_2.clone()]);
+        add_test_convos(&mut p2p, &[neighbor_1.clone(), neighbor_2.clone()], true);
+        p2p.walk_attempts = 1;
+        p2p.walk_last_success_ts = now;
+        assert_eq!(p2p.health_check(), vec![]);
+
+        // no outbound peers
+        let mut p2p = make_test_p2p_network(&vec![neighbor_1.clone()]);
+        assert_eq!(
+            health_check_codes(&p2p),
+            vec![HealthWarningCode::NoOutboundPeers]
+        );
+        add_test_convos(&mut p2p, &[neighbor_1.clone()], false);
+        assert_eq!(
+            health_check_codes(&p2p),
+            vec![HealthWarningCode::NoOutboundPeers]
+        );
+
+        // all peers in one ASN
+        let mut p2p = make_test_p2p_network(&vec![neighbor_1.clone(), neighbor_3.clone()]);
+        add_test_convos(&mut p2p, &[neighbor_1.clone(), neighbor_3.clone()], true);
+        let warnings = p2p.health_check();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].code, HealthWarningCode::SingleASN);
+        assert!(warnings[0].message.contains(&neighbor_1.asn.to_string()));
+
+        // no recent neighbor walk
+        let mut p2p = make_test_p2p_network(&vec![neighbor_1.clone(), neighbor_2.clone()]);
+        add_test_convos(&mut p2p, &[neighbor_1.clone(), neighbor_2.clone()], true);
+        p2p.walk_attempts = 3;
+        assert_eq!(
+            health_check_codes(&p2p),
+            vec![HealthWarningCode::NoRecentNeighborWalk]
+        );
+        p2p.walk_last_success_ts = now - NEIGHBOR_WALK_STALE_SECS - 1;
+        assert_eq!(
+            health_check_codes(&p2p),
+            vec![HealthWarningCode::NoRecentNeighborWalk]
+        );
+        p2p.walk_last_success_ts = now;
+        assert_eq!(health_check_codes(&p2p), vec![]);
+
+        // block download stalled
+        let mut downloader = BlockDownloader::new(1000, 10, 10);
+        downloader.last_download_at = now - BLOCK_DOWNLOAD_STALL_SECS - 1;
+        p2p.block_downloader = Some(downloader);
+        assert_eq!(
+            health_check_codes(&p2p),
+            vec![HealthWarningCode::BlockDownloadStalled]
+        );
+        p2p.block_downloader.as_mut().unwrap().last_download_at = now;
+        assert_eq!(health_check_codes(&p2p), vec![]);
+
+        // HTTP at connection cap
+        p2p.connection_opts.max_http_clients = 0;
+        assert_eq!(
+            health_check_codes(&p2p),
+            vec![HealthWarningCode::HttpAtCapacity]
+        );
+        p2p.connection_opts.max_http_clients = 10;
+
+        // rekey overdue
+        p2p.local_peer.private_key_expire = p2p.chain_view.burn_block_height - 1;
+        assert_eq!(
+            health_check_codes(&p2p),
+            vec![HealthWarningCode::RekeyOverdue]
+        );
+
+        // everything at once, in order of severity
+        let mut p2p = make_test_p2p_network(&vec![]);
+        p2p.walk_attempts = 1;
+        p2p.block_downloader = Some(BlockDownloader::new(1000, 10, 10));
+        p2p.block_downloader.as_mut().unwrap().last_download_at = 1;
+        p2p.connection_opts.max_http_clients = 0;
+        p2p.local_peer.private_key_expire = 0;
+        assert_eq!(
+            health_check_codes(&p2p),
+            vec![
+                HealthWarningCode::NoOutboundPeers,
+                HealthWarningCode::NoRecentNeighborWalk,
+                HealthWarningCode::BlockDownloadStalled,
+                HealthWarningCode::HttpAtCapacity,
+                HealthWarningCode::RekeyOverdue,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_healthiest_neighbors() {
+        with_timeout(100, || {
+            let mut peer_0_config = TestPeerConfig::new(function_name!(), 2350, 2351);
+            let peer_1_config = TestPeerConfig::new(function_name!(), 2352, 2353);
+            let peer_2_config = TestPeerConfig::new(function_name!(), 2354, 2355);
+
+            peer_0_config.add_neighbor(&peer_1_config.to_neighbor());
+            peer_0_config.add_neighbor(&peer_2_config.to_neighbor());
+
+            let mut peer_0 = TestPeer::new(peer_0_config);
+            let mut peer_1 = TestPeer::new(peer_1_config);
+            let mut peer_2 = TestPeer::new(peer_2_config);
+
+            let nk_1 = peer_1.to_neighbor().addr;
+            let nk_2 = peer_2.to_neighbor().addr;
+
+            connect_and_authenticate(&mut peer_0, &mut [&mut peer_1, &mut peer_2]);
+
+            // make peer 2 look much healthier than peer 1
+            let now = get_epoch_time_secs();
+            for (nk, bytes_rx) in [(&nk_1, 0), (&nk_2, 1_000_000_000)] {
+                let event_id = peer_0.network.get_event_id(nk).unwrap();
+                let convo = peer_0.network.peers.get_mut(&event_id).unwrap();
+                convo.stats.first_contact_time = now - 10;
+                convo.stats.last_contact_time = now;
+                convo.stats.last_handshake_time = now;
+                convo.stats.bytes_rx = bytes_rx;
+            }
+
+            let healthiest = peer_0.network.healthiest_neighbors(2);
+            assert_eq!(healthiest.len(), 2);
+            assert_eq!(healthiest[0], nk_2);
+            assert_eq!(healthiest[1], nk_1);
+
+            assert_eq!(peer_0.network.healthiest_neighbors(1), vec![nk_2.clone()]);
+            assert_eq!(peer_0.network.healthiest_neighbors(0), vec![]);
+        })
+    }
+
+    #[test]
+    fn test_process_neighbor_walk_rebalances_asns() {
+        with_timeout(100, || {
+            let mut peer_0_config = TestPeerConfig::new(function_name!(), 2372, 2373);
+            let mut peer_1_config = TestPeerConfig::new(function_name!(), 2374, 2375);
+            let mut peer_2_config = TestPeerConfig::new(function_name!(), 2376, 2377);
+
+            for config in [&mut peer_1_config, &mut peer_2_config] {
+                config.connection_opts.disable_neighbor_walk = true;
+                config.connection_opts.disable_pingbacks = true;
+            }
+
+            peer_0_config.add_neighbor(&peer_1_config.to_neighbor());
+            peer_0_config.add_neighbor(&peer_2_config.to_neighbor());
+
+            let mut peer_0 = TestPeer::new(peer_0_config);
+            let mut peer_1 = TestPeer::new(peer_1_config);
+            let mut peer_2 = TestPeer::new(peer_2_config);
+
+            let nk_1 = peer_1.to_neighbor().addr;
+            let nk_2 = peer_2.to_neighbor().addr;
+
+            connect_and_authenticate(&mut peer_0, &mut [&mut peer_1, &mut peer_2]);
+
+            peer_0.network.walk = None;
+            peer_0.network.connection_opts.soft_num_neighbors = 1;
+
+            // no ASN data, so nothing happens
+            peer_0
+                .network
+                .process_neighbor_walk(NeighborWalkResult::new());
+            assert_eq!(peer_0.network.walk_result.asn_diversity(), 0);
+            assert!(peer_0.network.is_registered(&nk_1));
+            assert!(peer_0.network.is_registered(&nk_2));
+
+            // put both neighbors into the same ASN
+            {
+                let tx = peer_0.network.peerdb.tx_begin().unwrap();
+                for nk in [&nk_1, &nk_2] {
+                    let mut neighbor = PeerDB::get_peer(&tx, nk.network_id, &nk.addrbytes, nk.port)
+                        .unwrap()
+                        .unwrap();
+                    neighbor.asn = 1;
+                    neighbor.org = 1;
+                    PeerDB::update_peer(&tx, &neighbor).unwrap();
+                }
+                tx.commit().unwrap();
+            }
+
+            // neither gets dropped if one is pinned and the other is always-allowed
+            assert!(peer_0.network.pin_neighbor(&nk_2));
+            {
+                let tx = peer_0.network.peerdb.tx_begin().unwrap();
+                let mut neighbor =
+                    PeerDB::get_peer(&tx, nk_1.network_id, &nk_1.addrbytes, nk_1.port)
+                        .unwrap()
+                        .unwrap();
+                neighbor.allowed = -1;
+                PeerDB::update_peer(&tx, &neighbor).unwrap();
+                tx.commit().unwrap();
+            }
+            peer_0
+                .network
+                .process_neighbor_walk(NeighborWalkResult::new());
+            assert_eq!(peer_0.network.walk_result.asn_diversity(), 1);
+            assert!(peer_0.network.is_registered(&nk_1));
+            assert!(peer_0.network.is_registered(&nk_2));
+
+            // once it's no longer allowed, one of them gets dropped, but never the pinned one
+            {
+                let tx = peer_0.network.peerdb.tx_begin().unwrap();
+                let mut neighbor =
+                    PeerDB::get_peer(&tx, nk_1.network_id, &nk_1.addrbytes, nk_1.port)
+                        .unwrap()
+                        .unwrap();
+                neighbor.allowed = 0;
+                PeerDB::update_peer(&tx, &neighbor).unwrap();
+                tx.commit().unwrap();
+            }
+            peer_0
+                .network
+                .process_neighbor_walk(NeighborWalkResult::new());
+            assert_eq!(peer_0.network.walk_result.asn_diversity(), 1);
+            assert!(!peer_0.network.is_registered(&nk_1));
+            assert!(peer_0.network.is_registered(&nk_2));
+        })
+    }
+
+    #[test]
+    fn test_relay_message_filter() {
+        with_timeout(100, || {
+            let mut peer_0_config = TestPeerConfig::new(function_name!(), 2368, 2369);
+            let peer_1_config = TestPeerConfig::new(function_name!(), 2370, 2371);
+
+            peer_0_config
+                .connection_opts
+                .relay_message_filter
+                .remove(&StacksMessageID::Ping);
+            peer_0_config.add_neighbor(&peer_1_config.to_neighbor());
+
+            let mut peer_0 = TestPeer::new(peer_0_config);
+            let mut peer_1 = TestPeer::new(peer_1_config);
+
+            let nk_1 = peer_1.to_neighbor().addr;
+
+            connect_and_authenticate(&mut peer_0, &mut [&mut peer_1]);
+
+            let chain_view = peer_0.network.chain_view.clone();
+            let private_key = peer_0.network.local_peer.private_key.clone();
+            let event_id = peer_0.network.get_event_id(&nk_1).unwrap();
+            let convo = peer_0.network.peers.get_mut(&event_id).unwrap();
+
+            let ping = convo
+                .sign_message(
+                    &chain_view,
+                    &private_key,
+                    StacksMessageType::Ping(PingData::new()),
+                )
+                .unwrap();
+            let nack = convo
+                .sign_message(
+                    &chain_view,
+                    &private_key,
+                    StacksMessageType::Nack(NackData::new(0)),
+                )
+                .unwrap();
+
+            // pings are filtered
+            match peer_0.network.relay_signed_message(&nk_1, ping) {
+                Err(net_error::MessageTypeFiltered) => {}
+                x => panic!("Expected MessageTypeFiltered, got {:?}", &x),
+            }
+
+            // everything else still gets relayed
+            peer_0.network.relay_signed_message(&nk_1, nack).unwrap();
+        })
+    }
+
+    #[test]
+    fn test_relay_signed_message_refuses_relayer_loop() {
+        with_timeout(100, || {
+            let mut peer_0_config = TestPeerConfig::new(function_name!(), 2378, 2379);
+            let mut peer_1_config = TestPeerConfig::new(function_name!(), 2380, 2381);
+
+            peer_1_config.connection_opts.disable_neighbor_walk = true;
+            peer_1_config.connection_opts.disable_pingbacks = true;
+            peer_0_config.add_neighbor(&peer_1_config.to_neighbor());
+
+            let mut peer_0 = TestPeer::new(peer_0_config);
+            let mut peer_1 = TestPeer::new(peer_1_config);
+
+            let nk_1 = peer_1.to_neighbor().addr;
+
+            connect_and_authenticate(&mut peer_0, &mut [&mut peer_1]);
+
+            let chain_view = peer_0.network.chain_view.clone();
+            let private_key = peer_0.network.local_peer.private_key.clone();
+            let event_id = peer_0.network.get_event_id(&nk_1).unwrap();
+            let convo = peer_0.network.peers.get_mut(&event_id).unwrap();
+
+            let mut looped = convo
+                .sign_message(
+                    &chain_view,
+                    &private_key,
+                    StacksMessageType::Nack(NackData::new(0)),
+                )
+                .unwrap();
+            let fresh = convo
+                .sign_message(
+                    &chain_view,
+                    &private_key,
+                    StacksMessageType::Nack(NackData::new(0)),
+                )
+                .unwrap();
+
+            // peer 1 already relayed this message
+            let peer_1_addr = NeighborAddress {
+                addrbytes: nk_1.addrbytes.clone(),
+                port: nk_1.port,
+                public_key_hash: Hash160::from_node_public_key(&Secp256k1PublicKey::from_private(
+                    &peer_1.network.local_peer.private_key,
+                )),
+            };
+            looped
+                .sign_relay(&peer_1.network.local_peer.private_key, 1, &peer_1_addr)
+                .unwrap();
+
+            match peer_0.network.relay_signed_message(&nk_1, looped) {
+                Err(net_error::RelayLoop) => {}
+                x => panic!("Expected RelayLoop, got {:?}", &x),
+            }
+
+            // messages peer 1 has not relayed still get sent
+            peer_0.network.relay_signed_message(&nk_1, fresh).unwrap();
+        })
+    }
+
+    #[test]
+    fn test_relay_signed_message_suppresses_duplicates() {
+        with_timeout(100, || {
+            let mut peer_0_config = TestPeerConfig::new(function_name!(), 2382, 2383);
+            let mut peer_1_config = TestPeerConfig::new(function_name!(), 2384, 2385);
+
+            peer_1_config.connection_opts.disable_neighbor_walk = true;
+            peer_1_config.connection_opts.disable_pingbacks = true;
+            peer_0_config.add_neighbor(&peer_1_config.to_neighbor());
+
+            let mut peer_0 = TestPeer::new(peer_0_config);
+            let mut peer_1 = TestPeer::new(peer_1_config);
+
+            let nk_1 = peer_1.to_neighbor().addr;
+
+            connect_and_authenticate(&mut peer_0, &mut [&mut peer_1]);
+
+            let chain_view = peer_0.network.chain_view.clone();
+            let private_key = peer_0.network.local_peer.private_key.clone();
+            let event_id = peer_0.network.get_event_id(&nk_1).unwrap();
+            let convo = peer_0.network.peers.get_mut(&event_id).unwrap();
+
+            // messages forwarded on behalf of some other peer
+            let relayer_privkey = Secp256k1PrivateKey::new();
+            let relayer_addr = NeighborAddress {
+                addrbytes: PeerAddress::from_ipv4(1, 2, 3, 4),
+                port: 20444,
+                public_key_hash: Hash160::from_node_public_key(&Secp256k1PublicKey::from_private(
+                    &relayer_privkey,
+                )),
+            };
+
+            let mut msgs = vec![];
+            for nack in [0, 0, 1] {
+                let mut msg = convo
+                    .sign_message(
+                        &chain_view,
+                        &private_key,
+                        StacksMessageType::Nack(NackData::new(nack)),
+                    )
+                    .unwrap();
+                msg.sign_relay(&relayer_privkey, 1, &relayer_addr).unwrap();
+                msgs.push(msg);
+            }
+            let different = msgs.pop().unwrap();
+            let repeated = msgs.pop().unwrap();
+            let first = msgs.pop().unwrap();
+
+            // messages we originate ourselves
+            let mut own_msgs = vec![];
+            for _ in 0..2 {
+                own_msgs.push(
+                    convo
+                        .sign_message(
+                            &chain_view,
+                            &private_key,
+                            StacksMessageType::Nack(NackData::new(2)),
+                        )
+                        .unwrap(),
+                );
+            }
+
+            peer_0.network.relay_signed_message(&nk_1, first).unwrap();
+
+            // same payload, so it's suppressed even though it was signed separately
+            match peer_0.network.relay_signed_message(&nk_1, repeated.clone()) {
+                Err(net_error::DuplicateRelay) => {}
+                x => panic!("Expected DuplicateRelay, got {:?}", &x),
+            }
+
+            // likewise if the caller already hashed the payload
+            let digest = SeenMessageCache::payload_digest(&repeated.payload);
+            match peer_0
+                .network
+                .relay_signed_message_with_digest(&nk_1, repeated, Some(digest))
+            {
+                Err(net_error::DuplicateRelay) => {}
+                x => panic!("Expected DuplicateRelay, got {:?}", &x),
+            }
+
+            // a different payload still gets relayed
+            peer_0
+                .network
+                .relay_signed_message(&nk_1, different)
+                .unwrap();
+
+            // our own messages can be re-sent on purpose
+            for own_msg in own_msgs.into_iter() {
+                peer_0.network.relay_signed_message(&nk_1, own_msg).unwrap();
+            }
+        })
+    }
+
+    #[test]
+    fn test_send_signed_request_with_timeout() {
+        with_timeout(100, || {
+            let mut peer_0_config = TestPeerConfig::new(function_name!(), 2386, 2387);
+            let mut peer_1_config = TestPeerConfig::new(function_name!(), 2388, 2389);
+
+            peer_1_config.connection_opts.disable_neighbor_walk = true;
+            peer_1_config.connection_opts.disable_pingbacks = true;
+            peer_0_config.add_neighbor(&peer_1_config.to_neighbor());
+
+            let mut peer_0 = TestPeer::new(peer_0_config);
+            let mut peer_1 = TestPeer::new(peer_1_config);
+
+            let nk_1 = peer_1.to_neighbor().addr;
+
+            connect_and_authenticate(&mut peer_0, &mut [&mut peer_1]);
+
+            let chain_view = peer_0.network.chain_view.clone();
+            let private_key = peer_0.network.local_peer.private_key.clone();
+            let event_id = peer_0.network.get_event_id(&nk_1).unwrap();
+            let convo = peer_0.network.peers.get_mut(&event_id).unwrap();
+            let ping = convo
+                .sign_message(
+                    &chain_view,
+                    &private_key,
+                    StacksMessageType::Ping(PingData::new()),
+                )
+                .unwrap();
+
+            let mut h = peer_0.network.new_handle(1);
+
+            // only peer 0 keeps running; peer 1 never answers
+            let p2p_thread = thread::spawn(move || {
+                for _ in 0..40 {
+                    let _ = peer_0.step();
+                    sleep_ms(100);
+                }
+                peer_0
+            });
+
+            let start = Instant::now();
+            match h.send_signed_request_with_timeout(nk_1, ping, 60, Duration::from_secs(2)) {
+                Err(net_error::Timeout) => {}
+                x => panic!("Expected Timeout, got {:?}", &x),
+            }
+            assert!(start.elapsed() >= Duration::from_secs(2));
+
+            let _peer_0 = p2p_thread.join().unwrap();
+            drop(peer_1);
+        })
+    }
+
+    #[test]
+    fn test_network_handle_ping_peer() {
+        with_timeout(100, || {
+            let mut peer_0_config = TestPeerConfig::new(function_name!(), 2390, 2391);
+            let mut peer_1_config = TestPeerConfig::new(function_name!(), 2392, 2393);
+
+            peer_1_config.connection_opts.disable_neighbor_walk = true;
+            peer_1_config.connection_opts.disable_pingbacks = true;
+            peer_0_config.add_neighbor(&peer_1_config.to_neighbor());
+
+            let mut peer_0 = TestPeer::new(peer_0_config);
+            let mut peer_1 = TestPeer::new(peer_1_config);
+
+            let nk_1 = peer_1.to_neighbor().addr;
+
+            connect_and_authenticate(&mut peer_0, &mut [&mut peer_1]);
+
+            let mut h = peer_0.network.new_handle(1);
+
+            let p2p_thread = thread::spawn(move || {
+                for _ in 0..40 {
+                    let _ = peer_0.step();
+                    let _ = peer_1.step();
+                    sleep_ms(100);
+                }
+                (peer_0, peer_1)
+            });
+
+            let rtt = h.ping_peer(&nk_1).unwrap();
+            assert!(rtt < 4_000);
+
+            // can't ping a peer we're not connected to
+            let mut unknown_nk = nk_1.clone();
+            unknown_nk.port += 1000;
+            match h.ping_peer(&unknown_nk) {
+                Err(net_error::NoSuchNeighbor) => {}
+                x => panic!("Expected NoSuchNeighbor, got {:?}", &x),
+            }
+
+            let _peers = p2p_thread.join().unwrap();
+        })
+    }
+
+    #[test]
+    fn test_dispatch_requests_many_per_handle() {
+        let mut p2p = make_test_p2p_network(&vec![]);
+        p2p.connection_opts.max_requests_per_handle_per_pass = 4;
+
+        let mut h_busy = p2p.new_handle(10);
+        let mut h_idle = p2p.new_handle(10);
+
+        // pings to unknown neighbors fail immediately, and the failure is sent back on the
+        // reply channel, so the replies count the dispatched requests
+        let (busy_tx, busy_rx) = sync_channel(10);
+        let (idle_tx, idle_rx) = sync_channel(10);
+        let nk = make_test_neighbor(2400).addr;
+        for _ in 0..10 {
+            h_busy
+                .send_request(NetworkRequest::Ping(nk.clone(), busy_tx.clone()))
+                .unwrap();
+        }
+        h_idle
+            .send_request(NetworkRequest::Ping(nk.clone(), idle_tx.clone()))
+            .unwrap();
+
+        let mut dispatched = vec![];
+        for _ in 0..3 {
+            p2p.dispatch_requests();
+            let mut num_busy = 0;
+            while let Ok(reply) = busy_rx.try_recv() {
+                assert!(matches!(reply, Err(net_error::NoSuchNeighbor)));
+                num_busy += 1;
+            }
+            let mut num_idle = 0;
+            while let Ok(reply) = idle_rx.try_recv() {
+                assert!(matches!(reply, Err(net_error::NoSuchNeighbor)));
+                num_idle += 1;
+            }
+            dispatched.push((num_busy, num_idle));
+        }
+
+        // the busy handle gets several requests per pass, but no more than the limit, and
+        // doesn't crowd out the other handle
+        assert_eq!(dispatched, vec![(4, 1), (4, 0), (2, 0)]);
+    }
+
+    #[test]
+    fn test_dispatch_requests_removes_dead_handles() {
+        let mut p2p = make_test_p2p_network(&vec![]);
+        p2p.connection_opts.max_requests_per_handle_per_pass = 4;
+
+        let mut h_dead = p2p.new_handle(10);
+        let _h_live = p2p.new_handle(10);
+        let h_idle = p2p.new_handle(10);
+        assert_eq!(p2p.handles.len(), 3);
+
+        // a dropped handle is removed only once its pending requests have been dispatched
+        let (reply_tx, reply_rx) = sync_channel(10);
+        let nk = make_test_neighbor(2400).addr;
+        for _ in 0..6 {
+            h_dead
+                .send_request(NetworkRequest::Ping(nk.clone(), reply_tx.clone()))
+                .unwrap();
+        }
+        drop(h_dead);
+        drop(h_idle);
+
+        p2p.dispatch_requests();
+        assert_eq!(p2p.handles.len(), 2);
+        p2p.dispatch_requests();
+        assert_eq!(p2p.handles.len(), 1);
+        assert_eq!(reply_rx.try_iter().count(), 6);
+
+        // even a limit of 0 still polls each handle once
+        p2p.connection_opts.max_requests_per_handle_per_pass = 0;
+        let h_other = p2p.new_handle(10);
+        drop(h_other);
+        p2p.dispatch_requests();
+        assert_eq!(p2p.handles.len(), 1);
+    }
+
+    #[test]
+    fn test_neighbor_key_try_from_str() {
+        let nk = NeighborKey::try_from_str("127.0.0.1:20444", 0x18000000, 0x80000000).unwrap();
+        assert_eq!(
+            nk,
+            NeighborKey::new(
+                0x18000000,
+                0x80000000,
+                PeerAddress::from_ipv4(127, 0, 0, 1),
+                20444
+            )
+        );
+        assert_eq!(
+            nk.to_socketaddr(),
+            "127.0.0.1:20444".parse::<SocketAddr>().unwrap()
+        );
+
+        let nk = NeighborKey::try_from_str("[::1]:20444", 0x18000000, 0x80000000).unwrap();
+        assert_eq!(
+            nk.to_socketaddr(),
+            "[::1]:20444".parse::<SocketAddr>().unwrap()
+        );
+
+        // bad ports
+        assert!(NeighborKey::try_from_str("127.0.0.1:0", 0x18000000, 0x80000000).is_err());
+        assert!(NeighborKey::try_from_str("127.0.0.1:65536", 0x18000000, 0x80000000).is_err());
+        assert!(NeighborKey::try_from_str("127.0.0.1", 0x18000000, 0x80000000).is_err());
+
+        // bad addresses
+        assert!(NeighborKey::try_from_str("127.0.0:20444", 0x18000000, 0x80000000).is_err());
+        assert!(NeighborKey::try_from_str("localhost:20444", 0x18000000, 0x80000000).is_err());
+    }
+
+    #[test]
+    fn test_rekey_reports_failed_neighbors() {
+        with_timeout(100, || {
+            let mut peer_0_config = TestPeerConfig::new(function_name!(), 2364, 2365);
+            let peer_1_config = TestPeerConfig::new(function_name!(), 2366, 2367);
+
+            peer_0_config.add_neighbor(&peer_1_config.to_neighbor());
+
+            let mut peer_0 = TestPeer::new(peer_0_config);
+            let mut peer_1 = TestPeer::new(peer_1_config);
+
+            let nk_1 = peer_1.to_neighbor().addr;
+
+            connect_and_authenticate(&mut peer_0, &mut [&mut peer_1]);
+
+            let old_local_peer = peer_0.network.local_peer.clone();
+
+            // everyone gets the re-key handshake
+            assert_eq!(peer_0.network.rekey(Some(&old_local_peer)), vec![]);
+
+            // forget how to reach peer 1, so the re-key handshake can't be sent
+            let event_id = peer_0.network.get_event_id(&nk_1).unwrap();
+            let convo_nk = peer_0
+                .network
+                .peers
+                .get(&event_id)
+                .unwrap()
+                .to_neighbor_key();
+            peer_0.network.events.retain(|_, eid| *eid != event_id);
+
+            assert_eq!(peer_0.network.rekey(Some(&old_local_peer)), vec![convo_nk]);
+        })
+    }
+
+    #[test]
+    fn test_outbound_only_refuses_inbound() {
+        with_timeout(100, || {
+            let mut peer_0_config = TestPeerConfig::new(function_name!(), 2360, 2361);
+            let peer_1_config = TestPeerConfig::new(function_name!(), 2362, 2363);
+
+            peer_0_config.connection_opts.outbound_only = true;
+            peer_0_config.add_neighbor(&peer_1_config.to_neighbor());
+
+            let mut peer_0 = TestPeer::new(peer_0_config);
+            let mut peer_1 = TestPeer::new(peer_1_config);
+
+            let nk_1 = peer_1.to_neighbor().addr;
+
+            // outbound connections still work
+            connect_and_authenticate(&mut peer_0, &mut [&mut peer_1]);
+
+            // nothing is listening on peer 0's p2p port
+            let p2p_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 2360);
+            assert!(std::net::TcpStream::connect(&p2p_addr).is_err());
+
+            // peer 0's HTTP server is unaffected
+            let http_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 2361);
+            assert!(std::net::TcpStream::connect(&http_addr).is_ok());
+        })
+    }
+
+    #[test]
+    fn test_connect_peer_with_pubkey() {
+        with_timeout(100, || {
+            let mut peer_0_config = TestPeerConfig::new(function_name!(), 2330, 2331);
+            let mut peer_1_config = TestPeerConfig::new(function_name!(), 2332, 2333);
+
+            // peer 1 must not connect back to peer 0, since an inbound conversation would shadow
+            // the outbound one under the same neighbor key
+            peer_1_config.connection_opts.disable_neighbor_walk = true;
+            peer_1_config.connection_opts.disable_pingbacks = true;
+
+            peer_0_config.add_neighbor(&peer_1_config.to_neighbor());
+
+            let mut peer_0 = TestPeer::new(peer_0_config);
+            let mut peer_1 = TestPeer::new(peer_1_config);
+
+            let neighbor_1 = peer_1.to_neighbor();
+            let nk_1 = neighbor_1.addr.clone();
+
+            // the conversation is given the expected key as soon as it is registered, so only a
+            // completed handshake shows that peer 1 proved it has that key
+            let handshaked = |peer_0: &TestPeer| {
+                peer_0
+                    .network
+                    .get_neighbor_convo(&nk_1)
+                    .map(|convo| convo.stats.last_handshake_time > 0)
+                    .unwrap_or(false)
+            };
+
+            // peer 1 can't authenticate with a key it doesn't have
+            let other_public_key = Secp256k1PublicKey::from_private(&Secp256k1PrivateKey::new());
+            peer_0
+                .network
+                .connect_peer_with_pubkey(&nk_1, &other_public_key)
+                .unwrap();
+            for _ in 0..20 {
+                peer_0.step().unwrap();
+                peer_1.step().unwrap();
+                assert!(!handshaked(&peer_0));
+                sleep_ms(100);
+            }
+
+            // the right key replaces the wrong one on the next connection
+            peer_0.network.deregister_neighbor(&nk_1);
+            peer_0
+                .network
+                .connect_peer_with_pubkey(&nk_1, &neighbor_1.public_key)
+                .unwrap();
+            for _ in 0..100 {
+                if handshaked(&peer_0) {
+                    break;
+                }
+                peer_0.step().unwrap();
+                peer_1.step().unwrap();
+                sleep_ms(100);
+            }
+            assert!(handshaked(&peer_0));
+            assert_eq!(
+                peer_0
+                    .network
+                    .get_neighbor_convo(&nk_1)
+                    .unwrap()
+                    .get_public_key(),
+                Some(neighbor_1.public_key.clone())
+            );
+
+            assert!(peer_0.network.clear_expected_public_key(&nk_1));
+            assert!(!peer_0.network.clear_expected_public_key(&nk_1));
+        })
+    }
+
+    #[test]
+    fn test_prune_connections_keeps_pinned_neighbors() {
+        with_timeout(100, || {
+            let mut peer_0_config = TestPeerConfig::new(function_name!(), 2320, 2321);
+            let mut peer_1_config = TestPeerConfig::new(function_name!(), 2322, 2323);
+            let mut peer_2_config = TestPeerConfig::new(function_name!(), 2324, 2325);
+
+            // peers 1 and 2 must not connect back to peer 0, since an inbound conversation would
+            // shadow the outbound one under the same neighbor key
+            for config in [&mut peer_1_config, &mut peer_2_config] {
+                config.connection_opts.disable_neighbor_walk = true;
+                config.connection_opts.disable_pingbacks = true;
+            }
+
+            peer_0_config.add_neighbor(&peer_1_config.to_neighbor());
+            peer_0_config.add_neighbor(&peer_2_config.to_neighbor());
+
+            let mut peer_0 = TestPeer::new(peer_0_config);
+            let mut peer_1 = TestPeer::new(peer_1_config);
+            let mut peer_2 = TestPeer::new(peer_2_config);
+
+            let nk_1 = peer_1.to_neighbor().addr;
+            let nk_2 = peer_2.to_neighbor().addr;
+
+            // wait for peer 0 to connect to both peers
+            connect_and_authenticate(&mut peer_0, &mut [&mut peer_1, &mut peer_2]);
+
+            assert!(peer_0.network.pin_neighbor(&nk_1));
+            assert!(!peer_0.network.pin_neighbor(&nk_1));
+            assert!(peer_0.network.is_neighbor_pinned(&nk_1));
+            assert!(!peer_0.network.is_neighbor_pinned(&nk_2));
+
+            // bootstrap neighbors are always allowed, so revoke that first
+            {
+                let tx = peer_0.network.peerdb.tx_begin().unwrap();
+                for nk in [&nk_1, &nk_2] {
+                    PeerDB::set_allow_peer(&tx, nk.network_id, &nk.addrbytes, nk.port, 0).unwrap();
+                }
+                tx.commit().unwrap();
+            }
+
+            // prune everything that isn't protected
+            peer_0.network.walk = None;
+            peer_0.network.stacker_db_syncs = None;
+            peer_0.network.connection_opts.soft_num_neighbors = 0;
+            peer_0.network.connection_opts.soft_max_neighbors_per_org = 0;
+            peer_0.network.prune_connections();
+
+            assert!(peer_0.network.is_registered(&nk_1));
+            assert!(!peer_0.network.is_registered(&nk_2));
+
+            assert!(peer_0.network.unpin_neighbor(&nk_1));
+            assert!(!peer_0.network.unpin_neighbor(&nk_1));
+            assert!(!peer_0.network.is_neighbor_pinned(&nk_1));
+        })
+    }
+
     #[test]
     #[ignore]
     fn test_dispatch_requests_connect_and_ban() {
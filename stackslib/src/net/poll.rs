@@ -49,7 +49,7 @@ impl NetworkPollState {
 #[derive(Debug)]
 pub struct NetworkServerState {
     addr: SocketAddr,
-    server_socket: mio_net::TcpListener,
+    server_socket: Option<mio_net::TcpListener>,
     server_event: mio::Token,
 }
 
@@ -151,7 +151,7 @@ impl NetworkState {
 
         let network_server = NetworkServerState {
             addr: local_addr.clone(),
-            server_socket: server,
+            server_socket: Some(server),
             server_event: mio::Token(next_server_event),
         };
 
@@ -166,6 +166,29 @@ impl NetworkState {
         Ok((next_server_event, local_addr))
     }
 
+    /// Allocate a server handle for the given address without listening on it.
+    /// Outbound sockets can be registered under the returned handle, but no inbound connections
+    /// will ever be accepted on it.
+    /// Returns the handle to the poll state, used to key network poll events.
+    pub fn bind_outbound_only(&mut self, addr: &SocketAddr) -> Result<usize, net_error> {
+        let next_server_event = self.next_event_id()?;
+        let network_server = NetworkServerState {
+            addr: addr.clone(),
+            server_socket: None,
+            server_event: mio::Token(next_server_event),
+        };
+
+        assert!(
+            !self.event_map.contains_key(&next_server_event),
+            "BUG: failed to generate an unused server event ID"
+        );
+
+        self.servers.push(network_server);
+        self.event_map.insert(next_server_event, 0); // server events always mapped to 0
+
+        Ok(next_server_event)
+    }
+
     /// Register a socket for read/write notifications with this poller.
     /// Try to use the given hint_event_id value, but generate a different event ID if it's been
     /// taken.
@@ -403,8 +426,14 @@ impl NetworkState {
                             )
                         });
 
+                    let Some(server_socket) = server.server_socket.as_ref() else {
+                        // outbound-only server; nothing to accept
+                        is_server_event = true;
+                        break;
+                    };
+
                     loop {
-                        let (client_sock, client_addr) = match server.server_socket.accept() {
+                        let (client_sock, client_addr) = match server_socket.accept() {
                             Ok((client_sock, client_addr)) => (client_sock, client_addr),
                             Err(e) => match e.kind() {
                                 ErrorKind::WouldBlock => {
@@ -423,7 +452,7 @@ impl NetworkState {
                             Some(eid) => eid,
                             None => {
                                 // no poll slots available. Close the socket and carry on.
-                                info!("Too many peers on {:?}, closing {:?} (events: {}, in-flight: {}, capacity: {})", &server.server_socket, &client_sock, self.event_map.len(), new_events.len(), self.event_capacity);
+                                info!("Too many peers on {:?}, closing {:?} (events: {}, in-flight: {}, capacity: {})", server_socket, &client_sock, self.event_map.len(), new_events.len(), self.event_capacity);
                                 let _ = client_sock.shutdown(Shutdown::Both);
                                 continue;
                             }
@@ -441,7 +470,7 @@ impl NetworkState {
                             &client_addr,
                             self.event_map.len(),
                             self.event_capacity,
-                            &server.server_socket
+                            server_socket
                         );
 
                         poll_state.new.insert(next_event_id, client_sock);
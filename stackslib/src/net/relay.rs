@@ -87,6 +87,17 @@ pub struct RelayerStats {
     next_priority: u64,
 }
 
+/// Bounded LRU cache of the (neighbor, payload digest) pairs we recently forwarded, so we don't
+/// relay the same message to the same neighbor twice.  Entries expire after `ttl` seconds.
+pub struct SeenMessageCache {
+    capacity: usize,
+    ttl: u64,
+    /// (neighbor, digest) --> (LRU priority, time forwarded)
+    seen: HashMap<(NeighborKey, Sha512Trunc256Sum), (u64, u64)>,
+    seen_updates: BTreeMap<u64, (NeighborKey, Sha512Trunc256Sum)>,
+    next_priority: u64,
+}
+
 pub struct ProcessedNetReceipts {
     pub mempool_txs_added: Vec<StacksTransaction>,
     pub processed_unconfirmed_state: ProcessedUnconfirmedState,
@@ -474,6 +485,66 @@ impl RelayerStats {
     }
 }
 
+impl SeenMessageCache {
+    pub fn new(capacity: usize, ttl: u64) -> SeenMessageCache {
+        SeenMessageCache {
+            capacity,
+            ttl,
+            seen: HashMap::new(),
+            seen_updates: BTreeMap::new(),
+            next_priority: 0,
+        }
+    }
+
+    /// Get the digest of a message payload
+    pub fn payload_digest(payload: &StacksMessageType) -> Sha512Trunc256Sum {
+        let mut bytes = vec![];
+        payload
+            .consensus_serialize(&mut bytes)
+            .expect("BUG: failed to serialize");
+        Sha512Trunc256Sum::from_data(&bytes)
+    }
+
+    /// Have we forwarded a message with this payload digest to this neighbor within the last
+    /// `ttl` seconds of `now`?
+    pub fn contains(&self, nk: &NeighborKey, digest: &Sha512Trunc256Sum, now: u64) -> bool {
+        self.seen
+            .get(&(nk.clone(), digest.clone()))
+            .map(|(_, seen_at)| now < seen_at.saturating_add(self.ttl))
+            .unwrap_or(false)
+    }
+
+    /// Record that we forwarded a message with this payload digest to this neighbor at `now`.
+    /// If the cache is full, the least-recently-seen entry is evicted.
+    pub fn insert(&mut self, nk: NeighborKey, digest: Sha512Trunc256Sum, now: u64) -> () {
+        if self.capacity == 0 {
+            return;
+        }
+        let key = (nk, digest);
+        if let Some((priority, _)) = self.seen.get(&key) {
+            self.seen_updates.remove(priority);
+        } else {
+            while self.seen.len() >= self.capacity {
+                let Some((_, oldest)) = self.seen_updates.pop_first() else {
+                    break;
+                };
+                self.seen.remove(&oldest);
+            }
+        }
+        self.seen.insert(key.clone(), (self.next_priority, now));
+        self.seen_updates.insert(self.next_priority, key);
+        self.next_priority += 1;
+    }
+
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+}
+
 impl Relayer {
     pub fn new(handle: NetworkHandle, stacker_dbs: StackerDBs) -> Relayer {
         Relayer {
@@ -2662,6 +2733,70 @@ pub mod test {
     use crate::net::*;
     use crate::util_lib::test::*;
 
+    #[test]
+    fn test_seen_message_cache_evicts_lru() {
+        let mut cache = SeenMessageCache::new(2, 60);
+        assert!(cache.is_empty());
+
+        let nk = NeighborKey {
+            peer_version: 12345,
+            network_id: 0x80000000,
+            addrbytes: PeerAddress([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xff, 127, 0, 0, 1]),
+            port: 54321,
+        };
+        let mut other_nk = nk.clone();
+        other_nk.port += 1;
+
+        let d1 = SeenMessageCache::payload_digest(&StacksMessageType::Nack(NackData::new(1)));
+        let d2 = SeenMessageCache::payload_digest(&StacksMessageType::Nack(NackData::new(2)));
+        let d3 = SeenMessageCache::payload_digest(&StacksMessageType::Nack(NackData::new(3)));
+
+        cache.insert(nk.clone(), d1.clone(), 1000);
+        cache.insert(nk.clone(), d2.clone(), 1000);
+        assert!(cache.contains(&nk, &d1, 1000));
+        assert!(cache.contains(&nk, &d2, 1000));
+        assert!(!cache.contains(&other_nk, &d1, 1000));
+
+        // refresh d1, so d2 is evicted next
+        cache.insert(nk.clone(), d1.clone(), 1000);
+        cache.insert(nk.clone(), d3.clone(), 1000);
+        assert_eq!(cache.len(), 2);
+        assert!(cache.contains(&nk, &d1, 1000));
+        assert!(!cache.contains(&nk, &d2, 1000));
+        assert!(cache.contains(&nk, &d3, 1000));
+
+        // a zero-sized cache remembers nothing
+        let mut cache = SeenMessageCache::new(0, 60);
+        cache.insert(nk.clone(), d1.clone(), 1000);
+        assert!(!cache.contains(&nk, &d1, 1000));
+        assert_eq!(cache.len(), 0);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_seen_message_cache_expires_entries() {
+        let mut cache = SeenMessageCache::new(2, 60);
+
+        let nk = NeighborKey {
+            peer_version: 12345,
+            network_id: 0x80000000,
+            addrbytes: PeerAddress([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xff, 127, 0, 0, 1]),
+            port: 54321,
+        };
+        let d1 = SeenMessageCache::payload_digest(&StacksMessageType::Nack(NackData::new(1)));
+
+        cache.insert(nk.clone(), d1.clone(), 1000);
+        assert!(cache.contains(&nk, &d1, 1000));
+        assert!(cache.contains(&nk, &d1, 1059));
+        assert!(!cache.contains(&nk, &d1, 1060));
+
+        // forwarding it again restarts the clock
+        cache.insert(nk.clone(), d1.clone(), 1060);
+        assert_eq!(cache.len(), 1);
+        assert!(cache.contains(&nk, &d1, 1119));
+        assert!(!cache.contains(&nk, &d1, 1120));
+    }
+
     #[test]
     fn test_relayer_stats_add_relyed_messages() {
         let mut relay_stats = RelayerStats::new();
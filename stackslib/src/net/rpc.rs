@@ -68,7 +68,8 @@ use crate::net::connection::{ConnectionHttp, ConnectionOptions, ReplyHandleHttp}
 use crate::net::db::PeerDB;
 use crate::net::http::{HttpRequestContents, HttpResponseContents};
 use crate::net::httpcore::{
-    StacksHttp, StacksHttpMessage, StacksHttpRequest, StacksHttpResponse, HTTP_REQUEST_ID_RESERVED,
+    HttpPreambleExtensions, StacksHttp, StacksHttpMessage, StacksHttpRequest, StacksHttpResponse,
+    HTTP_REQUEST_ID_RESERVED,
 };
 use crate::net::p2p::{PeerMap, PeerNetwork};
 use crate::net::relay::Relayer;
@@ -538,7 +539,7 @@ impl ConversationHttp {
             };
 
             match msg {
-                StacksHttpMessage::Request(req) => {
+                StacksHttpMessage::Request(mut req) => {
                     // new request that we can handle
                     self.total_request_count += 1;
                     self.last_request_timestamp = get_epoch_time_secs();
@@ -546,6 +547,7 @@ impl ConversationHttp {
                     let start_time = Instant::now();
                     let verb = req.verb().to_string();
                     let request_path = req.request_path().to_string();
+                    let request_id = req.get_or_set_request_id();
                     let msg_opt = monitoring::instrument_http_request_handler(
                         self,
                         req,
@@ -555,6 +557,7 @@ impl ConversationHttp {
                     info!("Handled StacksHTTPRequest";
                            "verb" => %verb,
                            "path" => %request_path,
+                           "request_id" => request_id,
                            "processing_time_ms" => start_time.elapsed().as_millis(),
                            "latency_ms" => latency,
                            "conn_id" => self.conn_id,
@@ -569,9 +572,13 @@ impl ConversationHttp {
                     self.total_request_count += 1;
                     self.last_request_timestamp = get_epoch_time_secs();
                     let start_time = Instant::now();
+                    let request_id = resp
+                        .preamble()
+                        .get_request_id()
+                        .unwrap_or(HTTP_REQUEST_ID_RESERVED);
                     self.reply_error(resp)?;
 
-                    info!("Handled StacksHTTPRequest Error"; "path" => %path, "processing_time_ms" => start_time.elapsed().as_millis(), "conn_id" => self.conn_id, "peer_addr" => &self.peer_addr);
+                    info!("Handled StacksHTTPRequest Error"; "path" => %path, "request_id" => request_id, "processing_time_ms" => start_time.elapsed().as_millis(), "conn_id" => self.conn_id, "peer_addr" => &self.peer_addr);
                 }
                 StacksHttpMessage::Response(resp) => {
                     // Is there someone else waiting for this message?  If so, pass it along.
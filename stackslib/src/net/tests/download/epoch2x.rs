@@ -596,6 +596,154 @@ pub fn test_get_blocks_and_microblocks_2_peers_download_plain() {
     })
 }
 
+#[test]
+#[ignore]
+pub fn test_get_blocks_and_microblocks_2_peers_download_max_inflight_blocks() {
+    with_timeout(600, || {
+        let max_inflight_blocks = 2;
+        let mut max_seen = vec![0; 2];
+        let check_inflight = |peers: &mut Vec<TestPeer>, max_seen: &mut Vec<usize>| {
+            for (i, peer) in peers.iter().enumerate() {
+                if let Some(ref dl) = peer.network.block_downloader {
+                    let inflight = dl.num_http_requests_inflight();
+                    assert!(
+                        inflight <= max_inflight_blocks,
+                        "peer {} has {} requests in flight",
+                        i,
+                        inflight
+                    );
+                    max_seen[i] = max_seen[i].max(inflight);
+                }
+            }
+        };
+
+        run_get_blocks_and_microblocks(
+            function_name!(),
+            3270,
+            2,
+            |ref mut peer_configs| {
+                // build initial network topology
+                assert_eq!(peer_configs.len(), 2);
+
+                for peer_config in peer_configs.iter_mut() {
+                    peer_config.connection_opts.disable_block_advertisement = true;
+                    peer_config.connection_opts.max_inflight_blocks = max_inflight_blocks as u64;
+                }
+
+                let peer_0 = peer_configs[0].to_neighbor();
+                let peer_1 = peer_configs[1].to_neighbor();
+                peer_configs[0].add_neighbor(&peer_1);
+                peer_configs[1].add_neighbor(&peer_0);
+            },
+            |num_blocks, ref mut peers| {
+                // build up block data to replicate
+                let mut block_data = vec![];
+                for _ in 0..num_blocks {
+                    let (mut burn_ops, stacks_block, microblocks) = peers[1].make_default_tenure();
+
+                    let (_, burn_header_hash, consensus_hash) =
+                        peers[1].next_burnchain_block(burn_ops.clone());
+                    peers[1].process_stacks_epoch_at_tip(&stacks_block, &microblocks);
+
+                    TestPeer::set_ops_burn_header_hash(&mut burn_ops, &burn_header_hash);
+
+                    peers[0].next_burnchain_block_raw(burn_ops);
+
+                    let sn = SortitionDB::get_canonical_burn_chain_tip(
+                        &peers[1].sortdb.as_ref().unwrap().conn(),
+                    )
+                    .unwrap();
+                    block_data.push((
+                        sn.consensus_hash.clone(),
+                        Some(stacks_block),
+                        Some(microblocks),
+                    ));
+                }
+                block_data
+            },
+            |peers| check_inflight(peers, &mut max_seen),
+            |_| true,
+            |_| true,
+        );
+
+        // the cap was actually reached, so the rest of the blocks had to wait their turn
+        assert_eq!(max_seen[0], max_inflight_blocks);
+    })
+}
+
+#[test]
+#[ignore]
+pub fn test_get_blocks_and_microblocks_2_peers_download_is_caught_up() {
+    with_timeout(600, || {
+        let mut was_behind = false;
+        run_get_blocks_and_microblocks(
+            function_name!(),
+            3280,
+            2,
+            |ref mut peer_configs| {
+                // build initial network topology
+                assert_eq!(peer_configs.len(), 2);
+
+                peer_configs[0].connection_opts.disable_block_advertisement = true;
+                peer_configs[1].connection_opts.disable_block_advertisement = true;
+
+                let peer_0 = peer_configs[0].to_neighbor();
+                let peer_1 = peer_configs[1].to_neighbor();
+                peer_configs[0].add_neighbor(&peer_1);
+                peer_configs[1].add_neighbor(&peer_0);
+            },
+            |num_blocks, ref mut peers| {
+                // build up block data to replicate
+                let mut block_data = vec![];
+                for _ in 0..num_blocks {
+                    let (mut burn_ops, stacks_block, microblocks) = peers[1].make_default_tenure();
+
+                    let (_, burn_header_hash, consensus_hash) =
+                        peers[1].next_burnchain_block(burn_ops.clone());
+                    peers[1].process_stacks_epoch_at_tip(&stacks_block, &microblocks);
+
+                    TestPeer::set_ops_burn_header_hash(&mut burn_ops, &burn_header_hash);
+
+                    peers[0].next_burnchain_block_raw(burn_ops);
+
+                    let sn = SortitionDB::get_canonical_burn_chain_tip(
+                        &peers[1].sortdb.as_ref().unwrap().conn(),
+                    )
+                    .unwrap();
+                    block_data.push((
+                        sn.consensus_hash.clone(),
+                        Some(stacks_block),
+                        Some(microblocks),
+                    ));
+                }
+                block_data
+            },
+            |peers| {
+                // peer 0 can't be caught up until it has all of the blocks peer 1 has
+                let num_burn_blocks = SortitionDB::get_canonical_burn_chain_tip(
+                    peers[1].sortdb.as_ref().unwrap().conn(),
+                )
+                .unwrap()
+                .block_height;
+                let inv_0 = get_blocks_inventory(&mut peers[0], 0, num_burn_blocks);
+                let inv_1 = get_blocks_inventory(&mut peers[1], 0, num_burn_blocks);
+                if inv_0.block_bitvec != inv_1.block_bitvec {
+                    was_behind = true;
+                    assert!(
+                        !peers[0].network.is_caught_up(),
+                        "peer 0 is caught up, but has {:?} while peer 1 has {:?}",
+                        &inv_0,
+                        &inv_1
+                    );
+                }
+            },
+            |_| true,
+            |peers| peers[0].network.is_caught_up(),
+        );
+        assert!(was_behind);
+    })
+}
+
 fn make_contract_call_transaction(
     miner: &mut TestMiner,
     sortdb: &mut SortitionDB,
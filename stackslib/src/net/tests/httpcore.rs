@@ -16,15 +16,21 @@
 
 use std::io::Write;
 use std::net::{SocketAddr, ToSocketAddrs};
-use std::str;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use std::{str, thread};
 
+use regex::{Captures, Regex};
 use stacks_common::codec::StacksMessageCodec;
-use stacks_common::types::chainstate::{StacksAddress, StacksBlockId, StacksPrivateKey};
+use stacks_common::types::chainstate::{
+    StacksAddress, StacksBlockId, StacksPrivateKey, StacksPublicKey,
+};
 use stacks_common::types::net::{PeerAddress, PeerHost};
 use stacks_common::util::chunked_encoding::{
     HttpChunkedTransferWriter, HttpChunkedTransferWriterState,
 };
 use stacks_common::util::hash::{hex_bytes, to_hex, Hash160};
+use stacks_common::util::sleep_ms;
 
 use crate::burnchains::Txid;
 use crate::chainstate::stacks::db::blocks::test::make_sample_microblock_stream;
@@ -36,16 +42,21 @@ use crate::chainstate::stacks::{
 use crate::net::api::getneighbors::{RPCNeighbor, RPCNeighborsInfo};
 use crate::net::connection::ConnectionOptions;
 use crate::net::http::{
-    http_error_from_code_and_text, http_reason, HttpContentType, HttpErrorResponse,
-    HttpRequestContents, HttpRequestPreamble, HttpReservedHeader, HttpResponsePreamble,
-    HttpVersion, HTTP_PREAMBLE_MAX_NUM_HEADERS,
+    http_error_from_code_and_text, http_reason, parse_json, Error as HttpDecodeError,
+    HttpContentType, HttpErrorResponse, HttpRequest, HttpRequestContents, HttpRequestPreamble,
+    HttpReservedHeader, HttpResponse, HttpResponseContents, HttpResponsePayload,
+    HttpResponsePreamble, HttpVersion, HTTP_PREAMBLE_MAX_NUM_HEADERS,
 };
 use crate::net::httpcore::{
-    HttpPreambleExtensions, HttpRequestContentsExtensions, StacksHttp, StacksHttpMessage,
-    StacksHttpPreamble, StacksHttpRequest, StacksHttpResponse,
+    HttpPreambleExtensions, HttpRequestContentsExtensions, RPCRequestHandler, StacksHttp,
+    StacksHttpMessage, StacksHttpPreamble, StacksHttpRequest, StacksHttpResponse,
 };
+use crate::net::p2p::PeerNetwork;
 use crate::net::rpc::ConversationHttp;
-use crate::net::{ProtocolFamily, TipRequest};
+use crate::net::test::{TestPeer, TestPeerConfig};
+use crate::net::{
+    Error as NetError, ProtocolFamily, RPCHandlerArgs, StacksNodeState, TipRequest, UrlString,
+};
 
 #[test]
 fn test_parse_stacks_http_preamble_request_err() {
@@ -1051,6 +1062,324 @@ fn test_http_parse_proof_request_query() {
     assert!(proof_req);
 }
 
+#[test]
+fn test_http_json_error_envelope() {
+    let missing_route =
+        "GET /v2/nonexistent HTTP/1.1\r\nUser-Agent: stacks/2.0\r\nHost: bad:123\r\n\r\n";
+
+    // terse text body by default
+    let mut http = StacksHttp::new(
+        "127.0.0.1:20443".parse().unwrap(),
+        &ConnectionOptions::default(),
+    );
+    let (preamble, offset) = http.read_preamble(missing_route.as_bytes()).unwrap();
+    let (msg, _) = http
+        .read_payload(&preamble, &missing_route.as_bytes()[offset..])
+        .unwrap();
+    let StacksHttpMessage::Error(_, resp) = msg else {
+        panic!("Expected an error response");
+    };
+    assert_eq!(resp.preamble().status_code, 404);
+    assert_eq!(resp.preamble().content_type, HttpContentType::Text);
+
+    // JSON envelope if configured
+    let conn_opts = ConnectionOptions {
+        json_error_responses: true,
+        ..ConnectionOptions::default()
+    };
+    let mut http = StacksHttp::new("127.0.0.1:20443".parse().unwrap(), &conn_opts);
+    let (preamble, offset) = http.read_preamble(missing_route.as_bytes()).unwrap();
+    let (msg, _) = http
+        .read_payload(&preamble, &missing_route.as_bytes()[offset..])
+        .unwrap();
+    let StacksHttpMessage::Error(_, resp) = msg else {
+        panic!("Expected an error response");
+    };
+    assert_eq!(resp.preamble().status_code, 404);
+    assert_eq!(resp.preamble().content_type, HttpContentType::JSON);
+    let HttpResponsePayload::JSON(body) = resp.body() else {
+        panic!("Expected a JSON body");
+    };
+    assert_eq!(body["code"], 404);
+    assert_eq!(body["path"], "/v2/nonexistent");
+    assert_eq!(body["error"], "No such file or directory");
+
+    // a known path with the wrong verb is a 405
+    let wrong_verb = "POST /v2/info HTTP/1.1\r\nUser-Agent: stacks/2.0\r\nHost: bad:123\r\nContent-Length: 0\r\n\r\n";
+    let (preamble, offset) = http.read_preamble(wrong_verb.as_bytes()).unwrap();
+    let (msg, _) = http
+        .read_payload(&preamble, &wrong_verb.as_bytes()[offset..])
+        .unwrap();
+    let StacksHttpMessage::Error(_, resp) = msg else {
+        panic!("Expected an error response");
+    };
+    assert_eq!(resp.preamble().status_code, 405);
+    let HttpResponsePayload::JSON(body) = resp.body() else {
+        panic!("Expected a JSON body");
+    };
+    assert_eq!(body["code"], 405);
+    assert_eq!(body["path"], "/v2/info");
+}
+
+/// Request handler that takes `delay_ms` to answer
+#[derive(Clone)]
+struct RPCSlowRequestHandler {
+    delay_ms: u64,
+}
+
+impl HttpRequest for RPCSlowRequestHandler {
+    fn verb(&self) -> &'static str {
+        "GET"
+    }
+
+    fn path_regex(&self) -> Regex {
+        Regex::new(r#"^/test/slow$"#).unwrap()
+    }
+
+    fn metrics_identifier(&self) -> &str {
+        "/test/slow"
+    }
+
+    fn try_parse_request(
+        &mut self,
+        _preamble: &HttpRequestPreamble,
+        _captures: &Captures,
+        query: Option<&str>,
+        _body: &[u8],
+    ) -> Result<HttpRequestContents, HttpDecodeError> {
+        Ok(HttpRequestContents::new().query_string(query))
+    }
+}
+
+impl RPCRequestHandler for RPCSlowRequestHandler {
+    fn restart(&mut self) {}
+
+    fn try_handle_request(
+        &mut self,
+        preamble: HttpRequestPreamble,
+        _contents: HttpRequestContents,
+        _node: &mut StacksNodeState,
+    ) -> Result<(HttpResponsePreamble, HttpResponseContents), NetError> {
+        sleep_ms(self.delay_ms);
+        let preamble = HttpResponsePreamble::ok_json(&preamble);
+        let body = HttpResponseContents::try_from_json(&self.delay_ms)?;
+        Ok((preamble, body))
+    }
+}
+
+impl HttpResponse for RPCSlowRequestHandler {
+    fn try_parse_response(
+        &self,
+        preamble: &HttpResponsePreamble,
+        body: &[u8],
+    ) -> Result<HttpResponsePayload, HttpDecodeError> {
+        let delay_ms: u64 = parse_json(preamble, body)?;
+        Ok(HttpResponsePayload::try_from_json(delay_ms)?)
+    }
+}
+
+#[test]
+fn test_http_slow_request_threshold() {
+    let peer_config = TestPeerConfig::from_port(32050);
+    let mut peer = TestPeer::new(peer_config);
+
+    let conn_opts = ConnectionOptions {
+        slow_request_threshold_ms: 50,
+        ..ConnectionOptions::default()
+    };
+    let mut http = StacksHttp::new("127.0.0.1:20443".parse().unwrap(), &conn_opts);
+    http.register_rpc_endpoint(RPCSlowRequestHandler { delay_ms: 100 });
+
+    let mut default_http = StacksHttp::new(
+        "127.0.0.1:20443".parse().unwrap(),
+        &ConnectionOptions::default(),
+    );
+    default_http.register_rpc_endpoint(RPCSlowRequestHandler { delay_ms: 100 });
+
+    let slow_request = StacksHttpRequest::new_for_peer(
+        PeerHost::from_host_port("127.0.0.1".to_string(), 20443),
+        "GET".into(),
+        "/test/slow".into(),
+        HttpRequestContents::new(),
+    )
+    .unwrap();
+    let fast_request = StacksHttpRequest::new_get_stx_transfer_cost(PeerHost::from_host_port(
+        "127.0.0.1".to_string(),
+        20443,
+    ));
+
+    // hack around the borrow-checker
+    let sortdb = peer.sortdb.take().unwrap();
+    let mut stacks_node = peer.stacks_node.take().unwrap();
+    let mut mempool = peer.mempool.take().unwrap();
+    {
+        let rpc_args = RPCHandlerArgs::default();
+        let mut node_state = StacksNodeState::new(
+            &mut peer.network,
+            &sortdb,
+            &mut stacks_node.chainstate,
+            &mut mempool,
+            &rpc_args,
+        );
+
+        // deliberately slow handler
+        let (preamble, _) = http
+            .try_handle_request(slow_request.clone(), &mut node_state)
+            .unwrap();
+        assert_eq!(preamble.status_code, 200);
+        assert_eq!(http.num_slow_requests, 1);
+
+        // fast handler
+        let (preamble, _) = http
+            .try_handle_request(fast_request, &mut node_state)
+            .unwrap();
+        assert_eq!(preamble.status_code, 200);
+        assert_eq!(http.num_slow_requests, 1);
+
+        // disabled by default
+        let (preamble, _) = default_http
+            .try_handle_request(slow_request, &mut node_state)
+            .unwrap();
+        assert_eq!(preamble.status_code, 200);
+        assert_eq!(default_http.num_slow_requests, 0);
+    }
+    peer.sortdb = Some(sortdb);
+    peer.stacks_node = Some(stacks_node);
+    peer.mempool = Some(mempool);
+}
+
+/// Request handler that records the request ID it sees
+#[derive(Clone)]
+struct RPCRequestIdHandler {
+    request_ids: Arc<Mutex<Vec<Option<u32>>>>,
+}
+
+impl HttpRequest for RPCRequestIdHandler {
+    fn verb(&self) -> &'static str {
+        "GET"
+    }
+
+    fn path_regex(&self) -> Regex {
+        Regex::new(r#"^/test/request-id$"#).unwrap()
+    }
+
+    fn metrics_identifier(&self) -> &str {
+        "/test/request-id"
+    }
+
+    fn try_parse_request(
+        &mut self,
+        _preamble: &HttpRequestPreamble,
+        _captures: &Captures,
+        query: Option<&str>,
+        _body: &[u8],
+    ) -> Result<HttpRequestContents, HttpDecodeError> {
+        Ok(HttpRequestContents::new().query_string(query))
+    }
+}
+
+impl RPCRequestHandler for RPCRequestIdHandler {
+    fn restart(&mut self) {}
+
+    fn try_handle_request(
+        &mut self,
+        preamble: HttpRequestPreamble,
+        _contents: HttpRequestContents,
+        _node: &mut StacksNodeState,
+    ) -> Result<(HttpResponsePreamble, HttpResponseContents), NetError> {
+        self.request_ids
+            .lock()
+            .unwrap()
+            .push(preamble.get_request_id());
+        let preamble = HttpResponsePreamble::ok_json(&preamble);
+        let body = HttpResponseContents::try_from_json(&"ok")?;
+        Ok((preamble, body))
+    }
+}
+
+impl HttpResponse for RPCRequestIdHandler {
+    fn try_parse_response(
+        &self,
+        preamble: &HttpResponsePreamble,
+        body: &[u8],
+    ) -> Result<HttpResponsePayload, HttpDecodeError> {
+        let ok: String = parse_json(preamble, body)?;
+        Ok(HttpResponsePayload::try_from_json(ok)?)
+    }
+}
+
+#[test]
+fn test_http_request_id_reaches_handler() {
+    let peer_config = TestPeerConfig::from_port(32060);
+    let mut peer = TestPeer::new(peer_config);
+
+    let request_ids = Arc::new(Mutex::new(vec![]));
+    let mut http = StacksHttp::new(
+        "127.0.0.1:20443".parse().unwrap(),
+        &ConnectionOptions::default(),
+    );
+    http.register_rpc_endpoint(RPCRequestIdHandler {
+        request_ids: request_ids.clone(),
+    });
+
+    // hack around the borrow-checker
+    let sortdb = peer.sortdb.take().unwrap();
+    let mut stacks_node = peer.stacks_node.take().unwrap();
+    let mut mempool = peer.mempool.take().unwrap();
+    {
+        let rpc_args = RPCHandlerArgs::default();
+        let mut node_state = StacksNodeState::new(
+            &mut peer.network,
+            &sortdb,
+            &mut stacks_node.chainstate,
+            &mut mempool,
+            &rpc_args,
+        );
+
+        for (request_id_hdr, expected_request_id) in [
+            (Some("12345"), Some(12345)),
+            (None, None),
+            (Some("operator-req-1"), None),
+        ] {
+            let mut request_bytes =
+                "GET /test/request-id HTTP/1.1\r\nHost: 127.0.0.1:20443\r\n".to_string();
+            if let Some(hdr) = request_id_hdr {
+                request_bytes.push_str(&format!("X-Request-Id: {}\r\n", hdr));
+            }
+            request_bytes.push_str("\r\n");
+
+            // the request gets its ID when its preamble is read
+            let (preamble, offset) = http.read_preamble(request_bytes.as_bytes()).unwrap();
+            let StacksHttpPreamble::Request(ref request_preamble) = preamble else {
+                panic!("Expected a request preamble");
+            };
+            let request_id = request_preamble.get_request_id().unwrap();
+            if let Some(expected_request_id) = expected_request_id {
+                assert_eq!(request_id, expected_request_id);
+            } else {
+                assert_ne!(request_id, 0);
+            }
+
+            let (msg, _) = http
+                .read_payload(&preamble, &request_bytes.as_bytes()[offset..])
+                .unwrap();
+            let StacksHttpMessage::Request(request) = msg else {
+                panic!("Expected a request");
+            };
+            assert_eq!(request.preamble().get_request_id(), Some(request_id));
+
+            // the handler sees the same ID, and the response echoes it
+            let (response_preamble, _) = http.try_handle_request(request, &mut node_state).unwrap();
+            assert_eq!(response_preamble.status_code, 200);
+            assert_eq!(response_preamble.get_request_id(), Some(request_id));
+            assert_eq!(request_ids.lock().unwrap().pop(), Some(Some(request_id)));
+        }
+    }
+    peer.sortdb = Some(sortdb);
+    peer.stacks_node = Some(stacks_node);
+    peer.mempool = Some(mempool);
+}
+
 #[test]
 fn test_metrics_identifiers() {
     let convo = ConversationHttp::new(
@@ -1117,3 +1446,36 @@ fn test_metrics_identifiers() {
         assert_eq!(response_handler_index.is_some(), should_have_handler);
     }
 }
+
+#[test]
+fn test_check_data_url_identity() {
+    let peer_config = TestPeerConfig::from_port(32040);
+    let mut peer = TestPeer::new(peer_config);
+
+    let data_url = peer.network.get_local_peer().data_url.clone();
+    let public_key_hash = Hash160::from_node_public_key(&StacksPublicKey::from_private(
+        &peer.network.get_local_peer().private_key,
+    ));
+
+    // the HTTP server is driven by stepping the peer, so check from another thread
+    let checker = thread::spawn(move || {
+        let timeout = Duration::from_secs(30);
+        let ours = PeerNetwork::check_data_url_identity(&data_url, &public_key_hash, timeout);
+        let theirs = PeerNetwork::check_data_url_identity(&data_url, &Hash160([0x01; 20]), timeout);
+        let unreachable = PeerNetwork::check_data_url_identity(
+            &UrlString::try_from("http://127.0.0.1:1").unwrap(),
+            &public_key_hash,
+            timeout,
+        );
+        (ours, theirs, unreachable)
+    });
+
+    while !checker.is_finished() {
+        peer.step().unwrap();
+        sleep_ms(100);
+    }
+    let (ours, theirs, unreachable) = checker.join().unwrap();
+    assert!(ours.unwrap());
+    assert!(!theirs.unwrap());
+    assert!(unreachable.is_err());
+}
@@ -58,6 +58,50 @@ fn peerblocksinv_has_ith_block() {
     }
 }
 
+#[test]
+fn inv_state_missing_relative_to() {
+    let nk = NeighborKey {
+        peer_version: 0x12345678,
+        network_id: 0x9abcdef0,
+        addrbytes: PeerAddress([0x11; 16]),
+        port: 12345,
+    };
+    let unknown_nk = NeighborKey {
+        port: 12346,
+        ..nk.clone()
+    };
+
+    let mut inv_state = InvState::new(12345, 10, 10);
+    inv_state.add_peer(nk.clone(), false);
+    inv_state.get_stats_mut(&nk).unwrap().inv =
+        PeerBlocksInv::new(vec![0x55, 0x77], vec![0x00, 0x00], vec![0x01], 16, 1, 12345);
+
+    let local_inv =
+        PeerBlocksInv::new(vec![0x05, 0x00], vec![0x00, 0x00], vec![0x01], 16, 1, 12345);
+
+    // remote has bits 0, 2, 4, 6 of the first byte and 0, 1, 2, 4, 5, 6 of the second, and we
+    // have bits 0 and 2 of the first byte
+    assert_eq!(
+        inv_state.missing_relative_to(&nk, &local_inv),
+        vec![
+            12345 + 4,
+            12345 + 6,
+            12345 + 8,
+            12345 + 9,
+            12345 + 10,
+            12345 + 12,
+            12345 + 13,
+            12345 + 14
+        ]
+    );
+    assert!(inv_state
+        .missing_relative_to(&nk, &inv_state.get_stats(&nk).unwrap().inv)
+        .is_empty());
+    assert!(inv_state
+        .missing_relative_to(&unknown_nk, &local_inv)
+        .is_empty());
+}
+
 #[test]
 fn peerblocksinv_merge() {
     let peer_inv = PeerBlocksInv::new(
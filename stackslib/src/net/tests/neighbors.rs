@@ -18,7 +18,7 @@ use clarity::vm::types::{StacksAddressExtensions, StandardPrincipalData};
 use rand::prelude::*;
 use rand::thread_rng;
 use stacks_common::util::hash::*;
-use stacks_common::util::sleep_ms;
+use stacks_common::util::{get_epoch_time_secs, sleep_ms};
 
 use crate::core::{
     StacksEpoch, StacksEpochId, PEER_VERSION_EPOCH_2_0, PEER_VERSION_EPOCH_2_05,
@@ -2825,3 +2825,119 @@ where
         }
     }
 }
+
+#[test]
+fn test_walk_rng_seeded() {
+    let mut peer_1_config = TestPeerConfig::from_port(33010);
+    let mut peer_2_config = TestPeerConfig::from_port(33012);
+    let peer_3_config = TestPeerConfig::from_port(33014);
+
+    peer_1_config.connection_opts.walk_seed = Some(42);
+    peer_2_config.connection_opts.walk_seed = Some(42);
+
+    let peer_1 = TestPeer::new(peer_1_config);
+    let peer_2 = TestPeer::new(peer_2_config);
+    let peer_3 = TestPeer::new(peer_3_config);
+
+    match peer_3.network.walk_rng() {
+        WalkRng::Thread(_) => {}
+        WalkRng::Seeded(_) => panic!("Expected thread RNG when no walk seed is set"),
+    }
+
+    // same seed, same walk decisions
+    let mut neighbor = peer_3.config.to_neighbor();
+    neighbor.in_degree = 1;
+    neighbor.out_degree = 100;
+    for _ in 0..100 {
+        assert_eq!(
+            peer_1.network.walk_rng().gen::<u64>(),
+            peer_2.network.walk_rng().gen::<u64>()
+        );
+        assert_eq!(
+            neighbor.degree_with_rng(&mut peer_1.network.walk_rng()),
+            neighbor.degree_with_rng(&mut peer_2.network.walk_rng())
+        );
+    }
+
+    // the seeded RNG is a single stream, not restarted on each use
+    let first = peer_1.network.walk_rng().gen::<u64>();
+    let second = peer_1.network.walk_rng().gen::<u64>();
+    assert_ne!(first, second);
+}
+
+#[test]
+fn test_walk_rng_seeded_picks_same_neighbors() {
+    let mut peer_1_config = TestPeerConfig::from_port(33020);
+    let mut peer_2_config = TestPeerConfig::from_port(33022);
+
+    peer_1_config.connection_opts.walk_seed = Some(42);
+    peer_2_config.connection_opts.walk_seed = Some(42);
+
+    // both peers know about the same neighbors
+    let neighbors: Vec<_> = (0..10)
+        .map(|i| TestPeerConfig::from_port(33100 + 2 * i).to_neighbor())
+        .collect();
+    for neighbor in neighbors.iter() {
+        peer_1_config.add_neighbor(neighbor);
+        peer_2_config.add_neighbor(neighbor);
+    }
+
+    let mut peer_1 = TestPeer::new(peer_1_config);
+    let mut peer_2 = TestPeer::new(peer_2_config);
+
+    // ...and have the same pingback candidates
+    for neighbor in neighbors.iter() {
+        let pingback = NeighborPingback {
+            peer_version: neighbor.addr.peer_version,
+            network_id: neighbor.addr.network_id,
+            ts: get_epoch_time_secs(),
+            pubkey: neighbor.public_key.clone(),
+        };
+        for peer in [&mut peer_1, &mut peer_2] {
+            peer.network
+                .walk_pingbacks
+                .insert(NeighborAddress::from_neighbor(neighbor), pingback.clone());
+        }
+    }
+
+    let walk_db = PeerDBNeighborWalk::new();
+    for _ in 0..20 {
+        let initial_1 = walk_db
+            .get_initial_walk_neighbors(&peer_1.network, true)
+            .unwrap();
+        let initial_2 = walk_db
+            .get_initial_walk_neighbors(&peer_2.network, true)
+            .unwrap();
+        assert_eq!(initial_1.len(), neighbors.len());
+        assert_eq!(
+            initial_1.iter().map(|n| &n.addr).collect::<Vec<_>>(),
+            initial_2.iter().map(|n| &n.addr).collect::<Vec<_>>()
+        );
+
+        let picked_1 = PeerDBNeighborWalk::pick_walk_neighbors(&peer_1.network, 5, 0).unwrap();
+        let picked_2 = PeerDBNeighborWalk::pick_walk_neighbors(&peer_2.network, 5, 0).unwrap();
+        assert_eq!(picked_1.len(), 5);
+        assert_eq!(
+            picked_1.iter().map(|n| &n.addr).collect::<Vec<_>>(),
+            picked_2.iter().map(|n| &n.addr).collect::<Vec<_>>()
+        );
+
+        let next_1 = walk_db.get_next_walk_neighbor(&peer_1.network).unwrap();
+        let next_2 = walk_db.get_next_walk_neighbor(&peer_2.network).unwrap();
+        assert_eq!(next_1.addr, next_2.addr);
+
+        let walk_1 = NeighborWalk::instantiate_walk_from_pingback(
+            PeerDBNeighborWalk::new(),
+            PeerNetworkComms::new(),
+            &peer_1.network,
+        )
+        .unwrap();
+        let walk_2 = NeighborWalk::instantiate_walk_from_pingback(
+            PeerDBNeighborWalk::new(),
+            PeerNetworkComms::new(),
+            &peer_2.network,
+        )
+        .unwrap();
+        assert_eq!(walk_1.cur_neighbor.addr, walk_2.cur_neighbor.addr);
+    }
+}
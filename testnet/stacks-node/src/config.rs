@@ -204,6 +204,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn should_reject_zero_max_requests_per_handle_per_pass() {
+        assert_eq!(
+            format!("connection_options.max_requests_per_handle_per_pass must be at least 1"),
+            Config::from_config_file(
+                ConfigFile::from_str(
+                    r#"
+                    [connection_options]
+                    max_requests_per_handle_per_pass = 0
+                    "#,
+                )
+                .unwrap()
+            )
+            .unwrap_err()
+        );
+
+        let config = Config::from_config_file(ConfigFile::from_str("").unwrap()).unwrap();
+        assert_eq!(
+            config.connection_options.max_requests_per_handle_per_pass,
+            16
+        );
+    }
+
     #[test]
     fn should_load_affirmation_map() {
         let affirmation_string = "nnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnppnnnnnnnnnnnnnnnnnnnnnnnnpppppnnnnnnnnnnnnnnnnnnnnnnnpppppppppppppppnnnnnnnnnnnnnnnnnnnnnnnppppppppppnnnnnnnnnnnnnnnnnnnppppnnnnnnnnnnnnnnnnnnnnnnnppppppppnnnnnnnnnnnnnnnnnnnnnnnppnppnnnnnnnnnnnnnnnnnnnnnnnppppnnnnnnnnnnnnnnnnnnnnnnnnnppppppnnnnnnnnnnnnnnnnnnnnnnnnnppnnnnnnnnnnnnnnnnnnnnnnnnnpppppppnnnnnnnnnnnnnnnnnnnnnnnnnnpnnnnnnnnnnnnnnnnnnnnnnnnnpppnppppppppppppppnnppppnpa";
@@ -297,6 +320,62 @@ mod tests {
         assert_eq!(config.burnchain.affirmation_overrides.len(), 5);
         assert_eq!(config.burnchain.affirmation_overrides[&413], affirmation);
     }
+
+    #[test]
+    fn should_load_initial_balances_from_file() {
+        let dir = std::env::temp_dir();
+        let tag: u64 = rand::random();
+        let csv_path = dir.join(format!("initial-balances-{}.csv", tag));
+        let json_path = dir.join(format!("initial-balances-{}.json", tag));
+        let bad_path = dir.join(format!("initial-balances-bad-{}.csv", tag));
+        fs::write(
+            &csv_path,
+            "address,amount\n# comment\nST2QKZ4FKHAH1NQKYKYAYZPY440FEPK7GZ1R5HBP2,100\n\nST319CF5WV77KYR1H3GT0GZ7B8Q4AQPY42ETP1VPF, 200\n",
+        )
+        .unwrap();
+        fs::write(
+            &json_path,
+            r#"[{"address": "ST221Z6TDTC5E0BYR2V624Q2ST6R0Q71T78WTAX6H", "amount": 300}]"#,
+        )
+        .unwrap();
+        fs::write(
+            &bad_path,
+            "ST2TFVBMRPS5SSNP98DQKQ5JNB2B6NZM91C4K3P7B,400\nnot-an-address,500\n",
+        )
+        .unwrap();
+
+        let mut config = Config::from_config_file(ConfigFile::from_str("").unwrap()).unwrap();
+        config
+            .load_initial_balances_from_file(csv_path.to_str().unwrap())
+            .unwrap();
+        config
+            .load_initial_balances_from_file(json_path.to_str().unwrap())
+            .unwrap();
+        assert_eq!(
+            config
+                .load_initial_balances_from_file(bad_path.to_str().unwrap())
+                .unwrap_err(),
+            "line 2: invalid address `not-an-address`"
+        );
+
+        let balances: Vec<_> = config
+            .initial_balances
+            .iter()
+            .map(|balance| (balance.address.to_string(), balance.amount))
+            .collect();
+        assert_eq!(
+            balances,
+            vec![
+                ("ST2QKZ4FKHAH1NQKYKYAYZPY440FEPK7GZ1R5HBP2".to_string(), 100),
+                ("ST319CF5WV77KYR1H3GT0GZ7B8Q4AQPY42ETP1VPF".to_string(), 200),
+                ("ST221Z6TDTC5E0BYR2V624Q2ST6R0Q71T78WTAX6H".to_string(), 300),
+            ]
+        );
+
+        for path in [csv_path, json_path, bad_path] {
+            fs::remove_file(path).unwrap();
+        }
+    }
 }
 
 impl ConfigFile {
@@ -1201,6 +1280,61 @@ impl Config {
         self.initial_balances.push(new_balance);
     }
 
+    /// Append the initial balances listed in the file at `path` to `initial_balances`.
+    /// A `.json` file holds a list of `{ "address": ..., "amount": ... }` objects; any other file
+    /// is read as CSV, with one `address,amount` pair per line. Blank lines, `#` comments and an
+    /// `address,amount` header line are skipped. Nothing is added if any entry is invalid.
+    pub fn load_initial_balances_from_file(&mut self, path: &str) -> Result<(), String> {
+        if self.is_mainnet() {
+            return Err("Attempted to run mainnet node with specified `initial_balances`".into());
+        }
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read initial balances file {}: {}", path, e))?;
+        let entries: Vec<(String, InitialBalanceFile)> = if path.ends_with(".json") {
+            let balances: Vec<InitialBalanceFile> = serde_json::from_str(&contents)
+                .map_err(|e| format!("Invalid initial balances file {}: {}", path, e))?;
+            balances
+                .into_iter()
+                .enumerate()
+                .map(|(i, balance)| (format!("entry {}", i + 1), balance))
+                .collect()
+        } else {
+            let mut entries = vec![];
+            for (i, line) in contents.lines().enumerate() {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') || line == "address,amount" {
+                    continue;
+                }
+                let location = format!("line {}", i + 1);
+                let (address, amount) = line
+                    .split_once(',')
+                    .ok_or_else(|| format!("{}: expected `address,amount`", location))?;
+                let amount = amount
+                    .trim()
+                    .parse()
+                    .map_err(|e| format!("{}: invalid amount `{}`: {}", location, amount, e))?;
+                let balance = InitialBalanceFile {
+                    address: address.trim().to_string(),
+                    amount,
+                };
+                entries.push((location, balance));
+            }
+            entries
+        };
+
+        let mut balances = Vec::with_capacity(entries.len());
+        for (location, balance) in entries {
+            let address = StacksAddress::from_string(&balance.address)
+                .ok_or_else(|| format!("{}: invalid address `{}`", location, balance.address))?;
+            balances.push(InitialBalance {
+                address: address.into(),
+                amount: balance.amount,
+            });
+        }
+        self.initial_balances.extend(balances);
+        Ok(())
+    }
+
     pub fn get_initial_liquid_ustx(&self) -> u128 {
         let mut total = 0;
         for ib in self.initial_balances.iter() {
@@ -2310,6 +2444,18 @@ pub struct ConnectionOptionsFile {
     pub antientropy_public: Option<bool>,
     pub private_neighbors: Option<bool>,
     pub block_proposal_token: Option<String>,
+    pub json_error_responses: Option<bool>,
+    pub slow_request_threshold_ms: Option<u64>,
+    pub max_pings_per_sec: Option<u64>,
+    pub max_inv_requests_per_sec: Option<u64>,
+    pub max_rate_limited_msgs: Option<u64>,
+    pub outbound_only: Option<bool>,
+    pub walk_seed: Option<u64>,
+    pub seen_cache_size: Option<usize>,
+    pub seen_cache_ttl: Option<u64>,
+    pub max_requests_per_handle_per_pass: Option<usize>,
+    pub contract_analysis_endpoint: Option<bool>,
+    pub maximum_contract_analysis_size: Option<u32>,
 }
 
 impl ConnectionOptionsFile {
@@ -2341,6 +2487,11 @@ impl ConnectionOptionsFile {
         self.read_only_call_limit_runtime.map(|x| {
             read_only_call_limit.runtime = x;
         });
+        if self.max_requests_per_handle_per_pass == Some(0) {
+            return Err(
+                "connection_options.max_requests_per_handle_per_pass must be at least 1".into(),
+            );
+        }
         Ok(ConnectionOptions {
             read_only_call_limit,
             inbox_maxlen: self
@@ -2434,6 +2585,20 @@ impl ConnectionOptionsFile {
             antientropy_public: self.antientropy_public.unwrap_or(true),
             private_neighbors: self.private_neighbors.unwrap_or(true),
             block_proposal_token: self.block_proposal_token,
+            json_error_responses: self.json_error_responses.unwrap_or(false),
+            slow_request_threshold_ms: self.slow_request_threshold_ms.unwrap_or(0),
+            max_pings_per_sec: self.max_pings_per_sec.unwrap_or(0),
+            max_inv_requests_per_sec: self.max_inv_requests_per_sec.unwrap_or(0),
+            max_rate_limited_msgs: self.max_rate_limited_msgs.unwrap_or(0),
+            outbound_only: self.outbound_only.unwrap_or(false),
+            walk_seed: self.walk_seed,
+            seen_cache_size: self.seen_cache_size.unwrap_or(1024),
+            seen_cache_ttl: self.seen_cache_ttl.unwrap_or(300),
+            max_requests_per_handle_per_pass: self.max_requests_per_handle_per_pass.unwrap_or(16),
+            contract_analysis_endpoint: self.contract_analysis_endpoint.unwrap_or(false),
+            maximum_contract_analysis_size: self
+                .maximum_contract_analysis_size
+                .unwrap_or(128 * 1024),
             ..ConnectionOptions::default()
         })
     }
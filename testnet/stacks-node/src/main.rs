@@ -303,16 +303,32 @@ fn main() {
         );
     }
 
+    let mut initial_balances_path: Option<String> = None;
+
     let config_file = match subcommand.as_str() {
         "mocknet" => {
+            initial_balances_path = args
+                .opt_value_from_str("--balances")
+                .expect("Failed to parse --balances argument");
             args.finish();
             ConfigFile::mocknet()
         }
         "helium" => {
+            initial_balances_path = args
+                .opt_value_from_str("--balances")
+                .expect("Failed to parse --balances argument");
             args.finish();
             ConfigFile::helium()
         }
         "testnet" => {
+            // extra genesis balances would give this node a different boot state than the public
+            // testnet, so it could never sync
+            if args.contains("--balances") {
+                warn!(
+                    "--balances is not supported by the testnet subcommand; use mocknet or helium"
+                );
+                process::exit(1);
+            }
             args.finish();
             ConfigFile::xenon()
         }
@@ -416,7 +432,7 @@ fn main() {
         }
     };
 
-    let conf = match Config::from_config_file(config_file) {
+    let mut conf = match Config::from_config_file(config_file) {
         Ok(conf) => conf,
         Err(e) => {
             warn!("Invalid config: {}", e);
@@ -424,6 +440,13 @@ fn main() {
         }
     };
 
+    if let Some(path) = initial_balances_path {
+        if let Err(e) = conf.load_initial_balances_from_file(&path) {
+            warn!("Invalid initial balances: {}", e);
+            process::exit(1);
+        }
+    }
+
     debug!("node configuration {:?}", &conf.node);
     debug!("burnchain configuration {:?}", &conf.burnchain);
     debug!("connection configuration {:?}", &conf.connection_options);
@@ -474,6 +497,8 @@ SUBCOMMANDS:
 mainnet\t\tStart a node that will join and stream blocks from the public mainnet.
 
 mocknet\t\tStart a node based on a fast local setup emulating a burnchain. Ideal for smart contract development. 
+\t\tArguments:
+\t\t  --balances: optional path of a JSON or CSV file of `address,amount` initial balances to add.
 
 helium\t\tStart a node based on a local setup relying on a local instance of bitcoind.
 \t\tThe following bitcoin.conf is expected:
@@ -483,6 +508,8 @@ helium\t\tStart a node based on a local setup relying on a local instance of bit
 \t\t  server=1
 \t\t  rpcuser=helium
 \t\t  rpcpassword=helium
+\t\tArguments:
+\t\t  --balances: optional path of a JSON or CSV file of `address,amount` initial balances to add.
 
 testnet\t\tStart a node that will join and stream blocks from the public testnet, relying on Bitcoin Testnet.
 
@@ -34,7 +34,7 @@ use stacks_common::util::hash::Sha256Sum;
 
 use crate::burnchains::make_bitcoin_indexer;
 use crate::nakamoto_node::relayer::RelayerDirective;
-use crate::neon_node::open_chainstate_with_faults;
+use crate::neon_node::{open_chainstate_with_faults, spawn_data_url_check};
 use crate::run_loop::nakamoto::{Globals, RunLoop};
 use crate::{Config, EventDispatcher};
 
@@ -130,6 +130,9 @@ impl PeerThread {
         // set termination flag so other threads die
         self.globals.signal_stop();
 
+        // invalidate all network handles, so no one waits on this thread
+        self.net.shutdown();
+
         // thread exited, so signal to the relayer thread to die.
         while let Err(TrySendError::Full(_)) =
             self.globals.relay_send.try_send(RelayerDirective::Exit)
@@ -184,6 +187,7 @@ impl PeerThread {
 
         net.bind(&p2p_sock, &rpc_sock)
             .expect("BUG: PeerNetwork could not bind or is already bound");
+        spawn_data_url_check(&net);
 
         let poll_timeout = cmp::min(5000, config.miner.first_attempt_time_ms / 2);
 
@@ -4071,6 +4071,42 @@ impl ParentStacksBlockInfo {
     }
 }
 
+/// Check, in the background, that our advertised data URL reaches our own HTTP server, and log
+/// the outcome.  A bad data URL won't stop this node from working, but it will stop other nodes
+/// from downloading blocks from it.
+pub(crate) fn spawn_data_url_check(net: &PeerNetwork) {
+    let local_peer = net.get_local_peer();
+    let data_url = local_peer.data_url.clone();
+    let public_key_hash =
+        Hash160::from_node_public_key(&StacksPublicKey::from_private(&local_peer.private_key));
+    let timeout = Duration::from_secs(net.connection_opts.connect_timeout);
+
+    let res = thread::Builder::new()
+        .name(format!("data-url-check-{}", &data_url))
+        .spawn(move || {
+            match PeerNetwork::check_data_url_identity(&data_url, &public_key_hash, timeout) {
+                Ok(true) => {
+                    info!("Data URL {} reaches this node", &data_url);
+                }
+                Ok(false) => {
+                    warn!(
+                        "Data URL {} reaches a different node; peers will not be able to download blocks from this node",
+                        &data_url
+                    );
+                }
+                Err(e) => {
+                    warn!(
+                        "Data URL {} is not reachable ({:?}); peers will not be able to download blocks from this node",
+                        &data_url, &e
+                    );
+                }
+            }
+        });
+    if let Err(e) = res {
+        warn!("Failed to spawn data URL check thread: {:?}", &e);
+    }
+}
+
 /// Thread that runs the network state machine, handling both p2p and http requests.
 pub struct PeerThread {
     /// Node config
@@ -4170,6 +4206,7 @@ impl PeerThread {
 
         net.bind(&p2p_sock, &rpc_sock)
             .expect("BUG: PeerNetwork could not bind or is already bound");
+        spawn_data_url_check(&net);
 
         let poll_timeout = cmp::min(5000, config.miner.first_attempt_time_ms / 2);
 
@@ -4708,6 +4745,9 @@ impl StacksNode {
         // set termination flag so other threads die
         p2p_thread.globals.signal_stop();
 
+        // invalidate all network handles, so no one waits on this thread
+        p2p_thread.with_network(|_, net| net.shutdown());
+
         // thread exited, so signal to the relayer thread to die.
         while let Err(TrySendError::Full(_)) = p2p_thread
             .globals
@@ -5,6 +5,7 @@ pub mod neon;
 
 use clarity::vm::costs::ExecutionCost;
 use clarity::vm::database::BurnStateDB;
+use clarity::vm::events::StacksTransactionEvent;
 use stacks::burnchains::{PoxConstants, Txid};
 use stacks::chainstate::stacks::db::StacksChainState;
 use stacks::chainstate::stacks::events::StacksTransactionReceipt;
@@ -43,6 +44,7 @@ pub struct RunLoopCallbacks {
     on_new_stacks_chain_state:
         Option<fn(u64, &BurnchainTip, &ChainTip, &mut StacksChainState, &dyn BurnStateDB)>,
     on_new_tenure: Option<fn(u64, &BurnchainTip, &ChainTip, &mut Tenure)>,
+    on_event: Option<Box<dyn Fn(u64, &ChainTip, &StacksTransactionEvent) + Send>>,
 }
 
 impl RunLoopCallbacks {
@@ -52,6 +54,7 @@ impl RunLoopCallbacks {
             on_new_burn_chain_state: None,
             on_new_stacks_chain_state: None,
             on_new_tenure: None,
+            on_event: None,
         }
     }
 
@@ -74,6 +77,18 @@ impl RunLoopCallbacks {
         self.on_new_tenure = Some(callback);
     }
 
+    /// Register a callback for every event emitted by the transactions of each newly processed
+    /// Stacks block. Events are delivered in transaction order within the block, and in emission
+    /// order within a transaction, before the block's `on_new_stacks_chain_state` callback runs.
+    /// Callbacks that only care about some kinds of events should match on the event and ignore
+    /// the rest. Unlike the other callbacks, this one may capture state, e.g. to count events.
+    pub fn on_event<F>(&mut self, callback: F)
+    where
+        F: Fn(u64, &ChainTip, &StacksTransactionEvent) + Send + 'static,
+    {
+        self.on_event = Some(Box::new(callback));
+    }
+
     pub fn invoke_burn_chain_initialized(&self, burnchain: &mut Box<dyn BurnchainController>) {
         if let Some(cb) = self.on_burn_chain_initialized {
             cb(burnchain);
@@ -130,6 +145,14 @@ impl RunLoopCallbacks {
             }
         }
 
+        if let Some(cb) = self.on_event.as_ref() {
+            for receipt in chain_tip.receipts.iter() {
+                for event in receipt.events.iter() {
+                    cb(round, chain_tip, event);
+                }
+            }
+        }
+
         if let Some(cb) = self.on_new_stacks_chain_state {
             cb(round, burnchain_tip, chain_tip, chain_state, burn_dbconn);
         }
@@ -1,14 +1,17 @@
 use std::collections::HashMap;
 use std::fmt::Write;
-use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 
 use clarity::vm::analysis::contract_interface_builder::{
     build_contract_interface, ContractInterface,
 };
 use clarity::vm::analysis::mem_type_check;
 use clarity::vm::costs::ExecutionCost;
+use clarity::vm::database::BurnStateDB;
+use clarity::vm::events::{STXEventType, StacksTransactionEvent};
 use clarity::vm::types::{
-    QualifiedContractIdentifier, ResponseData, StacksAddressExtensions, TupleData,
+    PrincipalData, QualifiedContractIdentifier, ResponseData, StacksAddressExtensions, TupleData,
 };
 use clarity::vm::{ClarityVersion, Value};
 use lazy_static::lazy_static;
@@ -41,6 +44,7 @@ use super::{
 };
 use crate::config::InitialBalance;
 use crate::helium::RunLoop;
+use crate::node::ChainTip;
 use crate::tests::make_sponsored_stacks_transfer_on_testnet;
 
 const OTHER_CONTRACT: &'static str = "
@@ -1241,6 +1245,35 @@ fn contract_stx_transfer() {
             return;
         });
 
+    let faucet_fundings = Arc::new(AtomicU64::new(0));
+    let faucet_spouts = Arc::new(AtomicU64::new(0));
+    let observed_fundings = faucet_fundings.clone();
+    let observed_spouts = faucet_spouts.clone();
+    run_loop
+        .callbacks
+        .on_event(move |_round, _chain_tip, event| {
+            let StacksTransactionEvent::STXEvent(STXEventType::STXTransferEvent(transfer)) = event
+            else {
+                return;
+            };
+            let contract_identifier: PrincipalData = QualifiedContractIdentifier::parse(&format!(
+                "{}.{}",
+                to_addr(&StacksPrivateKey::from_hex(SK_1).unwrap()).to_string(),
+                "faucet"
+            ))
+            .unwrap()
+            .into();
+            // every transfer either funds the faucet with 1000 uSTX or is a 1 uSTX spout
+            if transfer.recipient == contract_identifier {
+                assert_eq!(transfer.amount, 1000);
+                observed_fundings.fetch_add(1, Ordering::SeqCst);
+            } else {
+                assert_eq!(transfer.sender, contract_identifier);
+                assert_eq!(transfer.amount, 1);
+                observed_spouts.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+
     run_loop.callbacks.on_new_stacks_chain_state(
         |round, _burnchain_tip, chain_tip, chain_state, burn_dbconn| {
             let contract_identifier = QualifiedContractIdentifier::parse(&format!(
@@ -1255,49 +1288,18 @@ fn contract_stx_transfer() {
                     assert!(chain_tip.metadata.stacks_block_height == 2);
                     // Block #1 should have 2 txs -- coinbase + transfer
                     assert_eq!(chain_tip.block.txs.len(), 2);
-
-                    let cur_tip = (
-                        chain_tip.metadata.consensus_hash.clone(),
-                        chain_tip.metadata.anchored_header.block_hash(),
-                    );
                     // check that 1000 stx _was_ transfered to the contract principal
-                    assert_eq!(
-                        chain_state
-                            .with_read_only_clarity_tx(
-                                burn_dbconn,
-                                &StacksBlockHeader::make_index_block_hash(&cur_tip.0, &cur_tip.1),
-                                |conn| {
-                                    conn.with_clarity_db_readonly(|db| {
-                                        db.get_account_stx_balance(
-                                            &contract_identifier.clone().into(),
-                                        )
-                                        .unwrap()
-                                        .amount_unlocked()
-                                    })
-                                }
-                            )
-                            .unwrap(),
-                        1000
+                    assert_stx_balance(
+                        chain_state,
+                        burn_dbconn,
+                        chain_tip,
+                        contract_identifier.clone(),
+                        1000,
                     );
                     // check that 1000 stx _was_ debited from SK_3
                     let sk_3 = StacksPrivateKey::from_hex(SK_3).unwrap();
-                    let addr_3 = to_addr(&sk_3).into();
-                    assert_eq!(
-                        chain_state
-                            .with_read_only_clarity_tx(
-                                burn_dbconn,
-                                &StacksBlockHeader::make_index_block_hash(&cur_tip.0, &cur_tip.1),
-                                |conn| {
-                                    conn.with_clarity_db_readonly(|db| {
-                                        db.get_account_stx_balance(&addr_3)
-                                            .unwrap()
-                                            .amount_unlocked()
-                                    })
-                                }
-                            )
-                            .unwrap(),
-                        98990
-                    );
+                    let addr_3 = to_addr(&sk_3);
+                    assert_stx_balance(chain_state, burn_dbconn, chain_tip, addr_3, 98990);
                 }
                 2 => {
                     assert!(chain_tip.metadata.stacks_block_height == 3);
@@ -1311,47 +1313,17 @@ fn contract_stx_transfer() {
                     assert_eq!(chain_tip.block.txs.len(), 2);
 
                     // check that 1 stx was transfered to SK_2 via the contract-call
-                    let cur_tip = (
-                        chain_tip.metadata.consensus_hash.clone(),
-                        chain_tip.metadata.anchored_header.block_hash(),
-                    );
 
                     let sk_2 = StacksPrivateKey::from_hex(SK_2).unwrap();
-                    let addr_2 = to_addr(&sk_2).into();
-                    assert_eq!(
-                        chain_state
-                            .with_read_only_clarity_tx(
-                                burn_dbconn,
-                                &StacksBlockHeader::make_index_block_hash(&cur_tip.0, &cur_tip.1),
-                                |conn| {
-                                    conn.with_clarity_db_readonly(|db| {
-                                        db.get_account_stx_balance(&addr_2)
-                                            .unwrap()
-                                            .amount_unlocked()
-                                    })
-                                }
-                            )
-                            .unwrap(),
-                        991
-                    );
-
-                    assert_eq!(
-                        chain_state
-                            .with_read_only_clarity_tx(
-                                burn_dbconn,
-                                &StacksBlockHeader::make_index_block_hash(&cur_tip.0, &cur_tip.1),
-                                |conn| {
-                                    conn.with_clarity_db_readonly(|db| {
-                                        db.get_account_stx_balance(
-                                            &contract_identifier.clone().into(),
-                                        )
-                                        .unwrap()
-                                        .amount_unlocked()
-                                    })
-                                }
-                            )
-                            .unwrap(),
-                        999
+                    let addr_2 = to_addr(&sk_2);
+                    assert_stx_balance(chain_state, burn_dbconn, chain_tip, addr_2, 991);
+
+                    assert_stx_balance(
+                        chain_state,
+                        burn_dbconn,
+                        chain_tip,
+                        contract_identifier.clone(),
+                        999,
                     );
                 }
                 4 => {
@@ -1362,49 +1334,18 @@ fn contract_stx_transfer() {
                         "Should have 1 coinbase tx and MAXIMUM_MEMPOOL_TX_CHAINING transfers"
                     );
 
-                    let cur_tip = (
-                        chain_tip.metadata.consensus_hash.clone(),
-                        chain_tip.metadata.anchored_header.block_hash(),
-                    );
-
                     // check that 1000 stx were sent to the contract
-                    assert_eq!(
-                        chain_state
-                            .with_read_only_clarity_tx(
-                                burn_dbconn,
-                                &StacksBlockHeader::make_index_block_hash(&cur_tip.0, &cur_tip.1),
-                                |conn| {
-                                    conn.with_clarity_db_readonly(|db| {
-                                        db.get_account_stx_balance(
-                                            &contract_identifier.clone().into(),
-                                        )
-                                        .unwrap()
-                                        .amount_unlocked()
-                                    })
-                                }
-                            )
-                            .unwrap(),
-                        25999
+                    assert_stx_balance(
+                        chain_state,
+                        burn_dbconn,
+                        chain_tip,
+                        contract_identifier.clone(),
+                        25999,
                     );
                     // check that 1000 stx _was_ debited from SK_3
                     let sk_3 = StacksPrivateKey::from_hex(SK_3).unwrap();
-                    let addr_3 = to_addr(&sk_3).into();
-                    assert_eq!(
-                        chain_state
-                            .with_read_only_clarity_tx(
-                                burn_dbconn,
-                                &StacksBlockHeader::make_index_block_hash(&cur_tip.0, &cur_tip.1),
-                                |conn| {
-                                    conn.with_clarity_db_readonly(|db| {
-                                        db.get_account_stx_balance(&addr_3)
-                                            .unwrap()
-                                            .amount_unlocked()
-                                    })
-                                }
-                            )
-                            .unwrap(),
-                        68990
-                    );
+                    let addr_3 = to_addr(&sk_3);
+                    assert_stx_balance(chain_state, burn_dbconn, chain_tip, addr_3, 68990);
                 }
 
                 _ => {}
@@ -1413,6 +1354,14 @@ fn contract_stx_transfer() {
     );
 
     run_loop.start(num_rounds).unwrap();
+
+    // one funding transfer in round 1, one spout in round 3, and a chain of funding transfers in
+    // round 4
+    assert_eq!(
+        faucet_fundings.load(Ordering::SeqCst),
+        1 + MAXIMUM_MEMPOOL_TX_CHAINING
+    );
+    assert_eq!(faucet_spouts.load(Ordering::SeqCst), 1);
 }
 
 #[test]
@@ -1537,27 +1486,12 @@ fn mine_transactions_out_of_order() {
                     assert_eq!(chain_tip.block.txs.len(), 5);
 
                     // check that 1000 stx _was_ transfered to the contract principal
-                    let curr_tip = (
-                        chain_tip.metadata.consensus_hash.clone(),
-                        chain_tip.metadata.anchored_header.block_hash(),
-                    );
-                    assert_eq!(
-                        chain_state
-                            .with_read_only_clarity_tx(
-                                burn_dbconn,
-                                &StacksBlockHeader::make_index_block_hash(&curr_tip.0, &curr_tip.1),
-                                |conn| {
-                                    conn.with_clarity_db_readonly(|db| {
-                                        db.get_account_stx_balance(
-                                            &contract_identifier.clone().into(),
-                                        )
-                                        .unwrap()
-                                        .amount_unlocked()
-                                    })
-                                }
-                            )
-                            .unwrap(),
-                        3000
+                    assert_stx_balance(
+                        chain_state,
+                        burn_dbconn,
+                        chain_tip,
+                        contract_identifier.clone(),
+                        3000,
                     );
                 }
                 _ => {}
@@ -1568,6 +1502,37 @@ fn mine_transactions_out_of_order() {
     run_loop.start(num_rounds).unwrap();
 }
 
+/// Assert that `principal` has `expected` unlocked uSTX at `chain_tip`
+fn assert_stx_balance<P: Into<PrincipalData>>(
+    chain_state: &mut StacksChainState,
+    burn_dbconn: &dyn BurnStateDB,
+    chain_tip: &ChainTip,
+    principal: P,
+    expected: u128,
+) {
+    let principal = principal.into();
+    let balance = chain_state
+        .with_read_only_clarity_tx(
+            burn_dbconn,
+            &chain_tip.metadata.index_block_hash(),
+            |conn| {
+                conn.with_clarity_db_readonly(|db| {
+                    db.get_account_stx_balance(&principal)
+                        .unwrap()
+                        .amount_unlocked()
+                })
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        balance,
+        expected,
+        "Unexpected STX balance for {} at block {}",
+        principal,
+        chain_tip.metadata.index_block_hash()
+    );
+}
+
 /// Test mining a smart contract twice (in non-sequential blocks)
 ///   this can happen in the testnet leader if they get "behind"
 ///   the burnchain and a previously mined block doesn't get included
@@ -1792,49 +1757,18 @@ fn bad_contract_tx_rollback() {
                     assert!(chain_tip.metadata.stacks_block_height == 2);
                     // Block #1 should have 2 txs -- coinbase + transfer
                     assert_eq!(chain_tip.block.txs.len(), 2);
-
-                    let cur_tip = (
-                        chain_tip.metadata.consensus_hash.clone(),
-                        chain_tip.metadata.anchored_header.block_hash(),
-                    );
                     // check that 1000 stx _was_ transfered to the contract principal
-                    assert_eq!(
-                        chain_state
-                            .with_read_only_clarity_tx(
-                                burn_dbconn,
-                                &StacksBlockHeader::make_index_block_hash(&cur_tip.0, &cur_tip.1),
-                                |conn| {
-                                    conn.with_clarity_db_readonly(|db| {
-                                        db.get_account_stx_balance(
-                                            &contract_identifier.clone().into(),
-                                        )
-                                        .unwrap()
-                                        .amount_unlocked()
-                                    })
-                                }
-                            )
-                            .unwrap(),
-                        1000
+                    assert_stx_balance(
+                        chain_state,
+                        burn_dbconn,
+                        chain_tip,
+                        contract_identifier.clone(),
+                        1000,
                     );
                     // check that 1000 stx _was_ debited from SK_3
                     let sk_3 = StacksPrivateKey::from_hex(SK_3).unwrap();
-                    let addr_3 = to_addr(&sk_3).into();
-                    assert_eq!(
-                        chain_state
-                            .with_read_only_clarity_tx(
-                                burn_dbconn,
-                                &StacksBlockHeader::make_index_block_hash(&cur_tip.0, &cur_tip.1),
-                                |conn| {
-                                    conn.with_clarity_db_readonly(|db| {
-                                        db.get_account_stx_balance(&addr_3)
-                                            .unwrap()
-                                            .amount_unlocked()
-                                    })
-                                }
-                            )
-                            .unwrap(),
-                        98990
-                    );
+                    let addr_3 = to_addr(&sk_3);
+                    assert_stx_balance(chain_state, burn_dbconn, chain_tip, addr_3, 98990);
                 }
                 2 => {
                     assert_eq!(chain_tip.metadata.stacks_block_height, 3);
@@ -7,6 +7,7 @@ use clarity::vm::types::{PrincipalData, QualifiedContractIdentifier, StandardPri
 use clarity::vm::Value;
 use lazy_static::lazy_static;
 use stacks::chainstate::stacks::db::blocks::MemPoolRejection;
+use stacks::chainstate::stacks::db::StacksChainState;
 use stacks::chainstate::stacks::{
     Error as ChainstateError, StacksBlockHeader, StacksMicroblockHeader, StacksPrivateKey,
     StacksPublicKey, StacksTransaction, StacksTransactionSigner, TokenTransferMemo,
@@ -386,8 +387,10 @@ fn mempool_setup_chainstate() {
                     )
                     .unwrap_err();
                 eprintln!("Err: {:?}", e);
-                assert!(if let MemPoolRejection::FeeTooLow(0, _) = e {
-                    true
+                let expected_fee =
+                    StacksChainState::get_minimum_mempool_tx_fee(tx_bytes.len() as u64);
+                assert!(if let MemPoolRejection::FeeTooLow(0, minimum_fee) = e {
+                    minimum_fee == expected_fee
                 } else {
                     false
                 });
@@ -629,7 +632,7 @@ fn mempool_setup_chainstate() {
                     &contract_addr,
                     "foo_contract",
                     "bar",
-                    &[Value::UInt(1), Value::Int(2)],
+                    &[Value::Int(2)],
                 );
                 let tx =
                     StacksTransaction::consensus_deserialize(&mut tx_bytes.as_slice()).unwrap();
@@ -649,6 +652,69 @@ fn mempool_setup_chainstate() {
                     false
                 });
 
+                // wrong number of arguments
+                let tx_bytes = make_contract_call(
+                    &contract_sk,
+                    5,
+                    200,
+                    &contract_addr,
+                    "foo_contract",
+                    "bar",
+                    &[],
+                );
+                let tx =
+                    StacksTransaction::consensus_deserialize(&mut tx_bytes.as_slice()).unwrap();
+                let e = chain_state
+                    .will_admit_mempool_tx(
+                        &NULL_BURN_STATE_DB,
+                        consensus_hash,
+                        block_hash,
+                        &tx,
+                        tx_bytes.len() as u64,
+                    )
+                    .unwrap_err();
+                eprintln!("Err: {:?}", e);
+                assert!(if let MemPoolRejection::BadFunctionArity {
+                    expected: 1,
+                    got: 0,
+                } = e
+                {
+                    true
+                } else {
+                    false
+                });
+
+                let tx_bytes = make_contract_call(
+                    &contract_sk,
+                    5,
+                    200,
+                    &contract_addr,
+                    "foo_contract",
+                    "bar",
+                    &[Value::UInt(1), Value::Int(2)],
+                );
+                let tx =
+                    StacksTransaction::consensus_deserialize(&mut tx_bytes.as_slice()).unwrap();
+                let e = chain_state
+                    .will_admit_mempool_tx(
+                        &NULL_BURN_STATE_DB,
+                        consensus_hash,
+                        block_hash,
+                        &tx,
+                        tx_bytes.len() as u64,
+                    )
+                    .unwrap_err();
+                eprintln!("Err: {:?}", e);
+                assert!(if let MemPoolRejection::BadFunctionArity {
+                    expected: 1,
+                    got: 2,
+                } = e
+                {
+                    true
+                } else {
+                    false
+                });
+
                 let tx_bytes =
                     make_contract_publish(&contract_sk, 5, 1000, "foo_contract", FOO_CONTRACT);
                 let tx =